@@ -0,0 +1,91 @@
+package main
+
+import (
+	"fmt"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// ResolveMerged follows merges from an account ID to the account that ends up replacing it
+func (s *AccountState) ResolveMerged(id string) string {
+	if s == nil {
+		return id
+	}
+	for seen := 0; seen <= len(s.Merged); seen++ {
+		into, ok := s.Merged[id]
+		if !ok {
+			break
+		}
+		id = into
+	}
+	return id
+}
+
+// MergeAccount records that an account was replaced by another (e.g. a reissued card with a new
+// SimpleFin ID). Alerts reported for the old account are dropped and it is archived, so it only
+// reappears as part of the new account.
+func (s *AccountState) MergeAccount(oldID, newID string, now time.Time) error {
+	if s == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+	if oldID == newID || s.ResolveMerged(newID) == oldID {
+		return fmt.Errorf("cannot merge account %q into itself", oldID)
+	}
+
+	s.Merged[oldID] = newID
+	s.Archived[oldID] = now
+	delete(s.StaleAlerts, oldID)
+	delete(s.UtilizationAlerts, oldID)
+	return nil
+}
+
+// mergeReplacedAccounts folds the transactions of merged accounts into the accounts that replaced
+// them, so their history continues under the new ID. A merged account whose replacement wasn't
+// fetched takes the new ID itself. Transactions already present on the new account are skipped.
+func mergeReplacedAccounts(accounts []Account, state *AccountState) []Account {
+	if state == nil || len(state.Merged) == 0 {
+		return accounts
+	}
+
+	index := make(map[string]int)
+	for i, account := range accounts {
+		if _, merged := state.Merged[account.ID]; !merged {
+			index[account.ID] = i
+		}
+	}
+
+	var result []Account
+	var folded []Account
+	for _, account := range accounts {
+		if _, merged := state.Merged[account.ID]; merged {
+			folded = append(folded, account)
+			continue
+		}
+		result = append(result, account)
+	}
+
+	for _, old := range folded {
+		newID := state.ResolveMerged(old.ID)
+		i, ok := index[newID]
+		if !ok {
+			log.Debug().Str("old_account_id", old.ID).Str("new_account_id", newID).Msg("Replacement account not fetched, remapping the old account's ID")
+			old.ID = newID
+			index[newID] = len(result)
+			result = append(result, old)
+			continue
+		}
+
+		seen := make(map[string]bool)
+		for _, tx := range result[i].Transactions {
+			seen[tx.ID] = true
+		}
+		for _, tx := range old.Transactions {
+			if !seen[tx.ID] {
+				result[i].Transactions = append(result[i].Transactions, tx)
+			}
+		}
+		log.Debug().Str("old_account_id", old.ID).Str("new_account_id", newID).Int("transactions", len(old.Transactions)).Msg("Merged replaced account into its replacement")
+	}
+	return result
+}
@@ -0,0 +1,329 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// accountStateFile is the name of the JSON file holding per-account state inside the cache directory
+const accountStateFile = "account_state.json"
+
+// AccountState tracks per-account runtime state, such as accounts whose syncing is paused.
+// A nil *AccountState is valid and behaves as if no account has any state.
+type AccountState struct {
+	path              string
+	Paused            map[string]time.Time `json:"paused"`             // Account ID or name -> when syncing was paused
+	Archived          map[string]time.Time `json:"archived"`           // Account ID or name -> when the account was archived (e.g. closed)
+	Names             map[string]string    `json:"names"`              // Paused or archived account ID -> its name, to resume or unarchive it by name
+	StaleAlerts       map[string]int64     `json:"stale_alerts"`       // Account ID -> balance date already reported as stale
+	ConnectionErrors  map[string]time.Time `json:"connection_errors"`  // SimpleFin error message -> when it was first reported
+	DuplicateAlerts   map[string]time.Time `json:"duplicate_alerts"`   // Duplicate charge pair -> when it was reported
+	UtilizationAlerts map[string]time.Time `json:"utilization_alerts"` // Account ID -> when high credit utilization was reported
+	Merged            map[string]string    `json:"merged,omitempty"`   // Old account ID -> ID of the account that replaced it (e.g. a reissued card)
+	DigestSent        map[string]digestIDs `json:"digest_sent"`        // Account ID -> transactions already sent in a digest
+	WebhookBalances   map[string]Balance   `json:"webhook_balances"`   // Account ID -> balance at the last run, for account.balance_changed webhooks
+}
+
+// loadAccountState loads the account state from the cache directory, returning nil if no
+// cache directory is configured or the state can't be read or parsed
+func loadAccountState(cacheDir string) *AccountState {
+	if cacheDir == "" {
+		return nil
+	}
+
+	state := &AccountState{
+		path:              filepath.Join(cacheDir, accountStateFile),
+		Paused:            make(map[string]time.Time),
+		Archived:          make(map[string]time.Time),
+		Names:             make(map[string]string),
+		StaleAlerts:       make(map[string]int64),
+		ConnectionErrors:  make(map[string]time.Time),
+		DuplicateAlerts:   make(map[string]time.Time),
+		UtilizationAlerts: make(map[string]time.Time),
+		Merged:            make(map[string]string),
+		DigestSent:        make(map[string]digestIDs),
+		WebhookBalances:   make(map[string]Balance),
+	}
+
+	data, err := os.ReadFile(state.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return state
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("state_path", state.path).Msg("Failed to read account state, continuing without it")
+		return nil
+	}
+
+	// A partly decoded state saved back would drop the paused, archived and merged accounts that
+	// didn't load, so the file is left alone until it is fixed or removed
+	if err := json.Unmarshal(data, state); err != nil {
+		log.Warn().Err(err).Str("state_path", state.path).Msg("Failed to parse account state, continuing without it")
+		return nil
+	}
+	if state.Paused == nil {
+		state.Paused = make(map[string]time.Time)
+	}
+	if state.Archived == nil {
+		state.Archived = make(map[string]time.Time)
+	}
+	if state.Names == nil {
+		state.Names = make(map[string]string)
+	}
+	if state.StaleAlerts == nil {
+		state.StaleAlerts = make(map[string]int64)
+	}
+	if state.ConnectionErrors == nil {
+		state.ConnectionErrors = make(map[string]time.Time)
+	}
+	if state.DuplicateAlerts == nil {
+		state.DuplicateAlerts = make(map[string]time.Time)
+	}
+	if state.UtilizationAlerts == nil {
+		state.UtilizationAlerts = make(map[string]time.Time)
+	}
+	if state.Merged == nil {
+		state.Merged = make(map[string]string)
+	}
+	if state.DigestSent == nil {
+		state.DigestSent = make(map[string]digestIDs)
+	}
+	if state.WebhookBalances == nil {
+		state.WebhookBalances = make(map[string]Balance)
+	}
+
+	return state
+}
+
+// Save writes the account state to disk
+func (s *AccountState) Save() error {
+	if s == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(s, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling account state: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(s.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(s.path, data); err != nil {
+		return fmt.Errorf("error writing account state: %w", err)
+	}
+
+	return nil
+}
+
+// IsPaused reports whether syncing is paused for the account, matched by ID or name (case-insensitive)
+func (s *AccountState) IsPaused(account Account) bool {
+	if s == nil {
+		return false
+	}
+	return matchesAccount(s.Paused, account)
+}
+
+// IsArchived reports whether the account is archived, matched by ID or name (case-insensitive)
+func (s *AccountState) IsArchived(account Account) bool {
+	if s == nil {
+		return false
+	}
+	return matchesAccount(s.Archived, account)
+}
+
+// inactiveKeys returns the keys of the paused or archived map that refer to the account given by
+// the user, without fetching accounts: the key itself (an account ID, or a name stored by earlier
+// versions) or the name recorded for it, case-insensitive
+func (s *AccountState) inactiveKeys(keys map[string]time.Time, account string) []string {
+	var matched []string
+	for key := range keys {
+		if strings.EqualFold(key, account) || strings.EqualFold(s.Names[key], account) {
+			matched = append(matched, key)
+		}
+	}
+	return matched
+}
+
+// forgetName drops the name recorded for an account once it is neither paused nor archived
+func (s *AccountState) forgetName(key string) {
+	if _, paused := s.Paused[key]; paused {
+		return
+	}
+	if _, archived := s.Archived[key]; archived {
+		return
+	}
+	delete(s.Names, key)
+}
+
+// matchesAccount reports whether any key of the map is the account's ID or name (case-insensitive)
+func matchesAccount(keys map[string]time.Time, account Account) bool {
+	for key := range keys {
+		if keyMatchesAccount(key, account) {
+			return true
+		}
+	}
+	return false
+}
+
+// keyMatchesAccount reports whether a state key is the account's ID or name (case-insensitive)
+func keyMatchesAccount(key string, account Account) bool {
+	return key == account.ID || strings.EqualFold(key, account.Name)
+}
+
+// AlreadyAlertedStale reports whether the account was already reported as stale for its current balance date
+func (s *AccountState) AlreadyAlertedStale(account Account) bool {
+	if s == nil {
+		return false
+	}
+	balanceDate, ok := s.StaleAlerts[account.ID]
+	return ok && balanceDate == account.BalanceDate
+}
+
+// MarkStaleAlerted records that the account was reported as stale for its current balance date
+func (s *AccountState) MarkStaleAlerted(account Account) {
+	if s == nil {
+		return
+	}
+	s.StaleAlerts[account.ID] = account.BalanceDate
+}
+
+// ClearFreshAccounts forgets stale alerts for accounts that are no longer stale
+func (s *AccountState) ClearFreshAccounts(accounts []Account, stale []staleAccount) {
+	if s == nil {
+		return
+	}
+
+	staleIDs := make(map[string]bool, len(stale))
+	for _, st := range stale {
+		staleIDs[st.Account.ID] = true
+	}
+	for _, account := range accounts {
+		if !staleIDs[account.ID] {
+			delete(s.StaleAlerts, account.ID)
+		}
+	}
+}
+
+// NewConnectionErrors returns the SimpleFin errors that haven't been reported yet
+func (s *AccountState) NewConnectionErrors(apiErrors []string) []string {
+	if s == nil {
+		return apiErrors
+	}
+
+	var fresh []string
+	for _, apiErr := range apiErrors {
+		if _, ok := s.ConnectionErrors[apiErr]; !ok {
+			fresh = append(fresh, apiErr)
+		}
+	}
+	return fresh
+}
+
+// MarkConnectionErrorReported records that a SimpleFin error was reported
+func (s *AccountState) MarkConnectionErrorReported(apiErr string, now time.Time) {
+	if s == nil {
+		return
+	}
+	s.ConnectionErrors[apiErr] = now
+}
+
+// ClearResolvedConnectionErrors forgets errors the bridge no longer reports, so they are reported
+// again if they come back
+func (s *AccountState) ClearResolvedConnectionErrors(apiErrors []string) {
+	if s == nil {
+		return
+	}
+
+	current := make(map[string]bool, len(apiErrors))
+	for _, apiErr := range apiErrors {
+		current[apiErr] = true
+	}
+	for apiErr := range s.ConnectionErrors {
+		if !current[apiErr] {
+			delete(s.ConnectionErrors, apiErr)
+		}
+	}
+}
+
+// AlreadyAlertedDuplicate reports whether the duplicate charge was already reported
+func (s *AccountState) AlreadyAlertedDuplicate(d DuplicateCharge) bool {
+	if s == nil {
+		return false
+	}
+	_, ok := s.DuplicateAlerts[d.Key()]
+	return ok
+}
+
+// MarkDuplicateAlerted records that the duplicate charge was reported
+func (s *AccountState) MarkDuplicateAlerted(d DuplicateCharge, now time.Time) {
+	if s == nil {
+		return
+	}
+	s.DuplicateAlerts[d.Key()] = now
+}
+
+// PruneDuplicateAlerts forgets duplicate charges reported before the cutoff, which are past the
+// window of any fetch
+func (s *AccountState) PruneDuplicateAlerts(cutoff time.Time) {
+	if s == nil {
+		return
+	}
+	for key, reportedAt := range s.DuplicateAlerts {
+		if reportedAt.Before(cutoff) {
+			delete(s.DuplicateAlerts, key)
+		}
+	}
+}
+
+// AlreadyAlertedUtilization reports whether the account's high credit utilization was already reported
+func (s *AccountState) AlreadyAlertedUtilization(account Account) bool {
+	if s == nil {
+		return false
+	}
+	_, ok := s.UtilizationAlerts[account.ID]
+	return ok
+}
+
+// MarkUtilizationAlerted records that the account's high credit utilization was reported
+func (s *AccountState) MarkUtilizationAlerted(account Account, now time.Time) {
+	if s == nil {
+		return
+	}
+	s.UtilizationAlerts[account.ID] = now
+}
+
+// skipInactiveAccounts removes accounts whose syncing is paused and, unless includeArchived is
+// set, archived accounts
+func skipInactiveAccounts(accounts []Account, state *AccountState, includeArchived bool) []Account {
+	if state == nil || (len(state.Paused) == 0 && len(state.Archived) == 0) {
+		return accounts
+	}
+
+	var active []Account
+	for _, account := range accounts {
+		if state.IsPaused(account) {
+			log.Info().
+				Str("account_id", account.ID).
+				Str("account_name", account.Name).
+				Msg("⏸️ Skipping paused account")
+			continue
+		}
+		if !includeArchived && state.IsArchived(account) {
+			log.Debug().
+				Str("account_id", account.ID).
+				Str("account_name", account.Name).
+				Msg("Skipping archived account")
+			continue
+		}
+		active = append(active, account)
+	}
+	return active
+}
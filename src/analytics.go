@@ -0,0 +1,132 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+)
+
+// analyticsTopMerchants is the number of merchants listed in the computed analytics
+const analyticsTopMerchants = 5
+
+// SpendingTotal is the amount spent in a category or at a merchant
+type SpendingTotal struct {
+	Name     string
+	Total    Money
+	Count    int
+	Children []SpendingTotal // Subcategories rolled up into a category's total, largest first
+}
+
+// SpendingAnalytics holds figures computed from the expenses before they are sent to the LLM, so
+// the amounts in the summary are exact instead of the model's own arithmetic
+type SpendingAnalytics struct {
+	Total         Money
+	Count         int
+	Categories    []SpendingTotal // Categorized spending by top-level category, largest first
+	Uncategorized SpendingTotal   // Spending without a category, left for the LLM to classify
+	Merchants     []SpendingTotal // Top merchants, largest first
+	Largest       []Transaction   // Largest expenses, largest first
+}
+
+// computeAnalytics computes totals, category and merchant breakdowns and the largest expenses
+// from the given expenses (negative amounts). Amounts are reported as positive spending.
+func computeAnalytics(transactions []Transaction) SpendingAnalytics {
+	analytics := SpendingAnalytics{
+		Uncategorized: SpendingTotal{Name: "Uncategorized"},
+		Largest:       getTopExpenses(transactions, 10),
+	}
+
+	categories := make(map[string]*SpendingTotal)
+	subcategories := make(map[string]map[string]*SpendingTotal)
+	merchants := make(map[string]*SpendingTotal)
+	for _, tx := range transactions {
+		amount := -toMoney(tx.Amount)
+		analytics.Total += amount
+		analytics.Count++
+
+		if tx.Category == "" {
+			analytics.Uncategorized.Total += amount
+			analytics.Uncategorized.Count++
+		} else {
+			// "Dining > Coffee" rolls up into "Dining"
+			parent, child := splitCategory(tx.Category)
+			addSpending(categories, parent, amount)
+			if child != "" {
+				key := strings.ToLower(parent)
+				if subcategories[key] == nil {
+					subcategories[key] = make(map[string]*SpendingTotal)
+				}
+				addSpending(subcategories[key], child, amount)
+			}
+		}
+		addSpending(merchants, merchantName(tx), amount)
+	}
+
+	analytics.Categories = sortedSpending(categories)
+	for i := range analytics.Categories {
+		if children, ok := subcategories[strings.ToLower(analytics.Categories[i].Name)]; ok {
+			analytics.Categories[i].Children = sortedSpending(children)
+		}
+	}
+	analytics.Merchants = sortedSpending(merchants)
+	if len(analytics.Merchants) > analyticsTopMerchants {
+		analytics.Merchants = analytics.Merchants[:analyticsTopMerchants]
+	}
+	return analytics
+}
+
+// addSpending adds an amount to a case-insensitively keyed total, keeping the first spelling seen
+func addSpending(totals map[string]*SpendingTotal, name string, amount Money) {
+	key := strings.ToLower(name)
+	total, ok := totals[key]
+	if !ok {
+		total = &SpendingTotal{Name: name}
+		totals[key] = total
+	}
+	total.Total += amount
+	total.Count++
+}
+
+// sortedSpending returns the totals sorted by amount (largest first), then by name
+func sortedSpending(totals map[string]*SpendingTotal) []SpendingTotal {
+	sorted := make([]SpendingTotal, 0, len(totals))
+	for _, total := range totals {
+		sorted = append(sorted, *total)
+	}
+	sort.Slice(sorted, func(i, j int) bool {
+		if sorted[i].Total != sorted[j].Total {
+			return sorted[i].Total > sorted[j].Total
+		}
+		return sorted[i].Name < sorted[j].Name
+	})
+	return sorted
+}
+
+// formatAnalytics renders the computed analytics as a prompt section
+func formatAnalytics(analytics SpendingAnalytics, scope string) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "Computed Analytics (%s, exact figures):\n", scope)
+	fmt.Fprintf(&b, "- Total Spent: %s across %d transactions\n", analytics.Total.Format(), analytics.Count)
+
+	if len(analytics.Categories) > 0 || analytics.Uncategorized.Count > 0 {
+		b.WriteString("- Category Totals:\n")
+		for _, category := range analytics.Categories {
+			fmt.Fprintf(&b, "   - %s: %s (%d transactions)\n", category.Name, category.Total.Format(), category.Count)
+			for _, child := range category.Children {
+				fmt.Fprintf(&b, "      - %s: %s (%d transactions)\n", child.Name, child.Total.Format(), child.Count)
+			}
+		}
+		if analytics.Uncategorized.Count > 0 {
+			fmt.Fprintf(&b, "   - %s: %s (%d transactions)\n", analytics.Uncategorized.Name, analytics.Uncategorized.Total.Format(), analytics.Uncategorized.Count)
+		}
+	}
+
+	if len(analytics.Merchants) > 0 {
+		b.WriteString("- Top Merchants:\n")
+		for _, merchant := range analytics.Merchants {
+			fmt.Fprintf(&b, "   - %s: %s (%d transactions)\n", merchant.Name, merchant.Total.Format(), merchant.Count)
+		}
+	}
+
+	return b.String()
+}
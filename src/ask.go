@@ -0,0 +1,43 @@
+package main
+
+import (
+	"fmt"
+	"time"
+)
+
+// generateQuestionPrompt generates a prompt asking the LLM to answer a free-form question about
+// the period's transactions, with exact spending totals computed up front
+func generateQuestionPrompt(accounts []Account, startDate, endDate time.Time, question string) string {
+	var transactions, expenses []Transaction
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if tx.IsTransfer {
+				continue
+			}
+			transactions = append(transactions, tx)
+			if tx.Amount < 0 {
+				if net, ok := netExpense(tx); ok {
+					expenses = append(expenses, net)
+				}
+			}
+		}
+	}
+
+	return fmt.Sprintf(`## Question About My Finances
+%s
+
+Answer the question using only the data below, in a few sentences. Quote amounts from the
+Computed Analytics when they answer the question; otherwise add up only the amounts in the
+transactions table and say which transactions you included. If the data doesn't cover the question
+(e.g. it asks about dates outside the period), say so.%s
+
+Period: %s to %s
+
+%s
+Accounts Information:
+%s
+
+All Transactions (negative amounts are expenses, transfers between accounts are excluded):
+%s`, question, currentLocale.promptInstructions(), startDate.Format("2006-01-02"), endDate.Format("2006-01-02"),
+		formatAnalytics(computeAnalytics(expenses), "expenses in the period"), formatAccounts(accounts), formatTransactions(transactions))
+}
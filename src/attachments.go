@@ -0,0 +1,123 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// Attachment is a file (e.g. a receipt or invoice) stored for a transaction
+type Attachment struct {
+	TransactionID string
+	Name          string
+	Path          string
+	Size          int64
+}
+
+// attachmentDir returns the directory holding a transaction's attachments. Transaction IDs are
+// provider-defined, so path separators are replaced to keep the directory inside attachmentsDir.
+func attachmentDir(attachmentsDir string, transactionID string) string {
+	safeID := strings.NewReplacer("/", "_", "\\", "_", "..", "_").Replace(transactionID)
+	return filepath.Join(attachmentsDir, safeID)
+}
+
+// storeAttachment copies a file into the transaction's attachment directory, refusing to
+// overwrite an existing attachment with the same name
+func storeAttachment(attachmentsDir string, transactionID string, sourcePath string) (Attachment, error) {
+	if attachmentsDir == "" {
+		return Attachment{}, fmt.Errorf("attachments are unavailable (set ATTACHMENTS_DIR or CACHE_DIR)")
+	}
+
+	source, err := os.Open(sourcePath)
+	if err != nil {
+		return Attachment{}, fmt.Errorf("error opening %s: %w", sourcePath, err)
+	}
+	defer source.Close()
+
+	dir := attachmentDir(attachmentsDir, transactionID)
+	if err := os.MkdirAll(dir, 0o700); err != nil {
+		return Attachment{}, fmt.Errorf("error creating attachment directory: %w", err)
+	}
+
+	name := filepath.Base(sourcePath)
+	path := filepath.Join(dir, name)
+	target, err := os.OpenFile(path, os.O_WRONLY|os.O_CREATE|os.O_EXCL, 0o600)
+	if errors.Is(err, os.ErrExist) {
+		return Attachment{}, fmt.Errorf("transaction %q already has an attachment named %q", transactionID, name)
+	}
+	if err != nil {
+		return Attachment{}, fmt.Errorf("error creating attachment: %w", err)
+	}
+
+	size, err := io.Copy(target, source)
+	if closeErr := target.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		os.Remove(path)
+		return Attachment{}, fmt.Errorf("error copying attachment: %w", err)
+	}
+
+	return Attachment{TransactionID: transactionID, Name: name, Path: path, Size: size}, nil
+}
+
+// listAttachments returns the attachments of a transaction, or of every transaction when
+// transactionID is empty, sorted by transaction and name
+func listAttachments(attachmentsDir string, transactionID string) ([]Attachment, error) {
+	if attachmentsDir == "" {
+		return nil, nil
+	}
+
+	var dirs []string
+	if transactionID != "" {
+		dirs = []string{attachmentDir(attachmentsDir, transactionID)}
+	} else {
+		entries, err := os.ReadDir(attachmentsDir)
+		if errors.Is(err, os.ErrNotExist) {
+			return nil, nil
+		}
+		if err != nil {
+			return nil, fmt.Errorf("error reading attachments directory: %w", err)
+		}
+		for _, entry := range entries {
+			if entry.IsDir() {
+				dirs = append(dirs, filepath.Join(attachmentsDir, entry.Name()))
+			}
+		}
+	}
+
+	var attachments []Attachment
+	for _, dir := range dirs {
+		entries, err := os.ReadDir(dir)
+		if errors.Is(err, os.ErrNotExist) {
+			continue
+		}
+		if err != nil {
+			return nil, fmt.Errorf("error reading attachments: %w", err)
+		}
+		for _, entry := range entries {
+			info, err := entry.Info()
+			if err != nil || !info.Mode().IsRegular() {
+				continue
+			}
+			attachments = append(attachments, Attachment{
+				TransactionID: filepath.Base(dir),
+				Name:          entry.Name(),
+				Path:          filepath.Join(dir, entry.Name()),
+				Size:          info.Size(),
+			})
+		}
+	}
+
+	sort.Slice(attachments, func(i, j int) bool {
+		if attachments[i].TransactionID != attachments[j].TransactionID {
+			return attachments[i].TransactionID < attachments[j].TransactionID
+		}
+		return attachments[i].Name < attachments[j].Name
+	})
+	return attachments, nil
+}
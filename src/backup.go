@@ -0,0 +1,132 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// backupVersion is the archive format version written by createBackup
+const backupVersion = 1
+
+// backupFiles are the state files in the cache directory included in backups
+var backupFiles = []string{
+	summaryCacheFile,
+	accountStateFile,
+	runHistoryFile,
+	transactionNotesFile,
+	transactionRevisionsFile,
+	reconciliationFile,
+	manualAccountsFile,
+	netWorthHistoryFile,
+	organizationsFile,
+	llmCategoriesFile,
+	notificationLogFile,
+	pendingWarningsFile,
+}
+
+// BackupArchive is a versioned snapshot of the local state files
+type BackupArchive struct {
+	Version   int                        `json:"version"`
+	CreatedAt time.Time                  `json:"created_at"`
+	Files     map[string]json.RawMessage `json:"files"` // File name -> file contents
+}
+
+// createBackup writes every existing state file in the cache directory to w as a single archive
+func createBackup(cacheDir string, w io.Writer) (int, error) {
+	if cacheDir == "" {
+		return 0, fmt.Errorf("no cache directory configured")
+	}
+
+	archive := BackupArchive{
+		Version:   backupVersion,
+		CreatedAt: time.Now().UTC(),
+		Files:     make(map[string]json.RawMessage),
+	}
+
+	for _, name := range backupFiles {
+		path := filepath.Join(cacheDir, name)
+		// Files that were never written (or a cache directory that doesn't exist yet) have nothing
+		// to back up, and no directory to hold their lock file
+		if _, err := os.Stat(path); errors.Is(err, os.ErrNotExist) {
+			continue
+		}
+		unlock, err := lockFile(path)
+		if err != nil {
+			return 0, fmt.Errorf("error locking %s: %w", name, err)
+		}
+		data, err := os.ReadFile(path)
+		unlock()
+
+		if errors.Is(err, os.ErrNotExist) {
+			continue
+		}
+		if err != nil {
+			return 0, fmt.Errorf("error reading %s: %w", name, err)
+		}
+		if !json.Valid(data) {
+			return 0, fmt.Errorf("%s is not valid JSON, refusing to back it up", name)
+		}
+		archive.Files[name] = data
+	}
+
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	if err := encoder.Encode(archive); err != nil {
+		return 0, fmt.Errorf("error writing backup: %w", err)
+	}
+
+	return len(archive.Files), nil
+}
+
+// restoreBackup writes the state files from an archive into the cache directory. Existing files
+// are only replaced when overwrite is set.
+func restoreBackup(cacheDir string, r io.Reader, overwrite bool) (int, error) {
+	if cacheDir == "" {
+		return 0, fmt.Errorf("no cache directory configured")
+	}
+
+	var archive BackupArchive
+	if err := json.NewDecoder(r).Decode(&archive); err != nil {
+		return 0, fmt.Errorf("error reading backup: %w", err)
+	}
+	if archive.Version < 1 || archive.Version > backupVersion {
+		return 0, fmt.Errorf("unsupported backup version %d (this build supports up to %d)", archive.Version, backupVersion)
+	}
+
+	known := make(map[string]bool, len(backupFiles))
+	for _, name := range backupFiles {
+		known[name] = true
+	}
+	for name := range archive.Files {
+		if !known[name] {
+			return 0, fmt.Errorf("unexpected file %q in backup", name)
+		}
+		if _, err := os.Stat(filepath.Join(cacheDir, name)); err == nil && !overwrite {
+			return 0, fmt.Errorf("%s already exists in %s (use --force to overwrite)", name, cacheDir)
+		}
+	}
+
+	if err := os.MkdirAll(cacheDir, 0o755); err != nil {
+		return 0, fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	for name, data := range archive.Files {
+		path := filepath.Join(cacheDir, name)
+		unlock, err := lockFile(path)
+		if err != nil {
+			return 0, fmt.Errorf("error locking %s: %w", name, err)
+		}
+		err = writeFileAtomic(path, data)
+		unlock()
+		if err != nil {
+			return 0, fmt.Errorf("error writing %s: %w", name, err)
+		}
+	}
+
+	return len(archive.Files), nil
+}
@@ -0,0 +1,205 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// summaryCacheFile is the name of the JSON file holding cached summaries inside the cache directory
+const summaryCacheFile = "summaries.json"
+
+// CachedSummary is an LLM summary stored for a period along with the hash of the prompt and models
+// that produced it
+type CachedSummary struct {
+	PromptHash string    `json:"prompt_hash"`
+	Summary    string    `json:"summary"`
+	CreatedAt  time.Time `json:"created_at"`
+}
+
+// Cache backends selectable with CACHE_BACKEND
+const (
+	CacheBackendJSON = "json" // JSON file in CACHE_DIR (default)
+	CacheBackendNone = "none" // Caching disabled
+)
+
+// CacheStore stores LLM summaries per period so unchanged prompts don't trigger new LLM calls
+type CacheStore interface {
+	// Lookup returns the cached summary for the period if it was generated from the same prompt
+	Lookup(period string, promptHash string) (string, bool)
+	// Store saves the summary for the period
+	Store(period string, promptHash string, summary string) error
+	// Clear removes every cached summary
+	Clear() error
+}
+
+// newCacheStore returns the cache backend selected in the settings
+func newCacheStore(settings *Settings) (CacheStore, error) {
+	switch settings.CacheBackend {
+	case CacheBackendNone:
+		return noCacheStore{}, nil
+	case CacheBackendJSON, "":
+		if settings.CacheDir == "" {
+			log.Debug().Msg("No cache directory configured, summary caching disabled")
+			return noCacheStore{}, nil
+		}
+		if cache := loadSummaryCache(settings.CacheDir); cache != nil {
+			return cache, nil
+		}
+		return noCacheStore{}, nil
+	default:
+		return nil, fmt.Errorf("unknown cache backend %q (supported: %s, %s)", settings.CacheBackend, CacheBackendJSON, CacheBackendNone)
+	}
+}
+
+// noCacheStore is a CacheStore that never stores anything
+type noCacheStore struct{}
+
+func (noCacheStore) Lookup(string, string) (string, bool) { return "", false }
+
+func (noCacheStore) Store(string, string, string) error { return nil }
+
+func (noCacheStore) Clear() error { return fmt.Errorf("summary caching is disabled") }
+
+// SummaryCache is the JSON file CacheStore. Writes are serialized with a lock file and merged
+// with the file's current contents, so overlapping runs don't lose each other's entries.
+// A nil *SummaryCache is valid and behaves as an always-empty cache.
+type SummaryCache struct {
+	path      string
+	Summaries map[string]CachedSummary `json:"summaries"`
+}
+
+// hashPrompt returns a content hash of the prompt and the models it is sent to, so a change to any
+// section of the prompt (balances, comparison, income, interest, filters) or to the models
+// regenerates the summary
+func hashPrompt(prompt, models string) string {
+	sum := sha256.Sum256([]byte(models + "\n" + prompt))
+	return hex.EncodeToString(sum[:])
+}
+
+// loadSummaryCache loads the summary cache from the cache directory, returning nil if the
+// cache can't be read
+func loadSummaryCache(cacheDir string) *SummaryCache {
+	cache := &SummaryCache{path: filepath.Join(cacheDir, summaryCacheFile)}
+	if err := cache.read(); err != nil {
+		log.Warn().Err(err).Str("cache_path", cache.path).Msg("Failed to read summary cache, continuing without it")
+		return nil
+	}
+
+	log.Debug().
+		Str("cache_path", cache.path).
+		Int("summary_count", len(cache.Summaries)).
+		Msg("Loaded summary cache")
+
+	return cache
+}
+
+// read replaces the in-memory summaries with the contents of the cache file
+func (c *SummaryCache) read() error {
+	c.Summaries = make(map[string]CachedSummary)
+
+	data, err := os.ReadFile(c.path)
+	if errors.Is(err, os.ErrNotExist) {
+		log.Debug().Str("cache_path", c.path).Msg("No summary cache found, starting fresh")
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+
+	if err := json.Unmarshal(data, c); err != nil {
+		log.Warn().Err(err).Str("cache_path", c.path).Msg("Failed to parse summary cache, starting fresh")
+	}
+	if c.Summaries == nil {
+		c.Summaries = make(map[string]CachedSummary)
+	}
+
+	return nil
+}
+
+// Lookup returns the cached summary for the period if it was generated from the same prompt
+func (c *SummaryCache) Lookup(period string, promptHash string) (string, bool) {
+	if c == nil {
+		return "", false
+	}
+
+	entry, ok := c.Summaries[period]
+	if !ok || entry.PromptHash != promptHash {
+		return "", false
+	}
+
+	return entry.Summary, true
+}
+
+// Store saves the summary for the period and writes the cache to disk
+func (c *SummaryCache) Store(period string, promptHash string, summary string) error {
+	if c == nil {
+		return nil
+	}
+
+	if err := os.MkdirAll(filepath.Dir(c.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	unlock, err := lockFile(c.path)
+	if err != nil {
+		return fmt.Errorf("error locking summary cache: %w", err)
+	}
+	defer unlock()
+
+	// Pick up entries written by other runs since the cache was loaded
+	if err := c.read(); err != nil {
+		return fmt.Errorf("error reading summary cache: %w", err)
+	}
+
+	c.Summaries[period] = CachedSummary{
+		PromptHash: promptHash,
+		Summary:    summary,
+		CreatedAt:  time.Now().UTC(),
+	}
+
+	data, err := json.MarshalIndent(c, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling summary cache: %w", err)
+	}
+
+	if err := writeFileAtomic(c.path, data); err != nil {
+		return fmt.Errorf("error writing summary cache: %w", err)
+	}
+
+	log.Debug().Str("cache_path", c.path).Str("period", period).Msg("Stored summary in cache")
+	return nil
+}
+
+// Clear removes the summary cache file
+func (c *SummaryCache) Clear() error {
+	if c == nil {
+		return fmt.Errorf("summary cache is unavailable")
+	}
+
+	// Without a cache directory there is nothing to clear, nor anywhere to put the lock file
+	if _, err := os.Stat(filepath.Dir(c.path)); errors.Is(err, os.ErrNotExist) {
+		c.Summaries = make(map[string]CachedSummary)
+		return nil
+	}
+
+	unlock, err := lockFile(c.path)
+	if err != nil {
+		return fmt.Errorf("error locking summary cache: %w", err)
+	}
+	defer unlock()
+
+	if err := os.Remove(c.path); err != nil && !errors.Is(err, os.ErrNotExist) {
+		return fmt.Errorf("error removing summary cache: %w", err)
+	}
+
+	c.Summaries = make(map[string]CachedSummary)
+	return nil
+}
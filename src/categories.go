@@ -0,0 +1,163 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"strings"
+
+	"github.com/rs/zerolog/log"
+)
+
+// providerCategoryKeys lists the keys in a transaction's "extra" data where providers
+// (e.g. Plaid-backed SimpleFin connections) report their own category assignment
+var providerCategoryKeys = []string{
+	"category",
+	"categories",
+	"personal_finance_category",
+}
+
+// providerCategories extracts provider-assigned categories from a transaction's extra data,
+// ordered from most to least specific
+func providerCategories(tx Transaction) []string {
+	if tx.Extra == nil {
+		return nil
+	}
+
+	var candidates []string
+	for _, key := range providerCategoryKeys {
+		value, ok := (*tx.Extra)[key]
+		if !ok {
+			continue
+		}
+
+		switch v := value.(type) {
+		case string:
+			candidates = append(candidates, v)
+		case []interface{}:
+			// Hierarchical categories (e.g. ["Food and Drink", "Restaurants", "Coffee Shop"])
+			var parts []string
+			for _, part := range v {
+				if s, ok := part.(string); ok && s != "" {
+					parts = append(parts, s)
+				}
+			}
+			if len(parts) > 1 {
+				candidates = append(candidates, strings.Join(parts, " > "))
+			}
+			for i := len(parts) - 1; i >= 0; i-- {
+				candidates = append(candidates, parts[i])
+			}
+		case map[string]interface{}:
+			// Structured categories (e.g. {"primary": "FOOD_AND_DRINK", "detailed": "FOOD_AND_DRINK_COFFEE"})
+			for _, field := range []string{"detailed", "primary"} {
+				if s, ok := v[field].(string); ok && s != "" {
+					candidates = append(candidates, s)
+				}
+			}
+		default:
+			log.Debug().
+				Str("transaction_id", tx.ID).
+				Str("key", key).
+				Str("type", fmt.Sprintf("%T", value)).
+				Msg("Unsupported provider category format")
+		}
+	}
+
+	return candidates
+}
+
+// CategoryRule assigns a category to the transactions matching a filter rule. Rules are applied
+// on every run, so they also recategorize past transactions.
+type CategoryRule struct {
+	Category   string `yaml:"category"`
+	FilterRule `yaml:",inline"`
+}
+
+// CategorySource identifies how a transaction's category was assigned
+type CategorySource string
+
+// Available category sources
+const (
+	CategorySourceManual   CategorySource = "manual"   // Set with recategorize, annotate --category or review --approve
+	CategorySourceRule     CategorySource = "rule"     // First matching category_rules entry
+	CategorySourceLearned  CategorySource = "learned"  // Learned from the user's corrections for the merchant
+	CategorySourceProvider CategorySource = "provider" // Mapped provider category
+	CategorySourceLLM      CategorySource = "llm"      // Assigned by the LLM with LLM_CATEGORIZE; confidence reported by the model
+)
+
+// Category confidence by source. Provider mappings lose confidence for each step the matched
+// provider category is less specific than the most specific one (e.g. a primary instead of a
+// detailed Plaid category).
+const (
+	manualConfidence       = 1.0
+	ruleConfidence         = 0.95
+	learnedConfidence      = 0.9
+	providerConfidence     = 0.9
+	providerConfidenceStep = 0.1
+	minProviderConfidence  = 0.5
+)
+
+// matchCategory returns the category for a transaction, where it came from and how confident the
+// assignment is: the category set by the user, the first matching category rule, the category
+// learned for the merchant, or the mapped provider category
+func matchCategory(tx Transaction, rules []CategoryRule, mappings map[string]string) (string, CategorySource, float64) {
+	if tx.UserCategory != "" {
+		return tx.UserCategory, CategorySourceManual, manualConfidence
+	}
+	for _, rule := range rules {
+		if matchesRule(ruleFieldValue(tx, rule.FilterRule), rule.FilterRule) {
+			return rule.Category, CategorySourceRule, ruleConfidence
+		}
+	}
+	if tx.LearnedCategory != "" {
+		return tx.LearnedCategory, CategorySourceLearned, learnedConfidence
+	}
+	for i, candidate := range providerCategories(tx) {
+		// Provider category matching is case-insensitive
+		if local, ok := mappings[strings.ToLower(strings.TrimSpace(candidate))]; ok {
+			return local, CategorySourceProvider, math.Max(providerConfidence-float64(i)*providerConfidenceStep, minProviderConfidence)
+		}
+	}
+	return "", "", 0
+}
+
+// applyProviderCategories assigns local categories to transactions from user-set categories,
+// category rules and the configured category preset and provider category mappings, prefixed with
+// their category_parents. Uncategorized transactions are left for the LLM to classify.
+func applyProviderCategories(transactions []Transaction, filterConfig *FilterConfig) int {
+	var rules []CategoryRule
+	var mappings, parents map[string]string
+	if filterConfig != nil {
+		rules = filterConfig.CategoryRules
+		mappings = providerCategoryMappings(filterConfig)
+		parents = categoryParents(filterConfig)
+	}
+
+	categorized := 0
+	for i := range transactions {
+		category, source, confidence := matchCategory(transactions[i], rules, mappings)
+		if category == "" {
+			continue
+		}
+		category = categoryPath(category, parents)
+		transactions[i].Category = category
+		transactions[i].CategorySource = source
+		transactions[i].Confidence = confidence
+		categorized++
+		log.Debug().
+			Str("description", transactions[i].Description).
+			Str("source", string(source)).
+			Float64("confidence", confidence).
+			Str("category", category).
+			Msg("Categorized transaction")
+	}
+
+	if categorized > 0 {
+		log.Info().
+			Int("categorized_count", categorized).
+			Int("remaining_count", len(transactions)-categorized).
+			Msg("🏷️ Categorized transactions from rules and provider categories")
+	}
+
+	return categorized
+}
@@ -0,0 +1,259 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+
+	"github.com/rs/zerolog/log"
+)
+
+// llmCategoriesFile is the name of the JSON file holding LLM-assigned categories inside the cache directory
+const llmCategoriesFile = "llm_categories.json"
+
+// maxKnownCategories bounds the existing categories suggested to the model in each request
+const maxKnownCategories = 50
+
+// categorizePrompt asks the model to classify a batch of transactions as JSON
+const categorizePrompt = `Categorize each of these card transactions. Reply with only a JSON array, no other text, with one object per transaction:
+[{"id": "transaction id", "category": "category name", "confidence": 0.9}]
+Confidence is how sure you are of the category, from 0 to 1. %s
+
+Transactions (id | merchant | amount | date):
+%s`
+
+// LLMCategory is a category the LLM assigned to a transaction
+type LLMCategory struct {
+	Category   string  `json:"category"`
+	Confidence float64 `json:"confidence"`
+}
+
+// LLMCategories holds the LLM-assigned categories keyed by SimpleFin transaction ID, so each
+// transaction is only sent to the LLM once
+type LLMCategories struct {
+	path       string
+	Categories map[string]LLMCategory `json:"categories"`
+}
+
+// llmCategorization is one entry of the model's reply
+type llmCategorization struct {
+	ID         string  `json:"id"`
+	Category   string  `json:"category"`
+	Confidence float64 `json:"confidence"`
+}
+
+// loadLLMCategories loads the LLM-assigned categories from the cache directory, returning nil if
+// no cache directory is configured or the file can't be read
+func loadLLMCategories(cacheDir string) *LLMCategories {
+	if cacheDir == "" {
+		return nil
+	}
+
+	store := &LLMCategories{
+		path:       filepath.Join(cacheDir, llmCategoriesFile),
+		Categories: make(map[string]LLMCategory),
+	}
+
+	data, err := os.ReadFile(store.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return store
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("path", store.path).Msg("Failed to read LLM categories, continuing without them")
+		return nil
+	}
+	if err := json.Unmarshal(data, store); err != nil {
+		log.Warn().Err(err).Str("path", store.path).Msg("Failed to parse LLM categories, continuing without them")
+		return nil
+	}
+	if store.Categories == nil {
+		store.Categories = make(map[string]LLMCategory)
+	}
+	return store
+}
+
+// Save writes the LLM-assigned categories to disk
+func (s *LLMCategories) Save() error {
+	if s == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(s, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling LLM categories: %w", err)
+	}
+	if err := os.MkdirAll(filepath.Dir(s.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+	if err := writeFileAtomic(s.path, data); err != nil {
+		return fmt.Errorf("error writing LLM categories: %w", err)
+	}
+	return nil
+}
+
+// applyLLMCategories assigns the stored LLM categories to the transactions still without a
+// category, prefixed with their category_parents
+func applyLLMCategories(transactions []Transaction, store *LLMCategories, filterConfig *FilterConfig) int {
+	if store == nil || len(store.Categories) == 0 {
+		return 0
+	}
+
+	var parents map[string]string
+	if filterConfig != nil {
+		parents = categoryParents(filterConfig)
+	}
+
+	applied := 0
+	for i := range transactions {
+		if transactions[i].Category != "" {
+			continue
+		}
+		if stored, ok := store.Categories[transactions[i].ID]; ok {
+			transactions[i].Category = categoryPath(stored.Category, parents)
+			transactions[i].CategorySource = CategorySourceLLM
+			transactions[i].Confidence = stored.Confidence
+			applied++
+		}
+	}
+	return applied
+}
+
+// categorizeWithLLM categorizes the transactions left without a category by rules and provider
+// mappings. Transactions the LLM already classified reuse the stored result; the rest are sent
+// in batches of LLM_CATEGORIZE_BATCH_SIZE, at most LLM_CATEGORIZE_CONCURRENCY at a time. A failed
+// batch leaves its transactions for the summary prompt to classify. With dryRun the new categories
+// are applied to this run only, not saved.
+func categorizeWithLLM(settings *Settings, transactions []Transaction, filterConfig *FilterConfig, dryRun bool) int {
+	store := loadLLMCategories(settings.CacheDir)
+	categorized := applyLLMCategories(transactions, store, filterConfig)
+
+	var pending []int
+	for i := range transactions {
+		if transactions[i].Category == "" {
+			pending = append(pending, i)
+		}
+	}
+	if len(pending) == 0 {
+		return categorized
+	}
+
+	var batches [][]int
+	for start := 0; start < len(pending); start += settings.CategorizeBatchSize {
+		end := min(start+settings.CategorizeBatchSize, len(pending))
+		batches = append(batches, pending[start:end])
+	}
+	log.Info().
+		Int("transactions", len(pending)).
+		Int("batches", len(batches)).
+		Int("concurrency", settings.CategorizeWorkers).
+		Bool("local", localLLMTask(settings, LLMTaskCategorize)).
+		Msg("🏷️ Categorizing transactions with AI...")
+
+	// Each batch writes only its own slot, so the results need no locking
+	known := knownCategories(transactions)
+	taskSettings := llmSettings(settings, LLMTaskCategorize)
+	results := make([][]llmCategorization, len(batches))
+	errs := make([]error, len(batches))
+	sem := make(chan struct{}, settings.CategorizeWorkers)
+	var wg sync.WaitGroup
+	for b, batch := range batches {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			sem <- struct{}{}
+			defer func() { <-sem }()
+			results[b], errs[b] = categorizeBatch(taskSettings, transactions, batch, known)
+		}()
+	}
+	wg.Wait()
+
+	if store == nil {
+		store = &LLMCategories{Categories: make(map[string]LLMCategory)}
+	}
+	added := 0
+	for b, batch := range batches {
+		if errs[b] != nil {
+			log.Warn().Err(errs[b]).Int("batch", b+1).Int("transactions", len(batch)).Msg("AI categorization batch failed, leaving it to the summary")
+			continue
+		}
+		replies := make(map[string]llmCategorization, len(results[b]))
+		for _, reply := range results[b] {
+			replies[reply.ID] = reply
+		}
+		for _, i := range batch {
+			reply, ok := replies[transactions[i].ID]
+			if !ok || strings.TrimSpace(reply.Category) == "" {
+				continue
+			}
+			store.Categories[transactions[i].ID] = LLMCategory{
+				Category:   strings.TrimSpace(reply.Category),
+				Confidence: math.Min(math.Max(reply.Confidence, 0), 1),
+			}
+			added++
+		}
+	}
+
+	if added > 0 && store.path != "" {
+		if dryRun {
+			log.Debug().Int("categorized", added).Msg("Dry run, not saving AI categories")
+		} else if err := store.Save(); err != nil {
+			log.Warn().Err(err).Msg("Failed to save LLM categories")
+		}
+	}
+	categorized += applyLLMCategories(transactions, store, filterConfig)
+	log.Info().Int("categorized", added).Int("remaining", len(pending)-added).Msg(" └ AI categorization done")
+	return categorized
+}
+
+// categorizeBatch asks the LLM for the categories of one batch of transactions
+func categorizeBatch(settings *Settings, transactions []Transaction, batch []int, known []string) ([]llmCategorization, error) {
+	var lines strings.Builder
+	for _, i := range batch {
+		tx := transactions[i]
+		fmt.Fprintf(&lines, "%s | %s | %s | %s\n", tx.ID, merchantName(tx), toMoney(tx.Amount), transactionTime(tx).Format("2006-01-02"))
+	}
+
+	hint := "Use short, general category names such as Dining, Groceries, Transportation, Shopping or Utilities."
+	if len(known) > 0 {
+		hint = fmt.Sprintf("Use one of these categories when one fits, otherwise a short, general name: %s.", strings.Join(known, ", "))
+	}
+
+	content, err := getLLMResponse(settings, fmt.Sprintf(categorizePrompt, hint, lines.String()), false, nil)
+	if err != nil {
+		return nil, err
+	}
+
+	start := strings.Index(content, "[")
+	end := strings.LastIndex(content, "]")
+	if start < 0 || end < start {
+		return nil, fmt.Errorf("no categories in the model's reply")
+	}
+	var replies []llmCategorization
+	if err := json.Unmarshal([]byte(content[start:end+1]), &replies); err != nil {
+		return nil, fmt.Errorf("error parsing categories: %w", err)
+	}
+	return replies, nil
+}
+
+// knownCategories returns the distinct categories already assigned, so the LLM reuses them
+func knownCategories(transactions []Transaction) []string {
+	seen := make(map[string]bool)
+	var categories []string
+	for _, tx := range transactions {
+		if tx.Category != "" && !seen[strings.ToLower(tx.Category)] {
+			seen[strings.ToLower(tx.Category)] = true
+			categories = append(categories, tx.Category)
+		}
+	}
+	sort.Strings(categories)
+	if len(categories) > maxKnownCategories {
+		categories = categories[:maxKnownCategories]
+	}
+	return categories
+}
@@ -0,0 +1,2724 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os"
+	"sort"
+	"strconv"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/rs/zerolog/log"
+	"github.com/spf13/cobra"
+)
+
+// newSummarizeCommand creates the summarize command, which runs the full analysis and notification flow
+func newSummarizeCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "summarize",
+		Short: "Analyze transactions with AI and send notifications (default command)",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return run(runConfigFromFlags(cmd))
+		},
+	}
+	addRunFlags(cmd)
+	return cmd
+}
+
+// commandSetup is what a subcommand starts from: the run options of its flags, and the settings
+// and filter config they select
+type commandSetup struct {
+	Config       RunConfig
+	Settings     *Settings
+	FilterConfig *FilterConfig
+}
+
+// loadCommand initializes logging and loads the settings and filter config selected by the
+// command's flags
+func loadCommand(cmd *cobra.Command) (*commandSetup, error) {
+	config := runConfigFromFlags(cmd)
+	initLogger(config.Verbose)
+
+	settings, err := loadSettings(config.EnvFile, config.ConfigFile)
+	if err != nil {
+		return nil, err
+	}
+	if config.BillingDay == 0 {
+		config.BillingDay = settings.BillingCycleDay
+	}
+
+	return &commandSetup{Config: config, Settings: settings, FilterConfig: loadFilterConfig(settings)}, nil
+}
+
+// loadCommandAccounts loads the command's settings and filter config, and fetches the selected
+// accounts for the period of its flags
+func loadCommandAccounts(cmd *cobra.Command) (*commandSetup, []Account, time.Time, time.Time, error) {
+	setup, err := loadCommand(cmd)
+	if err != nil {
+		return nil, nil, time.Time{}, time.Time{}, err
+	}
+	accounts, start, end, err := setup.periodAccounts()
+	return setup, accounts, start, end, err
+}
+
+// periodAccounts fetches the selected accounts for the period in s.Config without sending any
+// notifications. Commands with their own defaults (e.g. all accounts, or a window ending today)
+// set them on s.Config first.
+func (s *commandSetup) periodAccounts() ([]Account, time.Time, time.Time, error) {
+	config := s.Config
+	config.DisableNotifications = true
+	settings, filterConfig := s.Settings, s.FilterConfig
+
+	dateRangeType, start, end, err := resolvePeriod(config)
+	if err != nil {
+		return nil, time.Time{}, time.Time{}, err
+	}
+
+	fetchStart, fetchEnd := widenForAccountBillingDays(dateRangeType, start, end, filterConfig)
+	accounts, _, err := fetchAccounts(settings, config, fetchStart, fetchEnd)
+	if err != nil {
+		return nil, time.Time{}, time.Time{}, err
+	}
+
+	if err := applyAccountBillingCycles(accounts, dateRangeType, start, end, filterConfig); err != nil {
+		return nil, time.Time{}, time.Time{}, err
+	}
+
+	accounts = excludeAccounts(accounts, filterConfig, AccountScopeSync)
+	normalizeMerchants(accounts, filterConfig)
+	transactionNotes := loadTransactionNotes(settings.CacheDir)
+	applyTransactionNotes(accounts, transactionNotes)
+	applyAccountDisplaySettings(accounts, filterConfig)
+	applyCreditLimits(accounts, filterConfig)
+	markTransfers(accounts)
+	detectRefunds(accounts, transactionNotes)
+	applyRefundLinks(accounts, transactionNotes)
+
+	accounts, err = selectAccounts(accounts, config.AllAccounts)
+	if err != nil {
+		return nil, time.Time{}, time.Time{}, err
+	}
+
+	return accounts, start, end, nil
+}
+
+// newAccountsCommand creates the accounts command, which lists balances without AI analysis
+func newAccountsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "accounts",
+		Short: "List accounts and balances",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			_, accounts, _, _, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "ACCOUNT\tID\tBALANCE\tUTILIZATION\tLAST SYNCED\tTRANSACTIONS")
+			for _, account := range accounts {
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%d\n",
+					accountDisplayName(account),
+					account.ID,
+					account.Balance,
+					formatUtilization(account),
+					time.Unix(account.BalanceDate, 0).Format("2006-01-02 15:04"),
+					len(account.Transactions))
+			}
+			return w.Flush()
+		},
+	}
+	addPeriodFlags(cmd)
+
+	cmd.AddCommand(newAccountSyncCommand("pause", "Pause syncing an account (by ID or name) without deleting its history", true))
+	cmd.AddCommand(newAccountSyncCommand("resume", "Resume syncing a paused account (by ID or name)", false))
+	cmd.AddCommand(newAccountArchiveCommand("archive", "Archive a closed account (by ID or name) so it is no longer synced or summarized", true))
+	cmd.AddCommand(newAccountArchiveCommand("unarchive", "Restore an archived account (by ID or name)", false))
+	cmd.AddCommand(newArchivedAccountsCommand())
+	cmd.AddCommand(newManualAccountsCommand())
+	cmd.AddCommand(newAccountMergeCommand())
+	return cmd
+}
+
+// newAccountMergeCommand creates the accounts merge command, which continues an account's history
+// under the new ID it got after being reissued
+func newAccountMergeCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "merge <old-account-id> <new-account-id>",
+		Short: "Continue an account's history under a new account ID (e.g. a reissued card)",
+		Long: `When a card is reissued, SimpleFin reports it as a new account and its history forks. Merging
+folds the old account's transactions into the new account whenever they are fetched, drops the
+old account's alerts and archives it. --undo restores the old account (it stays archived until
+"accounts unarchive").
+
+Example usage:
+  finance_tracker accounts merge ACT-old ACT-new
+  finance_tracker accounts merge ACT-old --undo`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			undo, _ := cmd.Flags().GetBool("undo")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			state := loadAccountState(settings.CacheDir)
+			if state == nil {
+				return fmt.Errorf("account state is unavailable (set CACHE_DIR)")
+			}
+
+			oldID := args[0]
+			if undo {
+				if _, ok := state.Merged[oldID]; !ok {
+					return fmt.Errorf("account %q is not merged", oldID)
+				}
+				delete(state.Merged, oldID)
+				if err := state.Save(); err != nil {
+					return err
+				}
+				log.Info().Str("account_id", oldID).Msg("🔀 Account merge undone")
+				return nil
+			}
+
+			if len(args) != 2 {
+				return fmt.Errorf("the new account ID is required")
+			}
+			if err := state.MergeAccount(oldID, args[1], time.Now().UTC()); err != nil {
+				return err
+			}
+			if err := state.Save(); err != nil {
+				return err
+			}
+
+			// The old account's balance checkpoint would never be reconciled again
+			if reconciliation := loadReconciliation(settings.CacheDir); reconciliation != nil {
+				if _, ok := reconciliation.Checkpoints[oldID]; ok {
+					delete(reconciliation.Checkpoints, oldID)
+					if err := reconciliation.Save(); err != nil {
+						log.Warn().Err(err).Msg("Failed to save reconciliation state")
+					}
+				}
+			}
+
+			log.Info().Str("old_account_id", oldID).Str("new_account_id", args[1]).Msg("🔀 Account merged into its replacement")
+			return nil
+		},
+	}
+	cmd.Flags().Bool("undo", false, "Undo merging the account")
+	return cmd
+}
+
+// newManualAccountsCommand creates the accounts manual command, which lists and edits accounts
+// whose balances are entered by hand (property, vehicles, cash, crypto wallets...)
+func newManualAccountsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "manual",
+		Short: "List accounts whose balances are entered by hand",
+		Long: `List, set and remove manual accounts: assets and liabilities that aren't available through
+SimpleFin, such as a house, a car, cash or a crypto wallet. Manual accounts are counted in
+"finance_tracker networth".
+
+Accounts can instead hold quantities of coins (priced by CoinGecko coin ID) or tickers (priced
+by Yahoo Finance). Their balance is the holdings' value, refreshed by "revalue" and by
+networth once the prices are a day old.
+
+Example usage:
+  finance_tracker accounts manual
+  finance_tracker accounts manual set House 450000 --kind property
+  finance_tracker accounts manual set Mortgage -310000 --kind liability
+  finance_tracker accounts manual holding "Ledger" bitcoin 0.25 --source coingecko
+  finance_tracker accounts manual holding "Old 401k" VTI 120 --source yahoo
+  finance_tracker accounts manual revalue
+  finance_tracker accounts manual remove House`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			manual, err := loadManualAccountsFromFlags(cmd)
+			if err != nil {
+				return err
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "ACCOUNT\tKIND\tBALANCE\tUPDATED\tHOLDINGS")
+			for _, account := range manual.List() {
+				var holdings []string
+				for _, holding := range account.Holdings {
+					holdings = append(holdings, fmt.Sprintf("%g %s", holding.Quantity, holding.Symbol))
+				}
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\n",
+					account.Name,
+					account.Kind,
+					toMoney(account.Balance),
+					account.UpdatedAt.Local().Format("2006-01-02"),
+					strings.Join(holdings, ", "))
+			}
+			return w.Flush()
+		},
+	}
+
+	set := &cobra.Command{
+		Use:   "set <name> <balance>",
+		Short: "Enter the balance of a manual account, creating it if needed",
+		Args:  cobra.ExactArgs(2),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			kind, _ := cmd.Flags().GetString("kind")
+
+			manual, err := loadManualAccountsFromFlags(cmd)
+			if err != nil {
+				return err
+			}
+
+			balance, err := strconv.ParseFloat(args[1], 64)
+			if err != nil {
+				return fmt.Errorf("invalid balance %q: %w", args[1], err)
+			}
+
+			account, err := manual.Set(args[0], AssetKind(kind), Balance(balance), time.Now().UTC())
+			if err != nil {
+				return err
+			}
+			if err := manual.Save(); err != nil {
+				return err
+			}
+
+			log.Info().Str("account", account.Name).Str("kind", string(account.Kind)).Str("balance", toMoney(account.Balance).String()).Msg("🏠 Manual account balance updated")
+			return nil
+		},
+	}
+	set.Flags().String("kind", "", "Asset kind: property, vehicle, cash, crypto, investment, liability or other (default: other, or the existing kind)")
+
+	remove := &cobra.Command{
+		Use:   "remove <name>",
+		Short: "Remove a manual account",
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			manual, err := loadManualAccountsFromFlags(cmd)
+			if err != nil {
+				return err
+			}
+
+			id := manualAccountID(args[0])
+			if _, ok := manual.Accounts[id]; !ok {
+				return fmt.Errorf("manual account %q not found", args[0])
+			}
+			delete(manual.Accounts, id)
+			if err := manual.Save(); err != nil {
+				return err
+			}
+
+			log.Info().Str("account", args[0]).Msg("🗑️ Manual account removed")
+			return nil
+		},
+	}
+
+	holding := &cobra.Command{
+		Use:   "holding <account> <symbol> <quantity>",
+		Short: "Set the quantity of a coin or ticker held in a manual account (0 removes it)",
+		Args:  cobra.ExactArgs(3),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			source, _ := cmd.Flags().GetString("source")
+
+			manual, err := loadManualAccountsFromFlags(cmd)
+			if err != nil {
+				return err
+			}
+
+			quantity, err := strconv.ParseFloat(args[2], 64)
+			if err != nil {
+				return fmt.Errorf("invalid quantity %q: %w", args[2], err)
+			}
+
+			if err := manual.SetHolding(args[0], args[1], quantity, PriceSource(source)); err != nil {
+				return err
+			}
+			if _, errs := manual.Revalue(newHTTPClient(30*time.Second), time.Now().UTC(), true); len(errs) > 0 {
+				for _, err := range errs {
+					log.Warn().Err(err).Msg("Failed to fetch price")
+				}
+			}
+			return manual.Save()
+		},
+	}
+	holding.Flags().String("source", string(PriceSourceYahoo), "Price source: coingecko (coin ID, e.g. bitcoin) or yahoo (ticker, e.g. VTI)")
+
+	revalue := &cobra.Command{
+		Use:   "revalue",
+		Short: "Fetch current prices and update the balances of manual accounts with holdings",
+		Args:  cobra.NoArgs,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			manual, err := loadManualAccountsFromFlags(cmd)
+			if err != nil {
+				return err
+			}
+
+			_, errs := manual.Revalue(newHTTPClient(30*time.Second), time.Now().UTC(), true)
+			for _, err := range errs {
+				log.Warn().Err(err).Msg("Failed to fetch price")
+			}
+			if err := manual.Save(); err != nil {
+				return err
+			}
+			if len(errs) > 0 {
+				return fmt.Errorf("%d prices could not be fetched", len(errs))
+			}
+			return nil
+		},
+	}
+
+	cmd.AddCommand(set, holding, revalue, remove)
+	return cmd
+}
+
+// loadManualAccountsFromFlags loads the settings selected by the global flags and the manual
+// accounts from their cache directory
+func loadManualAccountsFromFlags(cmd *cobra.Command) (*ManualAccounts, error) {
+	setup, err := loadCommand(cmd)
+	if err != nil {
+		return nil, err
+	}
+
+	manual := loadManualAccounts(setup.Settings.CacheDir)
+	if manual == nil {
+		return nil, fmt.Errorf("manual accounts are unavailable (set CACHE_DIR)")
+	}
+	return manual, nil
+}
+
+// newAccountSyncCommand creates a command that pauses or resumes syncing for an account
+func newAccountSyncCommand(use string, short string, pause bool) *cobra.Command {
+	return &cobra.Command{
+		Use:   use + " <account>",
+		Short: short,
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			state := loadAccountState(settings.CacheDir)
+			if state == nil {
+				return fmt.Errorf("account state is unavailable (set CACHE_DIR)")
+			}
+
+			if pause {
+				account, err := resolveAccount(settings, args[0])
+				if err != nil {
+					return err
+				}
+				state.Paused[account.ID] = time.Now().UTC()
+				state.Names[account.ID] = account.Name
+				if err := state.Save(); err != nil {
+					return err
+				}
+				log.Info().Str("account", account.Name).Str("account_id", account.ID).Msg("⏸️ Account syncing paused")
+				return nil
+			}
+
+			keys := state.inactiveKeys(state.Paused, args[0])
+			if len(keys) == 0 {
+				return fmt.Errorf("account %q is not paused", args[0])
+			}
+			for _, key := range keys {
+				delete(state.Paused, key)
+				state.forgetName(key)
+			}
+			if err := state.Save(); err != nil {
+				return err
+			}
+			log.Info().Str("account", args[0]).Msg("▶️ Account syncing resumed")
+			return nil
+		},
+	}
+}
+
+// resolveAccount finds the SimpleFin account the user refers to by ID or name (case-insensitive),
+// among all accounts including those with a zero balance. An exact ID wins over names; a name
+// shared by several accounts is rejected so the user picks one by ID.
+func resolveAccount(settings *Settings, account string) (Account, error) {
+	accounts, err := getAccountList(settings)
+	if err != nil {
+		return Account{}, fmt.Errorf("error fetching accounts: %w", err)
+	}
+
+	var matched []Account
+	for _, a := range accounts {
+		if a.ID == account {
+			return a, nil
+		}
+		if keyMatchesAccount(account, a) {
+			matched = append(matched, a)
+		}
+	}
+
+	switch len(matched) {
+	case 0:
+		return Account{}, fmt.Errorf("no account matches %q (see `accounts` for IDs and names)", account)
+	case 1:
+		return matched[0], nil
+	default:
+		ids := make([]string, len(matched))
+		for i, a := range matched {
+			ids[i] = a.ID
+		}
+		return Account{}, fmt.Errorf("%d accounts are named %q, use one of their IDs: %s", len(matched), account, strings.Join(ids, ", "))
+	}
+}
+
+// newAccountArchiveCommand creates a command that archives or unarchives an account
+func newAccountArchiveCommand(use string, short string, archive bool) *cobra.Command {
+	return &cobra.Command{
+		Use:   use + " <account>",
+		Short: short,
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			state := loadAccountState(settings.CacheDir)
+			if state == nil {
+				return fmt.Errorf("account state is unavailable (set CACHE_DIR)")
+			}
+
+			if archive {
+				account, err := resolveAccount(settings, args[0])
+				if err != nil {
+					return err
+				}
+				state.Archived[account.ID] = time.Now().UTC()
+				state.Names[account.ID] = account.Name
+				if err := state.Save(); err != nil {
+					return err
+				}
+				log.Info().Str("account", account.Name).Str("account_id", account.ID).Msg("🗄️ Account archived")
+				return nil
+			}
+
+			keys := state.inactiveKeys(state.Archived, args[0])
+			if len(keys) == 0 {
+				return fmt.Errorf("account %q is not archived", args[0])
+			}
+			for _, key := range keys {
+				delete(state.Archived, key)
+				state.forgetName(key)
+			}
+			if err := state.Save(); err != nil {
+				return err
+			}
+			log.Info().Str("account", args[0]).Msg("📂 Account unarchived")
+			return nil
+		},
+	}
+}
+
+// newArchivedAccountsCommand creates the command listing archived accounts
+func newArchivedAccountsCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:   "archived",
+		Short: "List archived accounts",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			state := loadAccountState(settings.CacheDir)
+			if state == nil || len(state.Archived) == 0 {
+				log.Info().Msg("No archived accounts")
+				return nil
+			}
+
+			keys := make([]string, 0, len(state.Archived))
+			for key := range state.Archived {
+				keys = append(keys, key)
+			}
+			sort.Strings(keys)
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "ACCOUNT\tARCHIVED")
+			for _, key := range keys {
+				account := key
+				if name := state.Names[key]; name != "" {
+					account = fmt.Sprintf("%s (%s)", name, key)
+				}
+				fmt.Fprintf(w, "%s\t%s\n", account, state.Archived[key].Local().Format("2006-01-02 15:04"))
+			}
+			return w.Flush()
+		},
+	}
+}
+
+// newTransactionsCommand creates the transactions command, which lists transactions for the period
+func newTransactionsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "transactions",
+		Short: "List transactions for the period",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			expensesOnly, _ := cmd.Flags().GetBool("expenses-only")
+			timeline, _ := cmd.Flags().GetBool("timeline")
+			jsonOutput, _ := cmd.Flags().GetBool("json")
+
+			setup, accounts, start, end, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+
+			if timeline {
+				// Balances walk back from the current balance, so a past period also needs the
+				// transactions posted since it ended
+				var later []Account
+				if now := time.Now(); end.Before(now) {
+					config := setup.Config
+					config.DisableNotifications = true
+					later, _, err = fetchAccounts(setup.Settings, config, end, now)
+					if err != nil {
+						return fmt.Errorf("error fetching transactions since the period: %w", err)
+					}
+				}
+
+				days := buildTimeline(accounts, later, expensesOnly)
+				if jsonOutput {
+					return writeTimelineJSON(os.Stdout, days)
+				}
+				return writeTimelineText(os.Stdout, days)
+			}
+
+			type accountTransaction struct {
+				Account     string
+				Transaction Transaction
+			}
+
+			var rows []accountTransaction
+			for _, account := range accounts {
+				for _, tx := range account.Transactions {
+					if expensesOnly && (tx.Amount >= 0 || tx.IsTransfer) {
+						continue
+					}
+					rows = append(rows, accountTransaction{Account: accountDisplayName(account), Transaction: tx})
+				}
+			}
+
+			sort.SliceStable(rows, func(i, j int) bool {
+				return transactionTime(rows[i].Transaction).Before(transactionTime(rows[j].Transaction))
+			})
+
+			fmt.Printf("Transactions from %s to %s\n\n", start.Format("2006-01-02"), end.Format("2006-01-02"))
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "DATE\tACCOUNT\tDESCRIPTION\tAMOUNT\tSTATUS\tID\tNOTE")
+			var total Money
+			for _, row := range rows {
+				total += toMoney(row.Transaction.Amount)
+				description := row.Transaction.Description
+				if row.Transaction.UserDescription != "" {
+					description = row.Transaction.UserDescription
+				}
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\t%s\n",
+					transactionTime(row.Transaction).Format("2006-01-02"),
+					row.Account,
+					description,
+					row.Transaction.Amount,
+					getTransactionStatus(row.Transaction),
+					row.Transaction.ID,
+					row.Transaction.Note)
+			}
+			fmt.Fprintf(w, "\t\tTOTAL (%d transactions)\t%s\t\t\t\n", len(rows), total)
+			return w.Flush()
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("expenses-only", false, "Only list expenses (negative amounts)")
+	cmd.Flags().Bool("timeline", false, "Group transactions by day with daily subtotals and running balances")
+	cmd.Flags().Bool("json", false, "Output the timeline as JSON (with --timeline)")
+
+	cmd.AddCommand(newTransactionAnnotateCommand())
+	cmd.AddCommand(newTransactionRecategorizeCommand())
+	cmd.AddCommand(newTransactionReviewCommand())
+	cmd.AddCommand(newTransactionLinkRefundCommand())
+	cmd.AddCommand(newTransactionAttachCommand())
+	cmd.AddCommand(newTransactionReceiptCommand())
+	cmd.AddCommand(newTransactionAttachmentsCommand())
+	cmd.AddCommand(newTransactionDuplicatesCommand())
+	cmd.AddCommand(newTransactionRevisionsCommand())
+	return cmd
+}
+
+// newTransactionAttachCommand creates the transactions attach command, which stores receipts and
+// invoices for a transaction
+func newTransactionAttachCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:   "attach <transaction-id> <file>...",
+		Short: "Attach receipts or invoices to a transaction",
+		Long: `Copy one or more files into ATTACHMENTS_DIR/<transaction-id>/ so they are kept with the
+transaction (e.g. receipts for tax time). Transaction IDs are shown by "transactions".
+
+Example usage:
+  finance_tracker transactions attach TRN-123 receipt.jpg invoice.pdf`,
+		Args: cobra.MinimumNArgs(2),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			transactionID := args[0]
+			for _, file := range args[1:] {
+				attachment, err := storeAttachment(settings.AttachmentsDir, transactionID, file)
+				if err != nil {
+					return err
+				}
+				log.Info().
+					Str("transaction_id", transactionID).
+					Str("path", attachment.Path).
+					Int64("size", attachment.Size).
+					Msg("📎 Attachment stored")
+			}
+			return nil
+		},
+	}
+}
+
+// newTransactionReceiptCommand creates the transactions receipt command, which reads a receipt
+// image with a vision model and attaches it to the matching transaction
+func newTransactionReceiptCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "receipt <image>",
+		Short: "Read a receipt image and attach it to the matching transaction",
+		Long: `Send a receipt photo to the OPENROUTER_VISION_MODEL to extract the merchant, date, total
+and line items, then find the expense with the same amount within a few days of the
+receipt date (preferring a matching merchant) in the selected period. The image is
+attached to that transaction, with the extracted data saved next to it as
+<image>.receipt.json.
+
+Example usage:
+  finance_tracker transactions receipt photo.jpg
+  finance_tracker transactions receipt photo.jpg --dry-run --all-accounts`,
+		Args: cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			dryRun, _ := cmd.Flags().GetBool("dry-run")
+
+			setup, accounts, _, _, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			log.Info().Str("image", args[0]).Msg("🧾 Reading receipt...")
+			receipt, err := extractReceipt(settings, args[0])
+			if err != nil {
+				return err
+			}
+			fmt.Print(formatReceipt(receipt))
+
+			tx, ok := matchReceipt(accounts, receipt)
+			if !ok {
+				return fmt.Errorf("no transaction of %s near %s found in the period (widen it with --date-range or --all-accounts, or use transactions attach)",
+					currentLocale.formatAmount(toMoney(Balance(receipt.Total))), receipt.Date)
+			}
+			fmt.Printf("\nMatched %s  %s  %s  %s\n", tx.ID, transactionTime(tx).Format("2006-01-02"), merchantName(tx), toMoney(tx.Amount).String())
+			if dryRun {
+				return nil
+			}
+
+			attachment, err := storeAttachment(settings.AttachmentsDir, tx.ID, args[0])
+			if err != nil {
+				return err
+			}
+			if err := saveReceiptData(attachment, receipt); err != nil {
+				return err
+			}
+			log.Info().
+				Str("transaction_id", tx.ID).
+				Str("path", attachment.Path).
+				Msg("📎 Receipt attached")
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("dry-run", false, "Extract and match the receipt without attaching it")
+	return cmd
+}
+
+// newTransactionDuplicatesCommand creates the transactions duplicates command, which lists possible
+// duplicate charges
+func newTransactionDuplicatesCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "duplicates",
+		Short: "List possible duplicate charges",
+		Long: `List pairs of posted charges on the same account from the same merchant with the same
+amount within 3 days, such as a double swipe or a subscription billed twice. New pairs are also
+reported through the warning notification channel when a summary runs.
+
+Example usage:
+  finance_tracker transactions duplicates --date-range last_month`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			duplicates := findDuplicateCharges(accounts)
+			if len(duplicates) == 0 {
+				fmt.Println("No duplicate charges found")
+				return nil
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "ACCOUNT\tMERCHANT\tAMOUNT\tFIRST\tSECOND\tIDS")
+			for _, d := range duplicates {
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s, %s\n",
+					accountDisplayName(d.Account),
+					merchantName(d.First),
+					-toMoney(d.First.Amount),
+					transactionTime(d.First).Format("2006-01-02"),
+					transactionTime(d.Second).Format("2006-01-02"),
+					d.First.ID,
+					d.Second.ID)
+			}
+			return w.Flush()
+		},
+	}
+	addPeriodFlags(cmd)
+	return cmd
+}
+
+// newTransactionRevisionsCommand creates the transactions revisions command, which lists changes
+// banks made to transactions after they were first seen
+func newTransactionRevisionsCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:   "revisions [transaction-id]",
+		Short: "List changes banks made to transactions after they were first seen",
+		Long: `List the descriptions, amounts and posted dates that banks rewrote after a transaction
+was first fetched, oldest first, optionally for a single transaction. Changes are detected when
+a summary runs, by comparing each fetched transaction with the version seen in the previous run.
+
+Example usage:
+  finance_tracker transactions revisions
+  finance_tracker transactions revisions TRN-123`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			revisions := loadTransactionRevisions(settings.CacheDir)
+			if revisions == nil {
+				return fmt.Errorf("transaction revisions are unavailable (set CACHE_DIR)")
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "DETECTED\tTRANSACTION\tACCOUNT\tFIELD\tOLD\tNEW")
+			for _, revision := range revisions.Revisions {
+				if len(args) == 1 && revision.TransactionID != args[0] {
+					continue
+				}
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\n",
+					revision.DetectedAt.Local().Format("2006-01-02 15:04"),
+					revision.TransactionID,
+					revision.Account,
+					revision.Field,
+					revision.Old,
+					revision.New)
+			}
+			return w.Flush()
+		},
+	}
+}
+
+// newTransactionAttachmentsCommand creates the transactions attachments command, which lists stored attachments
+func newTransactionAttachmentsCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:   "attachments [transaction-id]",
+		Short: "List attachments, for one transaction or all of them",
+		Args:  cobra.MaximumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			transactionID := ""
+			if len(args) == 1 {
+				transactionID = args[0]
+			}
+
+			attachments, err := listAttachments(settings.AttachmentsDir, transactionID)
+			if err != nil {
+				return err
+			}
+			if len(attachments) == 0 {
+				log.Info().Msg("No attachments found")
+				return nil
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "TRANSACTION\tNAME\tSIZE\tPATH")
+			for _, attachment := range attachments {
+				fmt.Fprintf(w, "%s\t%s\t%d\t%s\n", attachment.TransactionID, attachment.Name, attachment.Size, attachment.Path)
+			}
+			return w.Flush()
+		},
+	}
+}
+
+// newTransactionAnnotateCommand creates the transactions annotate command, which adds a note, an
+// edited description or a category to a transaction without changing the original bank description
+func newTransactionAnnotateCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "annotate <transaction-id>",
+		Short: "Add a note, edited description or category to a transaction",
+		Long: `Annotate a transaction by its SimpleFin ID (shown by "transactions"). The edited
+description is used instead of the merchant name in prompts, notifications and listings, and
+the note is passed to the AI summary. The original bank description is kept, so filters still
+match it. A category replaces the one from category rules or provider mappings, and is learned
+for the merchant's other transactions on the next run.
+
+Example usage:
+  finance_tracker transactions annotate TRN-123 --note "split with roommate"
+  finance_tracker transactions annotate TRN-123 --description "Concert tickets"
+  finance_tracker transactions annotate TRN-123 --category "Dining > Coffee"
+  finance_tracker transactions annotate TRN-123 --clear`,
+		Args: cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clearNote, _ := cmd.Flags().GetBool("clear")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			notes := loadTransactionNotes(settings.CacheDir)
+			if notes == nil {
+				return fmt.Errorf("transaction notes are unavailable (set CACHE_DIR)")
+			}
+
+			transactionID := args[0]
+			if clearNote {
+				if _, ok := notes.Notes[transactionID]; !ok {
+					return fmt.Errorf("transaction %q has no annotation", transactionID)
+				}
+				delete(notes.Notes, transactionID)
+				notes.forgetCategory(transactionID)
+			} else {
+				if !cmd.Flags().Changed("note") && !cmd.Flags().Changed("description") && !cmd.Flags().Changed("category") {
+					return fmt.Errorf("nothing to annotate: pass --note, --description, --category or --clear")
+				}
+				note := notes.Notes[transactionID]
+				if cmd.Flags().Changed("note") {
+					note.Note, _ = cmd.Flags().GetString("note")
+				}
+				if cmd.Flags().Changed("description") {
+					note.Description, _ = cmd.Flags().GetString("description")
+				}
+				if cmd.Flags().Changed("category") {
+					category, _ := cmd.Flags().GetString("category")
+					note.Category = strings.TrimSpace(category)
+					// Relearned from the new category on the next run
+					notes.forgetCategory(transactionID)
+				}
+				note.UpdatedAt = time.Now().UTC()
+				notes.Notes[transactionID] = note
+			}
+
+			if err := notes.Save(); err != nil {
+				return err
+			}
+
+			if clearNote {
+				log.Info().Str("transaction_id", transactionID).Msg("🗑️ Transaction annotation removed")
+			} else {
+				log.Info().Str("transaction_id", transactionID).Msg("📝 Transaction annotated")
+			}
+			return nil
+		},
+	}
+	cmd.Flags().String("note", "", "Note to attach to the transaction")
+	cmd.Flags().String("description", "", "Edited description, preferred over the bank description in reports")
+	cmd.Flags().String("category", "", "Category of the transaction, preferred over category rules and provider mappings")
+	cmd.Flags().Bool("clear", false, "Remove the transaction's note, edited description and category")
+	return cmd
+}
+
+// newTransactionRecategorizeCommand creates the transactions recategorize command, which sets the
+// category of every transaction in the period matching a filter
+func newTransactionRecategorizeCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "recategorize [category]",
+		Short: "Set the category of the transactions matching a filter",
+		Long: `Set the category of every transaction in the selected period that matches a filter. The
+filter uses the fields and match types of excluded_transactions rules. The category is
+stored with the transaction's annotations in CACHE_DIR and takes precedence over category
+rules and provider mappings; --clear removes it again. Each merchant also learns the category,
+which is then applied to its other transactions without a category rule.
+
+Stored categories only cover the matched transactions. To also categorize older and future
+ones, add the printed rule to category_rules in the filter config: rules are applied to
+every period, so they recategorize past transactions as well.
+
+Example usage:
+  finance_tracker transactions recategorize "Dining > Coffee" --match STARBUCKS --dry-run
+  finance_tracker transactions recategorize Groceries --match costco --field merchant --date-range last_3_months
+  finance_tracker transactions recategorize --clear --match AMZN`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			match, _ := cmd.Flags().GetString("match")
+			matchType, _ := cmd.Flags().GetString("match-type")
+			field, _ := cmd.Flags().GetString("field")
+			clearCategory, _ := cmd.Flags().GetBool("clear")
+			dryRun, _ := cmd.Flags().GetBool("dry-run")
+
+			if match == "" {
+				return fmt.Errorf("--match is required")
+			}
+			if clearCategory == (len(args) == 1) {
+				return fmt.Errorf("pass either a category or --clear")
+			}
+			var category string
+			if len(args) == 1 {
+				if category = strings.TrimSpace(args[0]); category == "" {
+					return fmt.Errorf("category must not be empty")
+				}
+			}
+
+			setup, accounts, start, end, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+			settings, filterConfig := setup.Settings, setup.FilterConfig
+			notes := loadTransactionNotes(settings.CacheDir)
+			if notes == nil {
+				return fmt.Errorf("transaction notes are unavailable (set CACHE_DIR)")
+			}
+
+			rule := FilterRule{Pattern: match, MatchType: MatchType(matchType), Field: FilterField(field)}
+			now := time.Now().UTC()
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "DATE\tACCOUNT\tDESCRIPTION\tAMOUNT\tCURRENT CATEGORY\tID")
+			matched := 0
+			for _, account := range accounts {
+				applyProviderCategories(account.Transactions, filterConfig)
+				for _, tx := range account.Transactions {
+					if !matchesRule(ruleFieldValue(tx, rule), rule) {
+						continue
+					}
+					matched++
+					fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\n",
+						transactionTime(tx).Format("2006-01-02"), accountDisplayName(account), tx.Description, tx.Amount, tx.Category, tx.ID)
+
+					note := notes.Notes[tx.ID]
+					note.Category = category
+					note.UpdatedAt = now
+					notes.forgetCategory(tx.ID)
+					if note.Note == "" && note.Description == "" && note.Category == "" {
+						delete(notes.Notes, tx.ID)
+					} else {
+						notes.Notes[tx.ID] = note
+						notes.learnCategory(tx.Merchant, tx.ID, note)
+					}
+				}
+			}
+			if err := w.Flush(); err != nil {
+				return err
+			}
+			if matched == 0 {
+				return fmt.Errorf("no transactions from %s to %s match %q", start.Format("2006-01-02"), end.Format("2006-01-02"), match)
+			}
+
+			if dryRun {
+				fmt.Printf("\n%d transactions would be recategorized (dry run)\n", matched)
+				return nil
+			}
+			if err := notes.Save(); err != nil {
+				return err
+			}
+			log.Info().Int("count", matched).Str("category", category).Msg("🏷️ Transactions recategorized")
+
+			if category != "" {
+				fmt.Printf("\nTo categorize past and future matches too, add this rule to the filter config:\n\n"+
+					"category_rules:\n  - category: %q\n    pattern: %q\n    match_type: %q\n    field: %q\n",
+					category, rule.Pattern, rule.MatchType, rule.Field)
+			}
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().String("match", "", "Pattern the transactions must match (required)")
+	cmd.Flags().String("match-type", string(MatchTypeSubstring), "How the pattern is matched: substring, prefix, suffix or exact")
+	cmd.Flags().String("field", string(FilterFieldDescription), "Field to match: description, merchant, category or note")
+	cmd.Flags().Bool("clear", false, "Remove the stored category instead of setting one")
+	cmd.Flags().Bool("dry-run", false, "List the matching transactions without changing them")
+	return cmd
+}
+
+// newTransactionReviewCommand creates the transactions review command, which lists the expenses
+// whose category is missing or uncertain
+func newTransactionReviewCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "review",
+		Short: "List uncategorized and low-confidence expenses",
+		Long: `List the expenses in the selected period without a category or whose category was
+assigned with a confidence below --threshold, least confident first. Categories set by hand
+have confidence 1, category_rules 0.95, categories learned from corrections for the merchant
+0.9 and provider mappings 0.9, minus 0.1 for each level the matched provider category is less
+specific than the most specific one. Categories from LLM_CATEGORIZE carry the confidence the
+model reported.
+
+Confirm the listed categories with --approve (they are stored like categories set by hand,
+so they aren't listed again), or correct one with "transactions annotate <id> --category".
+
+Example usage:
+  finance_tracker transactions review --date-range last_month
+  finance_tracker transactions review --threshold 0.9 --approve`,
+		Args: cobra.NoArgs,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			threshold, _ := cmd.Flags().GetFloat64("threshold")
+			approve, _ := cmd.Flags().GetBool("approve")
+
+			setup, accounts, start, end, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+			settings, filterConfig := setup.Settings, setup.FilterConfig
+			llmCategories := loadLLMCategories(settings.CacheDir)
+
+			type reviewItem struct {
+				Account     string
+				Transaction Transaction
+			}
+
+			var items []reviewItem
+			for _, account := range accounts {
+				applyProviderCategories(account.Transactions, filterConfig)
+				applyLLMCategories(account.Transactions, llmCategories, filterConfig)
+				for _, tx := range account.Transactions {
+					if tx.Amount >= 0 || tx.IsTransfer || (tx.Category != "" && tx.Confidence >= threshold) {
+						continue
+					}
+					items = append(items, reviewItem{Account: accountDisplayName(account), Transaction: tx})
+				}
+			}
+			sort.SliceStable(items, func(i, j int) bool {
+				a, b := items[i].Transaction, items[j].Transaction
+				if a.Confidence != b.Confidence {
+					return a.Confidence < b.Confidence
+				}
+				return transactionTime(a).Before(transactionTime(b))
+			})
+
+			fmt.Printf("Expenses to review from %s to %s (confidence below %.2f)\n\n", start.Format("2006-01-02"), end.Format("2006-01-02"), threshold)
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "DATE\tACCOUNT\tMERCHANT\tAMOUNT\tCATEGORY\tSOURCE\tCONFIDENCE\tID")
+			for _, item := range items {
+				tx := item.Transaction
+				category, source, confidence := tx.Category, string(tx.CategorySource), fmt.Sprintf("%.2f", tx.Confidence)
+				if category == "" {
+					category, source, confidence = "Uncategorized", "-", "-"
+				}
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\t%s\t%s\n",
+					transactionTime(tx).Format("2006-01-02"), item.Account, merchantName(tx), tx.Amount, category, source, confidence, tx.ID)
+			}
+			fmt.Fprintf(w, "\t\tTOTAL (%d transactions)\t\t\t\t\t\n", len(items))
+			if err := w.Flush(); err != nil {
+				return err
+			}
+
+			if !approve {
+				return nil
+			}
+			notes := loadTransactionNotes(settings.CacheDir)
+			if notes == nil {
+				return fmt.Errorf("transaction notes are unavailable (set CACHE_DIR)")
+			}
+			approved := 0
+			now := time.Now().UTC()
+			for _, item := range items {
+				if item.Transaction.Category == "" {
+					continue
+				}
+				note := notes.Notes[item.Transaction.ID]
+				note.Category = item.Transaction.Category
+				note.UpdatedAt = now
+				notes.Notes[item.Transaction.ID] = note
+				approved++
+			}
+			if approved == 0 {
+				log.Info().Msg("No categorized transactions to approve")
+				return nil
+			}
+			if err := notes.Save(); err != nil {
+				return err
+			}
+			log.Info().Int("count", approved).Msg("✅ Categories approved")
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Float64("threshold", 0.8, "List categorized expenses with a confidence below this (0 to 1)")
+	cmd.Flags().Bool("approve", false, "Store the listed categories as confirmed, so they aren't listed again")
+	return cmd
+}
+
+// newTransactionLinkRefundCommand creates the transactions link-refund command, which links a refund
+// to the purchase it returns so both are netted in spending
+func newTransactionLinkRefundCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "link-refund <refund-id> [purchase-id]",
+		Short: "Link a refund to its original purchase",
+		Long: `Link a refund to the purchase it returns, by their SimpleFin IDs (shown by "transactions").
+The refund amount is subtracted from the purchase in summaries and comparisons, and a fully
+refunded purchase is left out, so a returned purchase doesn't distort either period.
+
+Refunds from the same merchant on the same account within 90 days of a purchase are linked
+automatically when a summary runs; use this command for the ones that aren't, or --unlink to
+remove a wrong link. Both transactions must be within the period (default: last 3 months).
+
+Example usage:
+  finance_tracker transactions link-refund TRN-456 TRN-123
+  finance_tracker transactions link-refund TRN-456 --unlink`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			unlink, _ := cmd.Flags().GetBool("unlink")
+
+			refundID := args[0]
+			if unlink != (len(args) == 1) {
+				return fmt.Errorf("pass a refund and a purchase ID, or a refund ID with --unlink")
+			}
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			notes := loadTransactionNotes(setup.Settings.CacheDir)
+			if notes == nil {
+				return fmt.Errorf("refund links are unavailable (set CACHE_DIR)")
+			}
+
+			if unlink {
+				if _, ok := notes.Refunds[refundID]; !ok {
+					return fmt.Errorf("transaction %q is not linked to a purchase", refundID)
+				}
+				delete(notes.Refunds, refundID)
+				if err := notes.Save(); err != nil {
+					return err
+				}
+				log.Info().Str("refund_id", refundID).Msg("🗑️ Refund link removed")
+				return nil
+			}
+
+			if !cmd.Flags().Changed("date-range") {
+				setup.Config.DateRange = string(DateRangeTypeLast3Months)
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			purchaseID := args[1]
+			var refund, purchase *Transaction
+			for i := range accounts {
+				for j := range accounts[i].Transactions {
+					switch tx := &accounts[i].Transactions[j]; tx.ID {
+					case refundID:
+						refund = tx
+					case purchaseID:
+						purchase = tx
+					}
+				}
+			}
+			if refund == nil || purchase == nil {
+				return fmt.Errorf("transactions not found in the period (try a wider --date-range)")
+			}
+			if refund.Amount <= 0 || purchase.Amount >= 0 {
+				return fmt.Errorf("%s must be a credit and %s a purchase", refundID, purchaseID)
+			}
+
+			notes.Refunds[refundID] = RefundLink{
+				PurchaseID: purchaseID,
+				Amount:     refund.Amount,
+				LinkedAt:   time.Now().UTC(),
+			}
+			if err := notes.Save(); err != nil {
+				return err
+			}
+
+			log.Info().
+				Str("refund_id", refundID).
+				Str("purchase_id", purchaseID).
+				Str("amount", toMoney(refund.Amount).String()).
+				Msg("↩️ Refund linked to purchase")
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("unlink", false, "Remove the refund's link")
+	return cmd
+}
+
+// newExportCommand creates the export command, which writes the fetched accounts and transactions as JSON
+func newExportCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "export",
+		Short: "Export accounts and transactions as JSON",
+		Long: `Export accounts and transactions for the period as a SimpleFin-style JSON document.
+
+With --anonymize, account and transaction IDs are re-keyed, merchants are replaced with
+stable pseudonyms and amounts are randomly perturbed, while dates, signs and recurring
+patterns are preserved. The result can be attached to bug reports without leaking finances.
+
+Example usage:
+  finance_tracker export --anonymize --all-accounts -o bug-report.json`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			anonymize, _ := cmd.Flags().GetBool("anonymize")
+			seed, _ := cmd.Flags().GetInt64("seed")
+			output, _ := cmd.Flags().GetString("output")
+
+			_, accounts, _, _, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+
+			if seed == 0 {
+				seed = time.Now().UnixNano()
+			}
+
+			if output == "" || output == "-" {
+				return exportAccounts(os.Stdout, accounts, anonymize, seed)
+			}
+
+			file, err := os.Create(output)
+			if err != nil {
+				return fmt.Errorf("error creating export file: %w", err)
+			}
+			defer file.Close()
+
+			if err := exportAccounts(file, accounts, anonymize, seed); err != nil {
+				return err
+			}
+
+			log.Info().
+				Str("path", output).
+				Int("accounts", len(accounts)).
+				Bool("anonymized", anonymize).
+				Msg("📦 Export written")
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("anonymize", false, "Re-key IDs and perturb merchants and amounts for sharing")
+	cmd.Flags().Int64("seed", 0, "Random seed for anonymization (default: random)")
+	cmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+	return cmd
+}
+
+// newDigestCommand creates the digest command, which sends only the transactions synced since the
+// previous digest
+func newDigestCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "digest",
+		Short: "Send a short message listing transactions posted since the last digest",
+		Long: `Send a short notification listing only the transactions posted since the previous digest,
+independent of the AI summary. The transactions already sent are tracked per account in
+CACHE_DIR/account_state.json, so ones that post late are still listed; the first run only
+records them. Schedule it (e.g. hourly from cron)
+for near real-time updates. Transactions are fetched for the last --days days, so keep it
+longer than the schedule interval.
+
+Example usage:
+  finance_tracker digest
+  finance_tracker digest --notifications ntfy,email --dry-run`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			days, _ := cmd.Flags().GetInt("days")
+			notifications, _ := cmd.Flags().GetStringSlice("notifications")
+			dryRun, _ := cmd.Flags().GetBool("dry-run")
+
+			// Settings are loaded first so the fetch window uses the configured TIMEZONE
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings, filterConfig := setup.Settings, setup.FilterConfig
+
+			now := time.Now().In(periodLocation)
+			setup.Config.DateRange = string(DateRangeTypeCustom)
+			setup.Config.StartDate = now.AddDate(0, 0, -days).Format("2006-01-02")
+			setup.Config.EndDate = now.Format("2006-01-02")
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+			accounts = excludeAccounts(accounts, filterConfig, AccountScopeNotifications)
+
+			state := loadAccountState(settings.CacheDir)
+			if state == nil {
+				return fmt.Errorf("account state is unavailable (set CACHE_DIR)")
+			}
+
+			entries := unsentTransactions(accounts, state.DigestSent)
+			if len(entries) == 0 {
+				log.Info().Msg("No new transactions since the last digest")
+			} else {
+				message := formatTransactionDigest(entries)
+				if dryRun {
+					return renderNotification(settings, message, nil, nil, "info", notifications)
+				}
+				_, err := sendNotification(settings, message, nil, nil, "info", notifications)
+				if errors.Is(err, errNotificationDeferred) {
+					// The transactions aren't marked as sent so the next digest lists them
+					log.Info().Msg("Digest held back by notification rate limits")
+					return nil
+				}
+				if err != nil {
+					return fmt.Errorf("error sending digest: %w", err)
+				}
+				log.Info().Int("transactions", len(entries)).Msg("🧾 Digest sent")
+			}
+
+			if dryRun {
+				return nil
+			}
+			markDigestSent(accounts, state.DigestSent, fetchStart)
+			return state.Save()
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Int("days", 3, "Days of transactions to fetch when looking for new ones")
+	cmd.Flags().StringSliceP("notifications", "n", []string{"ntfy"}, "Notification types to send (email, ntfy, gotify)")
+	cmd.Flags().Bool("dry-run", false, "Render the digest locally without sending it or recording it as sent")
+	return cmd
+}
+
+// newBackfillCommand creates the backfill command, which fetches several years of history in
+// monthly chunks and writes it as a JSON export
+func newBackfillCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "backfill",
+		Short: "Fetch older history month by month and write it as JSON",
+		Long: `Walk backwards from the current month, fetching one calendar month per SimpleFin request,
+and write every account with all the transactions found as a SimpleFin-style JSON document (the
+same format as "export"). The walk stops after --months months, or earlier once two consecutive
+months have no transactions (the bridge's history has been exhausted).
+
+SimpleFin limits how many requests a connection may make per day, so requests are spaced by
+--delay. For shorter ranges, other commands accept --date-range custom with --start-date and
+--end-date; periods longer than SIMPLEFIN_MAX_DAYS are split automatically.
+
+Example usage:
+  finance_tracker backfill --months 36 -o history.json`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			months, _ := cmd.Flags().GetInt("months")
+			delay, _ := cmd.Flags().GetDuration("delay")
+			output, _ := cmd.Flags().GetString("output")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings, filterConfig := setup.Settings, setup.FilterConfig
+
+			now := time.Now().In(periodLocation)
+			monthStart := time.Date(now.Year(), now.Month(), 1, 0, 0, 0, 0, periodLocation)
+			merged := newAccountMerger()
+			empty := 0
+			for i := 0; i < months && empty < 2; i++ {
+				start := monthStart.AddDate(0, -i, 0)
+				end := start.AddDate(0, 1, 0).Add(-time.Second)
+				if end.After(now) {
+					end = now
+				}
+				if i > 0 && delay > 0 {
+					time.Sleep(delay)
+				}
+
+				accounts, apiErrors, err := getTransactionsForWindow(settings, start, end)
+				if err != nil {
+					return fmt.Errorf("error fetching %s: %w", start.Format("2006-01"), err)
+				}
+
+				count := 0
+				for _, account := range accounts {
+					count += len(account.Transactions)
+				}
+				if count == 0 {
+					empty++
+				} else {
+					empty = 0
+				}
+				merged.Add(accounts, apiErrors)
+				log.Info().Str("month", start.Format("2006-01")).Int("transactions", count).Msg("⏪ Backfilled month")
+			}
+
+			for _, apiErr := range merged.Errors {
+				log.Warn().Str("error", apiErr).Msg("API Error")
+			}
+
+			state := loadAccountState(settings.CacheDir)
+			accounts := mergeReplacedAccounts(merged.Accounts, state)
+			accounts = skipInactiveAccounts(accounts, state, setup.Config.IncludeArchived)
+			applyOrganizationOverrides(accounts, loadOrganizationOverrides(settings.CacheDir))
+			accounts = excludeAccounts(accounts, filterConfig, AccountScopeSync)
+			for i := range accounts {
+				sort.Slice(accounts[i].Transactions, func(a, b int) bool {
+					return accounts[i].Transactions[a].Posted < accounts[i].Transactions[b].Posted
+				})
+			}
+
+			file, err := os.Create(output)
+			if err != nil {
+				return fmt.Errorf("error creating backfill file: %w", err)
+			}
+			defer file.Close()
+
+			if err := exportAccounts(file, accounts, false, 0); err != nil {
+				return err
+			}
+
+			log.Info().Str("path", output).Int("accounts", len(accounts)).Msg("📦 Backfill written")
+			return nil
+		},
+	}
+	cmd.Flags().Int("months", 24, "Number of months to walk back, including the current one")
+	cmd.Flags().Duration("delay", 5*time.Second, "Pause between SimpleFin requests")
+	cmd.Flags().StringP("output", "o", "backfill.json", "Output file")
+	cmd.Flags().Bool("include-archived", false, "Include archived accounts")
+	return cmd
+}
+
+// newFeedCommand creates the feed command, which writes Atom feeds for feed readers
+func newFeedCommand() *cobra.Command {
+	feedCmd := &cobra.Command{
+		Use:   "feed",
+		Short: "Write Atom feeds of summaries and large transactions",
+		Long: `Write Atom feeds that can be served by any static web server and followed in a feed
+reader instead of email. Run from cron after each summary to keep them current.
+
+Example usage:
+  finance_tracker feed summaries -o /var/www/feeds/summaries.xml
+  finance_tracker feed transactions --min-amount 100 -o /var/www/feeds/transactions.xml`,
+	}
+
+	summariesCmd := &cobra.Command{
+		Use:   "summaries",
+		Short: "Write a feed of the cached AI summaries",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			output, _ := cmd.Flags().GetString("output")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+			if settings.CacheDir == "" {
+				return fmt.Errorf("summaries are unavailable (set CACHE_DIR)")
+			}
+
+			cache := loadSummaryCache(settings.CacheDir)
+			if cache == nil {
+				return fmt.Errorf("summary cache is unavailable")
+			}
+
+			return saveAtomFeed(output, buildSummariesFeed(cache.Summaries))
+		},
+	}
+	summariesCmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+
+	transactionsCmd := &cobra.Command{
+		Use:   "transactions",
+		Short: "Write a feed of transactions for the period above a minimum amount",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			minAmount, _ := cmd.Flags().GetFloat64("min-amount")
+			output, _ := cmd.Flags().GetString("output")
+
+			_, accounts, _, _, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+
+			return saveAtomFeed(output, buildTransactionsFeed(accounts, minAmount))
+		},
+	}
+	addPeriodFlags(transactionsCmd)
+	transactionsCmd.Flags().Float64("min-amount", 100, "Only include transactions of at least this absolute amount")
+	transactionsCmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+
+	feedCmd.AddCommand(summariesCmd)
+	feedCmd.AddCommand(transactionsCmd)
+	return feedCmd
+}
+
+// newHistoryCommand creates the history command, which lists recent runs
+func newHistoryCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "history",
+		Short: "Show the run in progress and recent runs with their status and duration",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			limit, _ := cmd.Flags().GetInt("limit")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			if lock, ok := readRunLock(settings.CacheDir); ok {
+				status := "in progress"
+				if lock.stale(time.Now()) {
+					status = "abandoned (the lock will be broken by the next run)"
+				}
+				command := lock.Command
+				if lock.Profile != "" {
+					command += " --profile " + lock.Profile
+				}
+				fmt.Printf("Current run: %s started %s (pid %d), %s\n\n",
+					command, lock.StartedAt.Local().Format("2006-01-02 15:04:05"), lock.PID, status)
+			}
+
+			records, err := loadRunHistory(settings.CacheDir)
+			if err != nil {
+				return err
+			}
+			if len(records) == 0 {
+				log.Info().Msg("No runs recorded yet")
+				return nil
+			}
+
+			if limit > 0 && len(records) > limit {
+				records = records[len(records)-limit:]
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "STARTED\tCOMMAND\tSTATUS\tDURATION\tERROR")
+			for i := len(records) - 1; i >= 0; i-- {
+				record := records[i]
+				errorText := record.Error
+				if errorText == "" && len(record.SyncIssues) > 0 {
+					errorText = strings.Join(record.SyncIssues, "; ")
+				}
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\n",
+					record.StartedAt.Local().Format("2006-01-02 15:04:05"),
+					record.Command,
+					record.Status,
+					(time.Duration(record.DurationMS) * time.Millisecond).String(),
+					errorText)
+			}
+			return w.Flush()
+		},
+	}
+	cmd.Flags().Int("limit", 20, "Number of most recent runs to show (0 for all)")
+	return cmd
+}
+
+// newCacheCommand creates the cache command with its subcommands
+func newCacheCommand() *cobra.Command {
+	cacheCmd := &cobra.Command{
+		Use:   "cache",
+		Short: "Manage cached AI summaries",
+	}
+
+	clearCmd := &cobra.Command{
+		Use:   "clear",
+		Short: "Remove all cached AI summaries",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			cache, err := newCacheStore(settings)
+			if err != nil {
+				return fmt.Errorf("error opening summary cache: %w", err)
+			}
+			if err := cache.Clear(); err != nil {
+				return err
+			}
+
+			log.Info().Str("cache_dir", settings.CacheDir).Msg("🧹 Summary cache cleared")
+			return nil
+		},
+	}
+
+	cacheCmd.AddCommand(clearCmd)
+	return cacheCmd
+}
+
+// newNotifyCommand creates the notify command with its subcommands
+func newNotifyCommand() *cobra.Command {
+	notifyCmd := &cobra.Command{
+		Use:   "notify",
+		Short: "Manage notification channels",
+	}
+
+	testCmd := &cobra.Command{
+		Use:   "test",
+		Short: "Send a test message through notification channels",
+		Long: `Send a canned test message through one or more notification channels and report
+the exact failure for any channel that can't deliver it.
+
+Example usage:
+  finance_tracker notify test                          # Test all channels
+  finance_tracker notify test --channel email          # Test email only
+  finance_tracker notify test --channel ntfy --warning # Test the ntfy warning topic`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			channels, _ := cmd.Flags().GetStringSlice("channel")
+			warning, _ := cmd.Flags().GetBool("warning")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			notificationTopic := "info"
+			if warning {
+				notificationTopic = "warning"
+			}
+
+			failed := 0
+			for _, channel := range channels {
+				log.Info().Str("channel", channel).Str("topic", notificationTopic).Msg("📨 Sending test notification...")
+				if err := sendTestNotification(settings, NotificationType(channel), notificationTopic); err != nil {
+					log.Error().Err(err).Str("channel", channel).Msg("❌ Test notification failed")
+					failed++
+					continue
+				}
+				log.Info().Str("channel", channel).Msg("✅ Test notification delivered")
+			}
+
+			if failed > 0 {
+				return fmt.Errorf("%d of %d test notifications failed", failed, len(channels))
+			}
+			return nil
+		},
+	}
+	testCmd.Flags().StringSliceP("channel", "c", []string{"email", "ntfy"}, "Notification channels to test (email, ntfy, gotify)")
+	testCmd.Flags().Bool("warning", false, "Send through the warning channel instead of the regular one")
+
+	previewCmd := &cobra.Command{
+		Use:   "preview [period]",
+		Short: "Render a cached summary through notification channels without sending it",
+		Long: `Render a cached AI summary exactly as each channel would send it, using the current
+templates, so TEMPLATES_DIR edits can be checked before the next scheduled run. ntfy
+and Gotify payloads are printed and email HTML is written to a temporary file. The
+period is a summary cache key (e.g. current_month:2025-01-15); without one the most
+recent summary is used. Cached summaries don't keep their transactions, so the email
+transaction table is empty.
+
+Example usage:
+  finance_tracker notify preview                                  # Latest summary, all channels
+  finance_tracker notify preview --channel email                  # Latest summary as email
+  finance_tracker notify preview current_month:2025-01-15 -c ntfy # A specific period`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			channels, _ := cmd.Flags().GetStringSlice("channel")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+			if settings.CacheDir == "" {
+				return fmt.Errorf("summaries are unavailable (set CACHE_DIR)")
+			}
+
+			cache := loadSummaryCache(settings.CacheDir)
+			if cache == nil || len(cache.Summaries) == 0 {
+				return fmt.Errorf("no cached summaries to preview")
+			}
+
+			period := ""
+			if len(args) > 0 {
+				period = args[0]
+			} else {
+				for key, summary := range cache.Summaries {
+					if period == "" || summary.CreatedAt.After(cache.Summaries[period].CreatedAt) {
+						period = key
+					}
+				}
+			}
+			summary, ok := cache.Summaries[period]
+			if !ok {
+				periods := make([]string, 0, len(cache.Summaries))
+				for key := range cache.Summaries {
+					periods = append(periods, key)
+				}
+				sort.Strings(periods)
+				return fmt.Errorf("no cached summary for %q (available: %s)", period, strings.Join(periods, ", "))
+			}
+
+			log.Info().Str("period", period).Time("created_at", summary.CreatedAt).Msg("🧪 Rendering cached summary")
+			return renderNotification(settings, summary.Summary, nil, nil, "info", channels)
+		},
+	}
+	previewCmd.Flags().StringSliceP("channel", "c", []string{"email", "ntfy", "gotify"}, "Notification channels to render (email, ntfy, gotify)")
+
+	notifyCmd.AddCommand(testCmd)
+	notifyCmd.AddCommand(previewCmd)
+	return notifyCmd
+}
+
+// newConfigCommand creates the config command with its init wizard
+func newConfigCommand() *cobra.Command {
+	configCmd := &cobra.Command{
+		Use:   "config",
+		Short: "Manage the configuration file",
+	}
+
+	initCmd := &cobra.Command{
+		Use:   "init",
+		Short: "Interactively write a starter config file",
+		Long: `Ask for the most common settings and write them to a YAML config file. Every other
+setting is listed as a commented-out line. Settings use the environment variable names in
+lowercase (e.g. simplefin_bridge_url).
+
+Precedence: command-line flags > environment variables (.env) > config file > defaults.`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			verbose, _ := cmd.Flags().GetBool("verbose")
+			configFile, _ := cmd.Flags().GetString("config")
+			force, _ := cmd.Flags().GetBool("force")
+
+			initLogger(verbose)
+
+			if configFile == "" {
+				configFile = defaultConfigFile()
+			}
+			if configFile == "" {
+				return fmt.Errorf("could not determine the config directory, pass --config")
+			}
+
+			if _, err := os.Stat(configFile); err == nil && !force {
+				return fmt.Errorf("config file %s already exists (use --force to overwrite)", configFile)
+			}
+
+			values, err := runConfigWizard(os.Stdin, os.Stdout)
+			if err != nil {
+				return err
+			}
+
+			if err := writeConfigFile(configFile, values); err != nil {
+				return err
+			}
+
+			log.Info().Str("config_file", configFile).Msg("📝 Config file written")
+			return nil
+		},
+	}
+	initCmd.Flags().Bool("force", false, "Overwrite an existing config file")
+
+	configCmd.AddCommand(initCmd)
+	return configCmd
+}
+
+// newStatusCommand creates the status command, which prints a one-line balance summary for status bars
+func newStatusCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "status",
+		Short: "Print a single-line balance summary for status bars",
+		Long: `Print account balances and the current billing cycle's spending on one line, for use in
+polybar, i3status or menu bar scripts. The line is cached for --max-age so frequent refreshes
+don't hit SimpleFin.
+
+Example usage:
+  finance_tracker status --accounts Chequing,Visa
+  finance_tracker status --max-age 5m`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			selection, _ := cmd.Flags().GetStringSlice("accounts")
+			maxAge, _ := cmd.Flags().GetDuration("max-age")
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			selectionKey := strings.Join(selection, ",")
+			if line, ok := loadCachedStatus(settings.CacheDir, selectionKey, maxAge); ok {
+				fmt.Println(line)
+				return nil
+			}
+
+			// Default to the current billing cycle across all account types
+			if !cmd.Flags().Changed("date-range") {
+				setup.Config.DateRange = string(DateRangeTypeCurrentMonth)
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			line := formatStatusLine(selectStatusAccounts(accounts, selection))
+			if err := storeCachedStatus(settings.CacheDir, selectionKey, line); err != nil {
+				log.Warn().Err(err).Msg("Failed to cache status line")
+			}
+
+			fmt.Println(line)
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().StringSlice("accounts", nil, "Accounts to show, by ID or name (default: all)")
+	cmd.Flags().Duration("max-age", 15*time.Minute, "Reuse the cached status line if it is newer than this (0 disables caching)")
+	return cmd
+}
+
+// newDoctorCommand creates the doctor command, which validates the configuration against the real services
+func newDoctorCommand() *cobra.Command {
+	return &cobra.Command{
+		Use:     "doctor",
+		Aliases: []string{"check"},
+		Short:   "Validate settings and connectivity to SimpleFin, OpenRouter, the local model, SMTP and ntfy",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				fmt.Printf("❌ Settings: %v\n", err)
+				return err
+			}
+
+			if failed := writeDoctorReport(os.Stdout, runDoctorChecks(setup.Settings)); failed > 0 {
+				return fmt.Errorf("%d checks failed", failed)
+			}
+			return nil
+		},
+	}
+}
+
+// newYNABCommand creates the ynab command for exporting to and pushing into YNAB
+func newYNABCommand() *cobra.Command {
+	ynabCmd := &cobra.Command{
+		Use:   "ynab",
+		Short: "Export or push transactions to YNAB",
+	}
+
+	exportCmd := &cobra.Command{
+		Use:   "export",
+		Short: "Write transactions as a YNAB-compatible CSV file",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			output, _ := cmd.Flags().GetString("output")
+
+			_, accounts, _, _, err := loadCommandAccounts(cmd)
+			if err != nil {
+				return err
+			}
+
+			if output == "" || output == "-" {
+				return writeYNABCSV(os.Stdout, accounts)
+			}
+
+			file, err := os.Create(output)
+			if err != nil {
+				return fmt.Errorf("error creating CSV file: %w", err)
+			}
+			defer file.Close()
+
+			if err := writeYNABCSV(file, accounts); err != nil {
+				return err
+			}
+
+			log.Info().Str("path", output).Int("accounts", len(accounts)).Msg("📦 YNAB CSV written")
+			return nil
+		},
+	}
+	addPeriodFlags(exportCmd)
+	exportCmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+
+	pushCmd := &cobra.Command{
+		Use:   "push",
+		Short: "Push transactions to the YNAB budget (deduplicated by import ID)",
+		Long: `Push transactions into YNAB_BUDGET_ID using YNAB_TOKEN. Each account needs a
+ynab_account_id in the filter config's accounts section; unmapped accounts are skipped.
+Import IDs are derived from the SimpleFin transaction IDs, so pushing the same period
+again doesn't create duplicates.`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+			if settings.YNABToken == "" || settings.YNABBudgetID == "" {
+				return fmt.Errorf("YNAB_TOKEN and YNAB_BUDGET_ID must be set")
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			transactions := buildYNABTransactions(accounts, setup.FilterConfig)
+			created, duplicates, err := pushYNABTransactions(settings, transactions)
+			if err != nil {
+				return err
+			}
+
+			log.Info().
+				Int("created", created).
+				Int("duplicates", duplicates).
+				Msg("📤 Pushed transactions to YNAB")
+			return nil
+		},
+	}
+	addPeriodFlags(pushCmd)
+
+	ynabCmd.AddCommand(exportCmd)
+	ynabCmd.AddCommand(pushCmd)
+	return ynabCmd
+}
+
+// newFireflyCommand creates the firefly command for mirroring data into Firefly III
+func newFireflyCommand() *cobra.Command {
+	fireflyCmd := &cobra.Command{
+		Use:   "firefly",
+		Short: "Mirror accounts and transactions into Firefly III",
+	}
+
+	syncCmd := &cobra.Command{
+		Use:   "sync",
+		Short: "Create missing accounts and transactions in Firefly III",
+		Long: `Mirror accounts and transactions for the period into the Firefly III instance at
+FIREFLY_URL using FIREFLY_TOKEN. Each account maps to a Firefly asset account: the
+firefly_account_id from the filter config, an existing asset account with the same name,
+or a newly created one. Firefly rejects duplicate transactions, so syncing a period again
+is safe. Run it from cron to use finance_tracker as a SimpleFin to Firefly III bridge.`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+			if _, err := newFireflyClient(settings); err != nil {
+				return err
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			filterConfig := setup.FilterConfig
+			for i := range accounts {
+				applyProviderCategories(accounts[i].Transactions, filterConfig)
+			}
+
+			result, err := syncToFirefly(settings, accounts, filterConfig)
+			if err != nil {
+				return err
+			}
+
+			log.Info().
+				Int("accounts_created", result.AccountsCreated).
+				Int("transactions_created", result.TransactionsCreated).
+				Int("duplicates", result.Duplicates).
+				Msg("🔥 Synced to Firefly III")
+			return nil
+		},
+	}
+	addPeriodFlags(syncCmd)
+
+	fireflyCmd.AddCommand(syncCmd)
+	return fireflyCmd
+}
+
+// newGoalsCommand creates the goals command, which shows progress toward savings goals
+func newGoalsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "goals",
+		Short: "Show progress toward savings goals",
+		Long: `Show progress toward the savings goals defined under goals: in the filter config. Each
+goal tracks the balance of its linked account against a target, and the account's net change
+over the period is used as the current savings pace.
+
+Example usage:
+  finance_tracker goals
+  finance_tracker goals --date-range last_3_months`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+
+			filterConfig := setup.FilterConfig
+			if filterConfig == nil || len(filterConfig.Goals) == 0 {
+				return fmt.Errorf("no goals configured, add them under goals: in the filter config")
+			}
+
+			// Goals usually track savings accounts
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, start, end, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			fmt.Print(formatGoalsSummary(computeGoalProgress(accounts, filterConfig, start, end, time.Now().In(periodLocation))))
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	return cmd
+}
+
+// newForecastCommand creates the forecast command, which projects end-of-month balances
+func newForecastCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "forecast",
+		Short: "Project end-of-month balances and flag accounts predicted to go negative",
+		Long: `Project each account's balance at the end of the current month. Recurring transactions
+(same merchant at a regular interval) detected in the history window are projected forward,
+and all other activity uses the account's average daily net over the window. Accounts other
+than credit cards that are predicted to go negative are flagged.
+
+The history window defaults to the last 3 months.
+
+Example usage:
+  finance_tracker forecast
+  finance_tracker forecast --date-range last_month`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			if !cmd.Flags().Changed("date-range") {
+				setup.Config.DateRange = string(DateRangeTypeLast3Months)
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, start, end, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			now := time.Now().In(periodLocation)
+			return writeForecast(os.Stdout, forecastAccounts(accounts, start, end, now), now)
+		},
+	}
+	addPeriodFlags(cmd)
+	return cmd
+}
+
+// newTrendsCommand creates the trends command, which prints monthly spending with a moving average
+func newTrendsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "trends",
+		Short: "Show monthly spending over time with a moving average",
+		Long: `Show spending per calendar month for the last --months months (including the current
+one), optionally limited to a category, with a trailing moving average for charting long-term
+habits. Categories come from category_mappings in the filter config. Transfers between accounts
+are ignored and linked refunds are netted. Spans longer than the history SimpleFin returns show
+empty months.
+
+Example usage:
+  finance_tracker trends --category Dining --months 12
+  finance_tracker trends --months 6 --window 2 --json`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			category, _ := cmd.Flags().GetString("category")
+			months, _ := cmd.Flags().GetInt("months")
+			window, _ := cmd.Flags().GetInt("window")
+			jsonOutput, _ := cmd.Flags().GetBool("json")
+
+			if months < 1 {
+				return fmt.Errorf("--months must be at least 1")
+			}
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+
+			now := time.Now().In(periodLocation)
+			start := time.Date(now.Year(), now.Month()-time.Month(months-1), 1, 0, 0, 0, 0, periodLocation)
+			setup.Config.DateRange = string(DateRangeTypeCustom)
+			setup.Config.StartDate = start.Format("2006-01-02")
+			setup.Config.EndDate = now.Format("2006-01-02")
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			filterConfig := setup.FilterConfig
+			for i := range accounts {
+				applyProviderCategories(accounts[i].Transactions, filterConfig)
+			}
+
+			points := buildTrend(accounts, category, start, months, window)
+			if jsonOutput {
+				return writeTrendJSON(os.Stdout, points)
+			}
+			return writeTrendText(os.Stdout, points, category, window)
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().String("category", "", "Only include this category (from category_mappings)")
+	cmd.Flags().Int("months", 12, "Number of calendar months to show, including the current one")
+	cmd.Flags().Int("window", 3, "Number of months in the moving average")
+	cmd.Flags().Bool("json", false, "Output the series as JSON")
+	return cmd
+}
+
+// newFlowsCommand creates the flows command, which prints income → category → merchant aggregates
+func newFlowsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "flows",
+		Short: "Show where money went as income → category → merchant flows",
+		Long: `Aggregate the period's money flow across all accounts: income into each spending
+category and each category into its merchants, with income left over flowing into "Savings".
+With --json, the nodes and links can be fed directly to a Sankey diagram library (e.g. d3-sankey
+or Plotly). Categories come from category_mappings in the filter config.
+
+Example usage:
+  finance_tracker flows --date-range last_month
+  finance_tracker flows --date-range last_month --json > flows.json`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			jsonOutput, _ := cmd.Flags().GetBool("json")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			filterConfig := setup.FilterConfig
+			for i := range accounts {
+				applyProviderCategories(accounts[i].Transactions, filterConfig)
+			}
+
+			report := buildFlowReport(accounts)
+			if jsonOutput {
+				return writeFlowReportJSON(os.Stdout, report)
+			}
+			return writeFlowReportText(os.Stdout, report)
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("json", false, "Output nodes and links as JSON for Sankey diagrams")
+	return cmd
+}
+
+// newTaxReportCommand creates the tax-report command, which exports a year's transactions grouped
+// by tax line for an accountant
+func newTaxReportCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "tax-report",
+		Short: "Export a year's transactions grouped by tax line as CSV",
+		Long: `Export the transactions of a calendar year that match the tax_rules in the filter config,
+grouped by tax line (e.g. "Charitable donations", "Medical") with a total row per line, as CSV
+for an accountant. Each transaction goes to the first rule it matches. Transfers between
+accounts are ignored and linked refunds are netted. Years beyond the history SimpleFin returns
+are incomplete.
+
+Example usage:
+  finance_tracker tax-report --year 2025 -o tax-2025.csv`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			year, _ := cmd.Flags().GetInt("year")
+			output, _ := cmd.Flags().GetString("output")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			filterConfig := setup.FilterConfig
+			if filterConfig == nil || len(filterConfig.TaxRules) == 0 {
+				return fmt.Errorf("no tax_rules in the filter config (see config.example.yaml)")
+			}
+
+			now := time.Now().In(periodLocation)
+			if year == 0 {
+				year = now.Year() - 1
+			}
+			start := time.Date(year, time.January, 1, 0, 0, 0, 0, periodLocation)
+			end := time.Date(year, time.December, 31, 0, 0, 0, 0, periodLocation)
+			if end.After(now) {
+				end = now
+			}
+			setup.Config.DateRange = string(DateRangeTypeCustom)
+			setup.Config.StartDate = start.Format("2006-01-02")
+			setup.Config.EndDate = end.Format("2006-01-02")
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+			for i := range accounts {
+				applyProviderCategories(accounts[i].Transactions, filterConfig)
+			}
+
+			items := assignTaxLines(accounts, filterConfig.TaxRules)
+			if output == "" || output == "-" {
+				if err := writeTaxReportCSV(os.Stdout, items); err != nil {
+					return err
+				}
+				fmt.Fprint(os.Stderr, formatTaxLineTotals(totalTaxLines(items)))
+				return nil
+			}
+
+			file, err := os.Create(output)
+			if err != nil {
+				return fmt.Errorf("error creating tax report: %w", err)
+			}
+			defer file.Close()
+
+			if err := writeTaxReportCSV(file, items); err != nil {
+				return err
+			}
+			fmt.Print(formatTaxLineTotals(totalTaxLines(items)))
+
+			log.Info().
+				Str("path", output).
+				Int("year", year).
+				Int("transactions", len(items)).
+				Msg("🧾 Tax report written")
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Int("year", 0, "Calendar year to report (default: last year)")
+	cmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+	return cmd
+}
+
+// newOrgsCommand creates the orgs command, which lists each institution's accounts, last sync
+// and bridge errors
+func newOrgsCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "orgs",
+		Short: "List institutions with their accounts, last sync and connection errors",
+		Long: `List each institution (SimpleFin organization) with its accounts, when its accounts last
+synced and the bridge errors that mention it. Errors that don't name an institution are listed
+separately.
+
+Banks sometimes appear twice with different organization IDs after reconnecting. "merge" moves
+the accounts of one organization to another, and "rename" sets the name shown everywhere.
+Account histories are kept by account ID, so they are unaffected.
+
+Example usage:
+  finance_tracker orgs
+  finance_tracker orgs --json
+  finance_tracker orgs rename ORG-123 "Chase"
+  finance_tracker orgs merge ORG-456 ORG-123`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			jsonOutput, _ := cmd.Flags().GetBool("json")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings, filterConfig := setup.Settings, setup.FilterConfig
+			config := setup.Config
+			config.DisableNotifications = true
+
+			// Only balances and errors are needed, so fetch a short window
+			now := time.Now()
+			accounts, apiErrors, err := fetchAccounts(settings, config, now.AddDate(0, 0, -7), now)
+			if err != nil {
+				return err
+			}
+			accounts = excludeAccounts(accounts, filterConfig, AccountScopeSync)
+			applyAccountDisplaySettings(accounts, filterConfig)
+
+			report := buildOrganizationReport(accounts, apiErrors)
+			if jsonOutput {
+				return writeOrganizationReportJSON(os.Stdout, report)
+			}
+			return writeOrganizationReportText(os.Stdout, report)
+		},
+	}
+	cmd.Flags().Bool("include-archived", false, "Include archived accounts")
+	cmd.Flags().Bool("json", false, "Output the organizations as JSON")
+
+	cmd.AddCommand(newOrgsEditCommand("rename <org-id> <name>", "Set the display name of an organization", func(overrides *OrganizationOverrides, args []string) error {
+		overrides.Names[args[0]] = args[1]
+		log.Info().Str("organization_id", args[0]).Str("name", args[1]).Msg("🏦 Organization renamed")
+		return nil
+	}))
+	cmd.AddCommand(newOrgsEditCommand("merge <from-org-id> <into-org-id>", "Move the accounts of an organization to another", func(overrides *OrganizationOverrides, args []string) error {
+		if err := overrides.Merge(args[0], args[1]); err != nil {
+			return err
+		}
+		log.Info().Str("from", args[0]).Str("into", args[1]).Msg("🏦 Organizations merged")
+		return nil
+	}))
+	cmd.AddCommand(newOrgsEditCommand("unmerge <org-id>", "Undo merging an organization into another", func(overrides *OrganizationOverrides, args []string) error {
+		if _, ok := overrides.Merged[args[0]]; !ok {
+			return fmt.Errorf("organization %q is not merged", args[0])
+		}
+		delete(overrides.Merged, args[0])
+		log.Info().Str("organization_id", args[0]).Msg("🏦 Organization unmerged")
+		return nil
+	}))
+	return cmd
+}
+
+// newOrgsEditCommand creates an orgs subcommand that edits the organization overrides
+func newOrgsEditCommand(use string, short string, edit func(*OrganizationOverrides, []string) error) *cobra.Command {
+	return &cobra.Command{
+		Use:   use,
+		Short: short,
+		Args:  cobra.ExactArgs(len(strings.Fields(use)) - 1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			overrides := loadOrganizationOverrides(settings.CacheDir)
+			if overrides == nil {
+				return fmt.Errorf("organization overrides are unavailable (set CACHE_DIR)")
+			}
+			if err := edit(overrides, args); err != nil {
+				return err
+			}
+			return overrides.Save()
+		},
+	}
+}
+
+// newNetWorthCommand creates the networth command, which totals synced and manual account balances
+// and records the total in the net worth history
+func newNetWorthCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "networth",
+		Short: "Show net worth across synced and manual accounts",
+		Long: `Total the balances of every synced account and every manual account (see
+"finance_tracker accounts manual"). Credit card balances count as liabilities. Holdings priced
+more than a day ago are revalued first. Each run records
+the day's total in CACHE_DIR/net_worth_history.json; --history lists the recorded days.
+
+Example usage:
+  finance_tracker networth
+  finance_tracker networth --history`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			history, _ := cmd.Flags().GetBool("history")
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+			netWorth := loadNetWorthHistory(settings.CacheDir)
+
+			if history {
+				if netWorth == nil {
+					return fmt.Errorf("net worth history is unavailable (set CACHE_DIR)")
+				}
+
+				w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+				fmt.Fprintln(w, "DATE\tSYNCED\tMANUAL\tNET WORTH")
+				for _, snapshot := range netWorth.Snapshots {
+					fmt.Fprintf(w, "%s\t%s\t%s\t%s\n", snapshot.Date, snapshot.Synced, snapshot.Manual, snapshot.Total())
+				}
+				return w.Flush()
+			}
+
+			// Only the balances are needed, so fetch a short period across all accounts
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+			if !cmd.Flags().Changed("date-range") {
+				setup.Config.DateRange = string(DateRangeTypeLastMonth)
+			}
+
+			accounts, _, _, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			// Refresh holdings priced more than a day ago, so a daily cron job keeps the history current
+			manual := loadManualAccounts(settings.CacheDir)
+			if revalued, errs := manual.Revalue(newHTTPClient(30*time.Second), time.Now().UTC(), false); revalued > 0 {
+				for _, err := range errs {
+					log.Warn().Err(err).Msg("Failed to fetch price, keeping the last one")
+				}
+				if err := manual.Save(); err != nil {
+					log.Warn().Err(err).Msg("Failed to save revalued manual accounts")
+				}
+			}
+			manualAccounts := manual.List()
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "ACCOUNT\tKIND\tBALANCE")
+			for _, account := range accounts {
+				fmt.Fprintf(w, "%s\t%s\t%s\n", accountDisplayName(account), "synced", toMoney(account.Balance))
+			}
+			for _, account := range manualAccounts {
+				fmt.Fprintf(w, "%s\t%s\t%s\n", account.Name, account.Kind, toMoney(account.Balance))
+			}
+
+			snapshot := computeNetWorth(accounts, manualAccounts, time.Now())
+			fmt.Fprintf(w, "\t\t\nNET WORTH\t\t%s\n", snapshot.Total())
+			if err := w.Flush(); err != nil {
+				return err
+			}
+
+			if netWorth != nil {
+				netWorth.Record(snapshot)
+				if err := netWorth.Save(); err != nil {
+					log.Warn().Err(err).Msg("Failed to save net worth history")
+				}
+			}
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	cmd.Flags().Bool("history", false, "List the recorded daily net worth instead of computing it")
+	return cmd
+}
+
+// newReconcileCommand creates the reconcile command, which checks that reported balances match the
+// previous balances plus the transactions posted since
+func newReconcileCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "reconcile",
+		Short: "Check that account balances match their transactions",
+		Long: `Compare each account's reported balance with the balance recorded by the previous run plus
+the transactions posted since. A gap usually means the bank connection missed transactions. Gaps
+are stored in CACHE_DIR and reported through the warning notification channel. The first run
+only records the opening balances, so schedule it (e.g. daily from cron) to keep checking.
+
+Example usage:
+  finance_tracker reconcile
+  finance_tracker reconcile --history`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			history, _ := cmd.Flags().GetBool("history")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings, config, filterConfig := setup.Settings, setup.Config, setup.FilterConfig
+
+			reconciliation := loadReconciliation(settings.CacheDir)
+			if reconciliation == nil {
+				return fmt.Errorf("reconciliation is unavailable (set CACHE_DIR)")
+			}
+
+			gaps := reconciliation.Gaps
+			if !history {
+				now := time.Now()
+				accounts, _, err := fetchAccounts(settings, config, reconciliation.FetchStart(now), now)
+				if err != nil {
+					return err
+				}
+				accounts = excludeAccounts(accounts, filterConfig, AccountScopeSync)
+				applyAccountDisplaySettings(accounts, filterConfig)
+
+				var checked int
+				gaps, checked = reconciliation.Reconcile(accounts, now.UTC())
+				log.Info().Int("accounts_checked", checked).Int("gaps", len(gaps)).Msg("🧮 Reconciled account balances")
+
+				// Zero-balance accounts aren't fetched with transactions, so their checkpoints are
+				// settled from the account list
+				if unfetched := reconciliation.Unfetched(accounts); len(unfetched) > 0 {
+					listed, err := getAccountList(settings)
+					if err != nil {
+						log.Warn().Err(err).Msg("Failed to list accounts, keeping the checkpoints of unfetched accounts")
+					} else {
+						reconciliation.Forward(unfetched, listed)
+					}
+				}
+
+				if err := notifyReconciliationGaps(settings, config, reconciliation, accounts, filterConfig); err != nil {
+					log.Error().Err(err).Msg("Failed to send reconciliation warning")
+				}
+				if !config.DryRun {
+					if err := reconciliation.Save(); err != nil {
+						return err
+					}
+				}
+			}
+
+			if len(gaps) == 0 {
+				fmt.Println("All balances reconcile")
+				return nil
+			}
+
+			w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+			fmt.Fprintln(w, "CHECKED\tACCOUNT\tFROM\tTO\tEXPECTED\tREPORTED\tGAP")
+			for _, gap := range gaps {
+				fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%s\t%s\t%s\n",
+					gap.CheckedAt.Local().Format("2006-01-02 15:04"),
+					gap.Account,
+					gap.From.In(periodLocation).Format("2006-01-02"),
+					gap.To.In(periodLocation).Format("2006-01-02"),
+					gap.Expected,
+					gap.Reported,
+					gap.Gap())
+			}
+			return w.Flush()
+		},
+	}
+	cmd.Flags().Bool("history", false, "List the stored gaps without checking again")
+	cmd.Flags().Bool("include-archived", false, "Include archived accounts")
+	cmd.Flags().StringSliceP("notifications", "n", []string{"email", "ntfy"}, "Notification types to send (email, ntfy, gotify)")
+	cmd.Flags().Bool("disable-notifications", false, "Disable all notifications")
+	cmd.Flags().Bool("dry-run", false, "Render notifications locally and don't update the stored balances")
+	return cmd
+}
+
+// newAskCommand creates the ask command, which answers a free-form question about the period's
+// transactions with the LLM
+func newAskCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "ask <question>",
+		Short: "Ask a question about your transactions",
+		Long: `Answer a free-form question about the transactions in the period with the AI model, e.g.
+"how much did I spend on restaurants?". The period's transactions across all accounts and exact
+spending totals are sent along with the question; pick the period the question is about with
+--date-range or --start-date/--end-date (default: last 3 months).
+
+Example usage:
+  finance_tracker ask "how much did I spend on restaurants?"
+  finance_tracker ask "what was my largest purchase?" --start-date 2026-03-01 --end-date 2026-03-31`,
+		Args: cobra.MinimumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			if !cmd.Flags().Changed("date-range") {
+				if cmd.Flags().Changed("start-date") {
+					setup.Config.DateRange = string(DateRangeTypeCustom)
+				} else {
+					setup.Config.DateRange = string(DateRangeTypeLast3Months)
+				}
+			}
+			if !cmd.Flags().Changed("all-accounts") {
+				setup.Config.AllAccounts = true
+			}
+
+			accounts, start, end, err := setup.periodAccounts()
+			if err != nil {
+				return err
+			}
+
+			prompt := generateQuestionPrompt(accounts, start, end, strings.Join(args, " "))
+			log.Debug().Str("prompt", prompt).Msg("Generated question prompt")
+
+			if _, err := getLLMResponse(llmSettings(setup.Settings, LLMTaskAsk), prompt, false, os.Stdout); err != nil {
+				return fmt.Errorf("error getting LLM response: %w", err)
+			}
+			fmt.Println()
+			return nil
+		},
+	}
+	addPeriodFlags(cmd)
+	return cmd
+}
+
+// newBackupCommand creates the backup command, which archives the local state files
+func newBackupCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "backup",
+		Short: "Archive cached summaries, account state and run history to a JSON file",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			output, _ := cmd.Flags().GetString("output")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			if output == "" || output == "-" {
+				_, err := createBackup(settings.CacheDir, os.Stdout)
+				return err
+			}
+
+			// Written to a temporary file and renamed, so a failed backup never replaces a good one
+			var archive bytes.Buffer
+			count, err := createBackup(settings.CacheDir, &archive)
+			if err != nil {
+				return err
+			}
+			if err := writeFileAtomic(output, archive.Bytes()); err != nil {
+				return fmt.Errorf("error writing backup file: %w", err)
+			}
+
+			log.Info().Str("path", output).Int("files", count).Msg("💾 Backup written")
+			return nil
+		},
+	}
+	cmd.Flags().StringP("output", "o", "", "Output file (default: stdout)")
+	return cmd
+}
+
+// newRestoreCommand creates the restore command, which reloads state files from a backup
+func newRestoreCommand() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "restore <backup-file>",
+		Short: "Restore cached summaries, account state and run history from a backup",
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			force, _ := cmd.Flags().GetBool("force")
+
+			setup, err := loadCommand(cmd)
+			if err != nil {
+				return err
+			}
+			settings := setup.Settings
+
+			file, err := os.Open(args[0])
+			if err != nil {
+				return fmt.Errorf("error opening backup file: %w", err)
+			}
+			defer file.Close()
+
+			count, err := restoreBackup(settings.CacheDir, file, force)
+			if err != nil {
+				return err
+			}
+
+			log.Info().Str("cache_dir", settings.CacheDir).Int("files", count).Msg("♻️ Backup restored")
+			return nil
+		},
+	}
+	cmd.Flags().Bool("force", false, "Overwrite existing state files")
+	return cmd
+}
@@ -0,0 +1,136 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// maxComparedCategories is the number of categories listed in the period comparison
+const maxComparedCategories = 8
+
+// CategoryDelta holds expenses for a category in the analyzed and the previous period
+type CategoryDelta struct {
+	Category string
+	Current  Money // Expenses as a positive amount
+	Previous Money
+}
+
+// PeriodComparison compares expenses with the same span of the previous period
+type PeriodComparison struct {
+	PreviousStart time.Time
+	PreviousEnd   time.Time
+	Total         Money // Expenses as a positive amount
+	PreviousTotal Money
+	Categories    []CategoryDelta
+}
+
+// previousPeriod returns the period to compare against: the previous cycle (or day, or week)
+// covering the same number of days, so an in-progress cycle is compared with the same part of the
+// last one. Multi-cycle and yearly ranges already span several periods and are not compared.
+func previousPeriod(dateRangeType DateRangeType, start, end time.Time) (time.Time, time.Time, bool) {
+	var previousStart time.Time
+	switch dateRangeType {
+	case DateRangeTypeCurrentMonth, DateRangeTypeLastMonth:
+		previousStart = start.AddDate(0, -1, 0)
+	case DateRangeTypeYesterday:
+		previousStart = start.AddDate(0, 0, -1)
+	case DateRangeTypeLast7Days:
+		previousStart = start.AddDate(0, 0, -7)
+	case DateRangeTypeCustom:
+		previousStart = start.Add(-end.Sub(start))
+	default:
+		return time.Time{}, time.Time{}, false
+	}
+
+	previousEnd := previousStart.Add(end.Sub(start))
+	if !previousEnd.Before(start) {
+		previousEnd = start.Add(-time.Nanosecond)
+	}
+	return previousStart, previousEnd, true
+}
+
+// comparePeriods computes total and per-category expense deltas between two sets of expenses.
+// Only categorized transactions are compared per category.
+func comparePeriods(current, previous []Transaction, previousStart, previousEnd time.Time) PeriodComparison {
+	comparison := PeriodComparison{
+		PreviousStart: previousStart,
+		PreviousEnd:   previousEnd,
+		Total:         -sumMoney(current),
+		PreviousTotal: -sumMoney(previous),
+	}
+
+	deltas := make(map[string]*CategoryDelta)
+	add := func(tx Transaction, isCurrent bool) {
+		if tx.Category == "" {
+			return
+		}
+		category, _ := splitCategory(tx.Category)
+		delta, ok := deltas[category]
+		if !ok {
+			delta = &CategoryDelta{Category: category}
+			deltas[category] = delta
+		}
+		if isCurrent {
+			delta.Current -= toMoney(tx.Amount)
+		} else {
+			delta.Previous -= toMoney(tx.Amount)
+		}
+	}
+	for _, tx := range current {
+		add(tx, true)
+	}
+	for _, tx := range previous {
+		add(tx, false)
+	}
+
+	for _, delta := range deltas {
+		comparison.Categories = append(comparison.Categories, *delta)
+	}
+	sort.Slice(comparison.Categories, func(i, j int) bool {
+		a, b := comparison.Categories[i], comparison.Categories[j]
+		if a.Current != b.Current {
+			return a.Current > b.Current
+		}
+		return a.Category < b.Category
+	})
+	if len(comparison.Categories) > maxComparedCategories {
+		comparison.Categories = comparison.Categories[:maxComparedCategories]
+	}
+
+	log.Debug().
+		Str("total", comparison.Total.String()).
+		Str("previous_total", comparison.PreviousTotal.String()).
+		Int("categories", len(comparison.Categories)).
+		Msg("Compared with previous period")
+
+	return comparison
+}
+
+// formatDelta formats the change from previous to current as a signed percentage
+func formatDelta(current, previous Money) string {
+	if previous == 0 {
+		if current == 0 {
+			return "no change"
+		}
+		return "new"
+	}
+	return fmt.Sprintf("%+.0f%%", float64(current-previous)/float64(previous)*100)
+}
+
+// formatPeriodComparison formats the comparison as a prompt section
+func formatPeriodComparison(comparison *PeriodComparison) string {
+	if comparison == nil {
+		return ""
+	}
+
+	result := fmt.Sprintf("Comparison With Previous Period (%s to %s, same number of days, pre-calculated):\n",
+		comparison.PreviousStart.Format("2006-01-02"), comparison.PreviousEnd.Format("2006-01-02"))
+	result += fmt.Sprintf("- Total Expenses: %s vs %s (%s)\n", comparison.Total.Format(), comparison.PreviousTotal.Format(), formatDelta(comparison.Total, comparison.PreviousTotal))
+	for _, delta := range comparison.Categories {
+		result += fmt.Sprintf("- %s: %s vs %s (%s)\n", delta.Category, delta.Current.Format(), delta.Previous.Format(), formatDelta(delta.Current, delta.Previous))
+	}
+	return result
+}
@@ -0,0 +1,229 @@
+package main
+
+import (
+	"bufio"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/rs/zerolog/log"
+	"gopkg.in/yaml.v3"
+)
+
+// settingsEnvVars lists every environment variable read by NewSettings. The config file uses the
+// same names in lowercase (e.g. simplefin_bridge_url).
+var settingsEnvVars = []string{
+	"SIMPLEFIN_BRIDGE_URL",
+	"OPENROUTER_URL",
+	"OPENROUTER_API_KEY",
+	"OPENROUTER_MODEL",
+	"OPENROUTER_VISION_MODEL",
+	"LOCAL_LLM_URL",
+	"LOCAL_LLM_MODEL",
+	"LOCAL_LLM_TASKS",
+	"LLM_CATEGORIZE",
+	"LLM_CATEGORIZE_BATCH_SIZE",
+	"LLM_CATEGORIZE_CONCURRENCY",
+	"MAILER_URL",
+	"MAILER_FROM",
+	"MAILER_TO",
+	"MAILER_CC",
+	"MAILER_BCC",
+	"MAILER_TLS_MODE",
+	"MAILER_TLS_CA_FILE",
+	"MAILER_TLS_SKIP_VERIFY",
+	"MAILER_TIMEOUT",
+	"EMAIL_ATTACH_CSV",
+	"EMAIL_SUBJECT",
+	"NOTIFICATION_TITLE",
+	"HOUSEHOLD_NAME",
+	"NTFY_TOPIC",
+	"NTFY_WARNING_SUFFIX",
+	"GOTIFY_URL",
+	"GOTIFY_TOKEN",
+	"GOTIFY_PRIORITY",
+	"GOTIFY_WARNING_PRIORITY",
+	"QUIET_HOURS",
+	"NOTIFICATION_RATE_LIMITS",
+	"NOTIFICATION_BATCH_WINDOW",
+	"CURRENCY",
+	"ROUNDING_MODE",
+	"BILLING_CYCLE_DAY",
+	"SUMMARY_CADENCE",
+	"TIMEZONE",
+	"LOCALE",
+	"STALE_ACCOUNT_DAYS",
+	"SUBSCRIPTION_HISTORY_MONTHS",
+	"INTEREST_YEAR_TO_DATE",
+	"CREDIT_UTILIZATION_THRESHOLD",
+	"SIMPLEFIN_MAX_DAYS",
+	"BANK_LOGO_SERVICE",
+	"REDACT_ACCOUNT_NUMBERS",
+	"LOG_FORMAT",
+	"CACHE_BACKEND",
+	"CACHE_DIR",
+	"TEMPLATES_DIR",
+	"ATTACHMENTS_DIR",
+	"REPORT_ARCHIVE",
+	"FILTER_CONFIG_PATH",
+	"YNAB_TOKEN",
+	"YNAB_BUDGET_ID",
+	"FIREFLY_URL",
+	"FIREFLY_TOKEN",
+	"WEBHOOK_URLS",
+	"WEBHOOK_EVENTS",
+	"WEBHOOK_SECRET",
+	"EVENT_BUS_URL",
+}
+
+// defaultConfigFile returns the default config file location ($XDG_CONFIG_HOME/finance_tracker/config.yaml)
+func defaultConfigFile() string {
+	configDir, err := os.UserConfigDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(configDir, "finance_tracker", "config.yaml")
+}
+
+// applyConfigFile loads settings from a YAML config file into the environment. Variables that are
+// already set (from the environment or the .env file) take precedence over the file. A missing
+// file is only an error if the path was given explicitly.
+func applyConfigFile(path string, explicit bool) error {
+	if path == "" {
+		return nil
+	}
+
+	data, err := os.ReadFile(path)
+	if errors.Is(err, os.ErrNotExist) && !explicit {
+		log.Debug().Str("config_file", path).Msg("No config file found")
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("error reading config file: %w", err)
+	}
+
+	var values map[string]interface{}
+	if err := yaml.Unmarshal(data, &values); err != nil {
+		return fmt.Errorf("error parsing config file %s: %w", path, err)
+	}
+
+	known := make(map[string]bool, len(settingsEnvVars))
+	for _, name := range settingsEnvVars {
+		known[name] = true
+	}
+
+	for key, value := range values {
+		name := strings.ToUpper(key)
+		if !known[name] {
+			return fmt.Errorf("unknown setting %q in config file %s", key, path)
+		}
+		if value == nil {
+			continue
+		}
+		if _, ok := os.LookupEnv(name); ok {
+			log.Debug().Str("setting", key).Msg("Setting overridden by environment")
+			continue
+		}
+		os.Setenv(name, fmt.Sprint(value))
+	}
+
+	log.Debug().Str("config_file", path).Int("setting_count", len(values)).Msg("Loaded config file")
+	return nil
+}
+
+// configPrompt is a setting asked for by the config init wizard
+type configPrompt struct {
+	Key     string
+	Prompt  string
+	Default string
+}
+
+// configInitPrompts are the settings asked for by config init, in order
+var configInitPrompts = []configPrompt{
+	{Key: "simplefin_bridge_url", Prompt: "SimpleFin Bridge URL (with access credentials)"},
+	{Key: "openrouter_api_key", Prompt: "OpenRouter API key"},
+	{Key: "openrouter_url", Prompt: "OpenRouter API URL", Default: "https://openrouter.ai/api/v1"},
+	{Key: "openrouter_model", Prompt: "OpenRouter model(s), comma-separated", Default: "deepseek/deepseek-r1:free,deepseek/deepseek-chat-v3.1:free"},
+	{Key: "ntfy_topic", Prompt: "ntfy topic (leave empty to disable ntfy)"},
+	{Key: "mailer_url", Prompt: "SMTP URL (leave empty to disable email)"},
+	{Key: "mailer_from", Prompt: "Email sender address"},
+	{Key: "mailer_to", Prompt: "Email recipient address"},
+	{Key: "billing_cycle_day", Prompt: "Billing cycle start day (1-28)", Default: "15"},
+}
+
+// runConfigWizard asks for each prompt on in and returns the non-empty answers
+func runConfigWizard(in io.Reader, out io.Writer) (map[string]string, error) {
+	reader := bufio.NewReader(in)
+	values := make(map[string]string)
+
+	for _, p := range configInitPrompts {
+		if p.Default != "" {
+			fmt.Fprintf(out, "%s [%s]: ", p.Prompt, p.Default)
+		} else {
+			fmt.Fprintf(out, "%s: ", p.Prompt)
+		}
+
+		line, err := reader.ReadString('\n')
+		if err != nil && !errors.Is(err, io.EOF) {
+			return nil, fmt.Errorf("error reading input: %w", err)
+		}
+
+		answer := strings.TrimSpace(line)
+		if answer == "" {
+			answer = p.Default
+		}
+		if answer != "" {
+			values[p.Key] = answer
+		}
+		if errors.Is(err, io.EOF) {
+			break
+		}
+	}
+
+	return values, nil
+}
+
+// writeConfigFile writes a starter config file with the given values. Every other setting is
+// included as a commented-out line so the file documents what can be configured.
+func writeConfigFile(path string, values map[string]string) error {
+	var b strings.Builder
+	b.WriteString("# Finance Tracker configuration\n")
+	b.WriteString("# Precedence: command-line flags > environment variables (.env) > this file > defaults\n\n")
+
+	keys := make([]string, 0, len(values))
+	for key := range values {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	for _, key := range keys {
+		encoded, err := yaml.Marshal(map[string]string{key: values[key]})
+		if err != nil {
+			return fmt.Errorf("error encoding %s: %w", key, err)
+		}
+		b.Write(encoded)
+	}
+
+	b.WriteString("\n# Other settings\n")
+	for _, name := range settingsEnvVars {
+		key := strings.ToLower(name)
+		if _, ok := values[key]; !ok {
+			fmt.Fprintf(&b, "# %s:\n", key)
+		}
+	}
+
+	if err := os.MkdirAll(filepath.Dir(path), 0o700); err != nil {
+		return fmt.Errorf("error creating config directory: %w", err)
+	}
+
+	// The file holds credentials, so keep it private
+	if err := os.WriteFile(path, []byte(b.String()), 0o600); err != nil {
+		return fmt.Errorf("error writing config file: %w", err)
+	}
+
+	return nil
+}
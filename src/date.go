@@ -3,8 +3,25 @@ package main
 import (
 	"fmt"
 	"time"
+
+	"github.com/rs/zerolog/log"
 )
 
+// periodLocation is the time zone used for period boundaries and for grouping transactions by day,
+// the system time zone unless TIMEZONE is set
+var periodLocation = time.Local
+
+// configureTimezone sets the time zone used for period calculations from an IANA name
+// (e.g. "America/Toronto"), "Local" for the system time zone, or "UTC"
+func configureTimezone(name string) error {
+	location, err := time.LoadLocation(name)
+	if err != nil {
+		return fmt.Errorf("invalid timezone %q: %w", name, err)
+	}
+	periodLocation = location
+	return nil
+}
+
 // calculateDateRange calculates the start and end dates based on the given date range type,
 // optional custom start/end dates, and the billing cycle day.
 func calculateDateRange(
@@ -13,7 +30,7 @@ func calculateDateRange(
 	endDate *time.Time,
 	billingDay int,
 ) (time.Time, time.Time, error) {
-	today := time.Now().UTC()
+	today := time.Now().In(periodLocation)
 	currentYear, currentMonth, _ := today.Date()
 
 	// Adjust billingDay to be within valid range (1-28)
@@ -27,10 +44,10 @@ func calculateDateRange(
 	var currentCycleStart time.Time
 	if today.Day() >= billingDay {
 		// Current cycle started this month
-		currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, time.UTC)
+		currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, periodLocation)
 	} else {
 		// Current cycle started last month
-		currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, time.UTC).AddDate(0, -1, 0)
+		currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, periodLocation).AddDate(0, -1, 0)
 	}
 
 	// If today is within 5 days after the *previous* billing day, switch to last month's cycle
@@ -55,14 +72,14 @@ func calculateDateRange(
 		// End is the day before the current cycle started
 		end := currentCycleStart.Add(-24 * time.Hour)
 		// Start is one month before the end date, at the billing day
-		start := time.Date(end.Year(), end.Month(), billingDay, 0, 0, 0, 0, time.UTC).AddDate(0, -1, 0)
+		start := time.Date(end.Year(), end.Month(), billingDay, 0, 0, 0, 0, periodLocation).AddDate(0, -1, 0)
 		// Handle edge case where end month doesn't have billingDay (e.g. billingDay=31)
 		/*
 			if start.Day() != billingDay {
 				// Go to the first of the *next* month, then subtract one day to get the last day of the correct month
-				start = time.Date(end.Year(), end.Month()+1, 1, 0, 0, 0, 0, time.UTC).AddDate(0, 0, -1)
+				start = time.Date(end.Year(), end.Month()+1, 1, 0, 0, 0, 0, periodLocation).AddDate(0, 0, -1)
 				// Now set the start day to the billing day for the correct month/year
-				start = time.Date(start.Year(), start.Month(), billingDay, 0, 0, 0, 0, time.UTC)
+				start = time.Date(start.Year(), start.Month(), billingDay, 0, 0, 0, 0, periodLocation)
 			}
 		*/
 
@@ -85,17 +102,27 @@ func calculateDateRange(
 
 	case DateRangeTypeCurrentYear:
 		// Start is Jan 1st of the current year
-		start := time.Date(currentYear, time.January, 1, 0, 0, 0, 0, time.UTC)
+		start := time.Date(currentYear, time.January, 1, 0, 0, 0, 0, periodLocation)
 		// End is today
 		return start, today, nil
 
 	case DateRangeTypeLastYear:
 		// Start is Jan 1st of last year
-		start := time.Date(currentYear-1, time.January, 1, 0, 0, 0, 0, time.UTC)
+		start := time.Date(currentYear-1, time.January, 1, 0, 0, 0, 0, periodLocation)
 		// End is Dec 31st of last year
-		end := time.Date(currentYear-1, time.December, 31, 23, 59, 59, 999999999, time.UTC)
+		end := time.Date(currentYear-1, time.December, 31, 23, 59, 59, 999999999, periodLocation)
 		return start, end, nil
 
+	case DateRangeTypeYesterday:
+		// The full previous day
+		end := time.Date(currentYear, currentMonth, today.Day(), 0, 0, 0, 0, periodLocation)
+		return end.AddDate(0, 0, -1), end.Add(-time.Nanosecond), nil
+
+	case DateRangeTypeLast7Days:
+		// The previous 7 days up to now
+		start := time.Date(currentYear, currentMonth, today.Day(), 0, 0, 0, 0, periodLocation).AddDate(0, 0, -7)
+		return start, today, nil
+
 	case DateRangeTypeCustom:
 		if startDate == nil || endDate == nil {
 			return time.Time{}, time.Time{}, fmt.Errorf("custom date range requires both start_date and end_date")
@@ -107,17 +134,93 @@ func calculateDateRange(
 	}
 }
 
-// validateBillingPeriod ensures that the provided billing period is valid:
-// - Start date must be before end date
-// - Billing period can't exceed 90 days (SimpleFIN limit?)
+// validateBillingPeriod ensures that the start date is before the end date. Periods longer than
+// SimpleFin's request limit are fetched in chunks by getTransactionsForPeriod.
 func validateBillingPeriod(start, end time.Time) error {
 	if start.After(end) {
 		return fmt.Errorf("start date cannot be after end date")
 	}
 
-	if end.Sub(start).Hours() > 90*24 {
-		// Note: SimpleFIN seems to have a 90-day limit for fetching transactions
-		return fmt.Errorf("billing period cannot exceed 90 days")
+	return nil
+}
+
+// widenForAccountBillingDays extends the period so it also covers the current period of every
+// account configured with its own billing day
+func widenForAccountBillingDays(dateRangeType DateRangeType, start, end time.Time, filterConfig *FilterConfig) (time.Time, time.Time) {
+	if dateRangeType == DateRangeTypeCustom || filterConfig == nil {
+		return start, end
+	}
+
+	for _, accountSettings := range filterConfig.Accounts {
+		if accountSettings.BillingDay == 0 {
+			continue
+		}
+		accountStart, accountEnd, err := calculateDateRange(dateRangeType, nil, nil, accountSettings.BillingDay)
+		if err != nil {
+			continue
+		}
+		if accountStart.Before(start) {
+			start = accountStart
+		}
+		if accountEnd.After(end) {
+			end = accountEnd
+		}
+	}
+
+	return start, end
+}
+
+// accountPeriod returns the account's statement period when it is configured with its own billing
+// day, and the global period otherwise
+func accountPeriod(account Account, dateRangeType DateRangeType, globalStart, globalEnd time.Time, filterConfig *FilterConfig) (time.Time, time.Time, error) {
+	if dateRangeType == DateRangeTypeCustom || filterConfig == nil {
+		return globalStart, globalEnd, nil
+	}
+	billingDay := lookupAccountSettings(account, filterConfig).BillingDay
+	if billingDay == 0 {
+		return globalStart, globalEnd, nil
+	}
+	start, end, err := calculateDateRange(dateRangeType, nil, nil, billingDay)
+	if err != nil {
+		return time.Time{}, time.Time{}, fmt.Errorf("error calculating billing period for account %s: %w", account.Name, err)
+	}
+	return start, end, nil
+}
+
+// applyAccountBillingCycles trims the transactions of accounts configured with their own billing
+// day to that account's statement period, and all other accounts to the global period
+// (the fetch range may have been widened by widenForAccountBillingDays)
+func applyAccountBillingCycles(accounts []Account, dateRangeType DateRangeType, globalStart, globalEnd time.Time, filterConfig *FilterConfig) error {
+	if dateRangeType == DateRangeTypeCustom || filterConfig == nil || len(filterConfig.Accounts) == 0 {
+		return nil
+	}
+
+	for i := range accounts {
+		billingDay := lookupAccountSettings(accounts[i], filterConfig).BillingDay
+
+		start, end, err := accountPeriod(accounts[i], dateRangeType, globalStart, globalEnd, filterConfig)
+		if err != nil {
+			return err
+		}
+
+		var transactions []Transaction
+		for _, tx := range accounts[i].Transactions {
+			txTime := transactionTime(tx)
+			if !txTime.Before(start) && !txTime.After(end) {
+				transactions = append(transactions, tx)
+			}
+		}
+
+		log.Debug().
+			Str("account_name", accounts[i].Name).
+			Int("billing_day", billingDay).
+			Str("start", start.Format("2006-01-02")).
+			Str("end", end.Format("2006-01-02")).
+			Int("transactions_kept", len(transactions)).
+			Int("transactions_dropped", len(accounts[i].Transactions)-len(transactions)).
+			Msg("Applied account billing cycle")
+
+		accounts[i].Transactions = transactions
 	}
 
 	return nil
@@ -0,0 +1,88 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+)
+
+// DigestEntry is a transaction listed in a new transactions digest
+type DigestEntry struct {
+	Account     Account
+	Transaction Transaction
+}
+
+// digestRetention is how long the IDs of transactions sent in a digest are kept beyond the fetch
+// window, so a transaction the bank posts late with an old date is still recognized as sent
+const digestRetention = 30 * 24 * time.Hour
+
+// digestIDs maps the IDs of transactions already sent in a digest to their posted time
+type digestIDs map[string]int64
+
+// unsentTransactions returns the posted transactions of each account not yet sent in a digest,
+// oldest first. Selecting by ID rather than by date includes transactions that arrive late with a
+// posted date before ones already sent. Accounts without sent IDs are skipped: they are recorded
+// by markDigestSent so the first digest doesn't list the whole fetch window.
+func unsentTransactions(accounts []Account, sent map[string]digestIDs) []DigestEntry {
+	var entries []DigestEntry
+	for _, account := range accounts {
+		ids, ok := sent[account.ID]
+		if !ok {
+			continue
+		}
+		for _, tx := range account.Transactions {
+			if _, done := ids[tx.ID]; !done && getTransactionStatus(tx) == "posted" {
+				entries = append(entries, DigestEntry{Account: account, Transaction: tx})
+			}
+		}
+	}
+	sort.SliceStable(entries, func(i, j int) bool {
+		return entries[i].Transaction.Posted < entries[j].Transaction.Posted
+	})
+	return entries
+}
+
+// markDigestSent records every fetched posted transaction as sent and forgets the IDs posted more
+// than digestRetention before the fetch start, so the sent sets don't grow forever
+func markDigestSent(accounts []Account, sent map[string]digestIDs, fetchStart time.Time) {
+	for _, account := range accounts {
+		ids, ok := sent[account.ID]
+		if !ok {
+			ids = make(digestIDs)
+			sent[account.ID] = ids
+		}
+		for _, tx := range account.Transactions {
+			if getTransactionStatus(tx) == "posted" {
+				ids[tx.ID] = tx.Posted
+			}
+		}
+	}
+
+	cutoff := fetchStart.Add(-digestRetention).Unix()
+	for _, ids := range sent {
+		for id, posted := range ids {
+			if posted < cutoff {
+				delete(ids, id)
+			}
+		}
+	}
+}
+
+// formatTransactionDigest formats the new transactions as a short message listing each one
+func formatTransactionDigest(entries []DigestEntry) string {
+	var b strings.Builder
+	if len(entries) == 1 {
+		b.WriteString("🧾 **1 new transaction**\n\n")
+	} else {
+		fmt.Fprintf(&b, "🧾 **%d new transactions**\n\n", len(entries))
+	}
+	for _, entry := range entries {
+		fmt.Fprintf(&b, "- %s · %s: %s (%s)\n",
+			currentLocale.formatDate(transactionTime(entry.Transaction)),
+			accountDisplayName(entry.Account),
+			merchantName(entry.Transaction),
+			currentLocale.formatAmount(toMoney(entry.Transaction.Amount)))
+	}
+	return b.String()
+}
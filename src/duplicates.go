@@ -0,0 +1,139 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// duplicateChargeWindow is how close together two identical charges must be to be flagged
+const duplicateChargeWindow = 3 * 24 * time.Hour
+
+// duplicateAlertRetention is how long a reported duplicate is remembered so it isn't reported again
+const duplicateAlertRetention = 90 * 24 * time.Hour
+
+// DuplicateCharge is a pair of charges on the same account from the same merchant with the same
+// amount, close together: a double swipe or a subscription billed twice
+type DuplicateCharge struct {
+	Account Account
+	First   Transaction
+	Second  Transaction
+}
+
+// Key identifies the pair of transactions
+func (d DuplicateCharge) Key() string {
+	return d.First.ID + "|" + d.Second.ID
+}
+
+// findDuplicateCharges returns pairs of posted charges on the same account from the same merchant
+// with identical amounts within duplicateChargeWindow. Each charge is paired at most once, and
+// transfers and fully refunded charges are ignored.
+func findDuplicateCharges(accounts []Account) []DuplicateCharge {
+	var duplicates []DuplicateCharge
+	for _, account := range accounts {
+		var charges []Transaction
+		for _, tx := range account.Transactions {
+			if tx.Amount >= 0 || tx.IsTransfer || (tx.Pending != nil && *tx.Pending) {
+				continue
+			}
+			if _, ok := netExpense(tx); !ok {
+				continue
+			}
+			charges = append(charges, tx)
+		}
+		sort.SliceStable(charges, func(i, j int) bool {
+			return transactionTime(charges[i]).Before(transactionTime(charges[j]))
+		})
+
+		paired := make(map[string]bool)
+		for i, first := range charges {
+			if paired[first.ID] {
+				continue
+			}
+			for _, second := range charges[i+1:] {
+				if transactionTime(second).Sub(transactionTime(first)) > duplicateChargeWindow {
+					break
+				}
+				if paired[second.ID] || toMoney(second.Amount) != toMoney(first.Amount) ||
+					!strings.EqualFold(merchantName(second), merchantName(first)) {
+					continue
+				}
+				paired[first.ID] = true
+				paired[second.ID] = true
+				duplicates = append(duplicates, DuplicateCharge{Account: account, First: first, Second: second})
+				break
+			}
+		}
+	}
+	return duplicates
+}
+
+// formatDuplicateCharge describes a duplicate charge on one line
+func formatDuplicateCharge(d DuplicateCharge) string {
+	return fmt.Sprintf("%s: %s %s on %s and %s",
+		accountDisplayName(d.Account),
+		merchantName(d.First),
+		-toMoney(d.First.Amount),
+		transactionTime(d.First).Format("2006-01-02"),
+		transactionTime(d.Second).Format("2006-01-02"))
+}
+
+// checkDuplicateCharges warns about possible duplicate charges through the warning channel. Each
+// pair is only reported once.
+func checkDuplicateCharges(settings *Settings, config RunConfig, accounts []Account, filterConfig *FilterConfig) error {
+	duplicates := findDuplicateCharges(excludeAccounts(accounts, filterConfig, AccountScopeNotifications))
+	state := loadAccountState(settings.CacheDir)
+
+	var lines []string
+	var alerted []DuplicateCharge
+	for _, d := range duplicates {
+		log.Warn().
+			Str("account_name", d.Account.Name).
+			Str("merchant", merchantName(d.First)).
+			Str("amount", (-toMoney(d.First.Amount)).String()).
+			Str("first_id", d.First.ID).
+			Str("second_id", d.Second.ID).
+			Msg("👯 Possible duplicate charge")
+
+		if state.AlreadyAlertedDuplicate(d) {
+			log.Debug().Str("key", d.Key()).Msg("Duplicate charge already reported, skipping alert")
+			continue
+		}
+		lines = append(lines, "- "+formatDuplicateCharge(d))
+		alerted = append(alerted, d)
+	}
+
+	if len(lines) > 0 && !config.DisableNotifications {
+		message := fmt.Sprintf("👯 **Possible duplicate charges**\n\nThe same amount was charged twice by the same merchant. Check whether one should be disputed:\n%s", strings.Join(lines, "\n"))
+		if config.DryRun {
+			return renderNotification(settings, message, nil, nil, "warning", config.Notifications)
+		}
+		_, err := sendNotification(settings, message, nil, nil, "warning", config.Notifications)
+		if errors.Is(err, errNotificationDeferred) {
+			// Not marked as reported, so it is sent again by a later run
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("error sending duplicate charge warning: %w", err)
+		}
+		now := time.Now()
+		for _, d := range alerted {
+			state.MarkDuplicateAlerted(d, now)
+		}
+	}
+
+	if config.DryRun || state == nil {
+		return nil
+	}
+
+	state.PruneDuplicateAlerts(time.Now().Add(-duplicateAlertRetention))
+	if err := state.Save(); err != nil {
+		log.Warn().Err(err).Msg("Failed to save duplicate charge alert state")
+	}
+
+	return nil
+}
@@ -0,0 +1,283 @@
+package main
+
+import (
+	"bufio"
+	"crypto/tls"
+	"encoding/json"
+	"fmt"
+	"net"
+	"net/url"
+	"strings"
+	"time"
+)
+
+// defaultEventBusPrefix is the channel (Redis) or subject (NATS) prefix when EVENT_BUS_URL has none
+const defaultEventBusPrefix = "finance_tracker"
+
+// eventBusTimeout bounds connecting to the event bus and publishing one event
+const eventBusTimeout = 5 * time.Second
+
+// EventBus publishes events to the EVENT_BUS_URL broker over a single connection, opened on the
+// first event and reused for the rest of the run. Redis (redis://, rediss://) uses PUBLISH and
+// NATS (nats://, or tls:// for TLS) uses PUB; events go to "<prefix>.<event>", where the prefix
+// is the channel or subject query parameter.
+type EventBus struct {
+	url    *url.URL
+	nats   bool
+	prefix string
+	conn   net.Conn
+	reader *bufio.Reader
+}
+
+// publishEvent publishes the event body on the run's event bus, opening it on the first event
+func publishEvent(settings *Settings, event string, body []byte) error {
+	if settings.EventBus == nil {
+		bus, err := newEventBus(settings.EventBusURL)
+		if err != nil {
+			return err
+		}
+		settings.EventBus = bus
+	}
+	return settings.EventBus.Publish(event, body)
+}
+
+// newEventBus parses EVENT_BUS_URL without connecting
+func newEventBus(busURL SecretURL) (*EventBus, error) {
+	u, err := busURL.Parse()
+	if err != nil {
+		return nil, fmt.Errorf("error parsing EVENT_BUS_URL: %w", err)
+	}
+
+	bus := &EventBus{url: u}
+	switch u.Scheme {
+	case "redis", "rediss":
+		bus.prefix = u.Query().Get("channel")
+	case "nats", "tls":
+		bus.nats = true
+		bus.prefix = u.Query().Get("subject")
+	default:
+		return nil, fmt.Errorf("unsupported EVENT_BUS_URL scheme %q (supported: redis, rediss, nats, tls)", u.Scheme)
+	}
+	if bus.prefix == "" {
+		bus.prefix = defaultEventBusPrefix
+	}
+	return bus, nil
+}
+
+// Publish publishes the event body, connecting first if needed. A reused connection the broker
+// has since closed is replaced once before giving up.
+func (b *EventBus) Publish(event string, body []byte) error {
+	reused := b.conn != nil
+	err := b.publish(b.prefix+"."+event, body)
+	if err != nil && reused {
+		b.Close()
+		err = b.publish(b.prefix+"."+event, body)
+	}
+	if err != nil {
+		b.Close()
+	}
+	return err
+}
+
+// publish sends one event on the current connection, opening it first if needed
+func (b *EventBus) publish(subject string, body []byte) error {
+	if b.conn == nil {
+		if err := b.connect(); err != nil {
+			return err
+		}
+	}
+	b.conn.SetDeadline(time.Now().Add(eventBusTimeout))
+	if b.nats {
+		return b.publishNATS(subject, body)
+	}
+	return b.publishRedis(subject, body)
+}
+
+// Close closes the connection, if open. It is safe on a nil bus.
+func (b *EventBus) Close() {
+	if b == nil || b.conn == nil {
+		return
+	}
+	b.conn.Close()
+	b.conn, b.reader = nil, nil
+}
+
+// connect opens the connection and authenticates with the URL's credentials
+func (b *EventBus) connect() error {
+	if b.nats {
+		return b.connectNATS()
+	}
+	return b.connectRedis()
+}
+
+// dialEventBus connects to the broker, with TLS when tlsEnabled
+func dialEventBus(u *url.URL, defaultPort string, tlsEnabled bool) (net.Conn, error) {
+	port := u.Port()
+	if port == "" {
+		port = defaultPort
+	}
+	addr := net.JoinHostPort(u.Hostname(), port)
+
+	dialer := &net.Dialer{Timeout: eventBusTimeout}
+	var conn net.Conn
+	var err error
+	if tlsEnabled {
+		conn, err = tls.DialWithDialer(dialer, "tcp", addr, &tls.Config{ServerName: u.Hostname()})
+	} else {
+		conn, err = dialer.Dial("tcp", addr)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("error connecting to %s: %w", addr, err)
+	}
+	conn.SetDeadline(time.Now().Add(eventBusTimeout))
+	return conn, nil
+}
+
+// redisCommand encodes a command as a RESP array of bulk strings
+func redisCommand(args ...string) []byte {
+	var b strings.Builder
+	fmt.Fprintf(&b, "*%d\r\n", len(args))
+	for _, arg := range args {
+		fmt.Fprintf(&b, "$%d\r\n%s\r\n", len(arg), arg)
+	}
+	return []byte(b.String())
+}
+
+// readRedisReply reads a simple reply line, failing on Redis error replies
+func readRedisReply(reader *bufio.Reader) (string, error) {
+	line, err := reader.ReadString('\n')
+	if err != nil {
+		return "", fmt.Errorf("error reading reply: %w", err)
+	}
+	line = strings.TrimRight(line, "\r\n")
+	if strings.HasPrefix(line, "-") {
+		return "", fmt.Errorf("redis error: %s", strings.TrimPrefix(line, "-"))
+	}
+	return line, nil
+}
+
+// connectRedis opens the connection and sends AUTH with the URL's credentials, if any
+func (b *EventBus) connectRedis() error {
+	conn, err := dialEventBus(b.url, "6379", b.url.Scheme == "rediss")
+	if err != nil {
+		return err
+	}
+	reader := bufio.NewReader(conn)
+
+	if u := b.url; u.User != nil {
+		password, hasPassword := u.User.Password()
+		args := []string{"AUTH", u.User.Username()}
+		if hasPassword {
+			if u.User.Username() == "" {
+				args = []string{"AUTH", password}
+			} else {
+				args = append(args, password)
+			}
+		}
+		if _, err := conn.Write(redisCommand(args...)); err != nil {
+			conn.Close()
+			return fmt.Errorf("error sending AUTH: %w", err)
+		}
+		if _, err := readRedisReply(reader); err != nil {
+			conn.Close()
+			return fmt.Errorf("authentication failed: %w", err)
+		}
+	}
+
+	b.conn, b.reader = conn, reader
+	return nil
+}
+
+// publishRedis sends PUBLISH and reads the number of subscribers it reached
+func (b *EventBus) publishRedis(channel string, body []byte) error {
+	if _, err := b.conn.Write(redisCommand("PUBLISH", channel, string(body))); err != nil {
+		return fmt.Errorf("error sending PUBLISH: %w", err)
+	}
+	_, err := readRedisReply(b.reader)
+	return err
+}
+
+// natsInfo is the part of the NATS server's INFO greeting used when connecting
+type natsInfo struct {
+	TLSRequired bool `json:"tls_required"`
+}
+
+// connectNATS opens the connection and sends CONNECT with the URL's credentials. The server
+// greets in plain text; the connection is then upgraded to TLS for tls:// URLs or when the
+// server requires it.
+func (b *EventBus) connectNATS() error {
+	conn, err := dialEventBus(b.url, "4222", false)
+	if err != nil {
+		return err
+	}
+	reader := bufio.NewReader(conn)
+
+	line, err := reader.ReadString('\n')
+	if err != nil {
+		conn.Close()
+		return fmt.Errorf("error reading server info: %w", err)
+	}
+	var info natsInfo
+	if err := json.Unmarshal([]byte(strings.TrimPrefix(strings.TrimSpace(line), "INFO ")), &info); err != nil {
+		conn.Close()
+		return fmt.Errorf("error parsing server info: %w", err)
+	}
+
+	useTLS := b.url.Scheme == "tls" || info.TLSRequired
+	if useTLS {
+		tlsConn := tls.Client(conn, &tls.Config{ServerName: b.url.Hostname()})
+		if err := tlsConn.Handshake(); err != nil {
+			conn.Close()
+			return fmt.Errorf("error negotiating TLS: %w", err)
+		}
+		conn, reader = tlsConn, bufio.NewReader(tlsConn)
+	}
+
+	options := map[string]interface{}{"verbose": false, "pedantic": false, "tls_required": useTLS, "name": "finance_tracker"}
+	if u := b.url; u.User != nil {
+		if password, ok := u.User.Password(); ok {
+			options["user"], options["pass"] = u.User.Username(), password
+		} else {
+			options["auth_token"] = u.User.Username()
+		}
+	}
+	connect, err := json.Marshal(options)
+	if err != nil {
+		conn.Close()
+		return fmt.Errorf("error encoding CONNECT: %w", err)
+	}
+	if _, err := conn.Write([]byte(fmt.Sprintf("CONNECT %s\r\n", connect))); err != nil {
+		conn.Close()
+		return fmt.Errorf("error sending CONNECT: %w", err)
+	}
+
+	b.conn, b.reader = conn, reader
+	return nil
+}
+
+// publishNATS sends PUB and waits for the PONG confirming it was processed, answering the
+// server's keepalive PINGs meanwhile
+func (b *EventBus) publishNATS(subject string, body []byte) error {
+	message := fmt.Sprintf("PUB %s %d\r\n%s\r\nPING\r\n", subject, len(body), body)
+	if _, err := b.conn.Write([]byte(message)); err != nil {
+		return fmt.Errorf("error sending PUB: %w", err)
+	}
+
+	for {
+		line, err := b.reader.ReadString('\n')
+		if err != nil {
+			return fmt.Errorf("error reading reply: %w", err)
+		}
+		line = strings.TrimRight(line, "\r\n")
+		switch {
+		case line == "PONG":
+			return nil
+		case line == "PING":
+			if _, err := b.conn.Write([]byte("PONG\r\n")); err != nil {
+				return fmt.Errorf("error sending PONG: %w", err)
+			}
+		case strings.HasPrefix(line, "-ERR"):
+			return fmt.Errorf("nats error: %s", strings.TrimSpace(strings.TrimPrefix(line, "-ERR")))
+		}
+	}
+}
@@ -0,0 +1,122 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"hash/fnv"
+	"io"
+)
+
+// anonymizer re-keys identifiers and perturbs merchants and amounts while preserving the
+// structure of the data (recurring merchants stay recurring, equal amounts stay equal, signs
+// and dates are unchanged)
+type anonymizer struct {
+	seed      int64
+	merchants map[string]string
+	orgs      map[string]string
+	txnCount  int
+}
+
+// newAnonymizer creates an anonymizer with the given random seed
+func newAnonymizer(seed int64) *anonymizer {
+	return &anonymizer{
+		seed:      seed,
+		merchants: make(map[string]string),
+		orgs:      make(map[string]string),
+	}
+}
+
+// perturb scales an amount by a factor between 0.8 and 1.2, keeping its sign. The factor is
+// derived from the seed and the amount's magnitude, so identical amounts (recurring charges,
+// duplicates, both sides of a transfer) stay identical after perturbing.
+func (a *anonymizer) perturb(amount Balance) Balance {
+	magnitude := toMoney(amount)
+	if magnitude < 0 {
+		magnitude = -magnitude
+	}
+	h := fnv.New64a()
+	fmt.Fprintf(h, "%d:%d", a.seed, magnitude)
+	factor := 0.8 + float64(h.Sum64()%1_000_000)/1_000_000*0.4
+	return Balance(toMoney(amount*Balance(factor)).Float64())
+}
+
+// merchant returns a stable pseudonym for a transaction description
+func (a *anonymizer) merchant(description string) string {
+	if pseudonym, ok := a.merchants[description]; ok {
+		return pseudonym
+	}
+	pseudonym := fmt.Sprintf("Merchant %d", len(a.merchants)+1)
+	a.merchants[description] = pseudonym
+	return pseudonym
+}
+
+// organization returns an anonymized copy of an organization, stable across accounts
+func (a *anonymizer) organization(org Organization) Organization {
+	key := org.SfinURL + getStringValue(org.Name)
+	name, ok := a.orgs[key]
+	if !ok {
+		name = fmt.Sprintf("Bank %d", len(a.orgs)+1)
+		a.orgs[key] = name
+	}
+	return Organization{
+		SfinURL: "https://bridge.example.com/simplefin",
+		Name:    &name,
+	}
+}
+
+// account returns an anonymized copy of an account and its transactions
+func (a *anonymizer) account(account Account, index int) Account {
+	// Keep credit card indicators in the name so account type filtering behaves the same
+	name := fmt.Sprintf("Account %d", index+1)
+	if isCreditCard(account) {
+		name = fmt.Sprintf("Credit Card %d", index+1)
+	}
+
+	anonymized := Account{
+		ID:          fmt.Sprintf("ACT-%04d", index+1),
+		Name:        name,
+		Balance:     a.perturb(account.Balance),
+		BalanceDate: account.BalanceDate,
+		Org:         a.organization(account.Org),
+		Currency:    account.Currency,
+	}
+	if account.AvailableBalance != nil {
+		available := a.perturb(*account.AvailableBalance)
+		anonymized.AvailableBalance = &available
+	}
+
+	for _, tx := range account.Transactions {
+		a.txnCount++
+		anonymized.Transactions = append(anonymized.Transactions, Transaction{
+			ID:           fmt.Sprintf("TRN-%05d", a.txnCount),
+			Description:  a.merchant(tx.Description),
+			Amount:       a.perturb(tx.Amount),
+			Posted:       tx.Posted,
+			TransactedAt: tx.TransactedAt,
+			Pending:      tx.Pending,
+		})
+	}
+
+	return anonymized
+}
+
+// exportAccounts writes accounts as a SimpleFin-style JSON document, optionally anonymized.
+// Extra provider data is dropped when anonymizing since it may contain identifying details.
+func exportAccounts(w io.Writer, accounts []Account, anonymize bool, seed int64) error {
+	response := AccountsResponse{Accounts: accounts}
+
+	if anonymize {
+		a := newAnonymizer(seed)
+		response.Accounts = make([]Account, 0, len(accounts))
+		for i, account := range accounts {
+			response.Accounts = append(response.Accounts, a.account(account, i))
+		}
+	}
+
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	if err := encoder.Encode(response); err != nil {
+		return fmt.Errorf("error encoding export: %w", err)
+	}
+	return nil
+}
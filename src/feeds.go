@@ -0,0 +1,121 @@
+package main
+
+import (
+	"bytes"
+	"encoding/xml"
+	"fmt"
+	"html"
+	"io"
+	"math"
+	"os"
+	"sort"
+	"time"
+)
+
+// atomFeed is an Atom 1.0 feed document
+type atomFeed struct {
+	XMLName xml.Name    `xml:"http://www.w3.org/2005/Atom feed"`
+	ID      string      `xml:"id"`
+	Title   string      `xml:"title"`
+	Updated string      `xml:"updated"`
+	Entries []atomEntry `xml:"entry"`
+}
+
+// atomEntry is a single entry of an Atom feed
+type atomEntry struct {
+	ID      string      `xml:"id"`
+	Title   string      `xml:"title"`
+	Updated string      `xml:"updated"`
+	Content atomContent `xml:"content"`
+}
+
+// atomContent is the body of an Atom entry
+type atomContent struct {
+	Type string `xml:"type,attr"`
+	Body string `xml:",chardata"`
+}
+
+// writeAtomFeed writes the feed with entries sorted newest first
+func writeAtomFeed(w io.Writer, feed atomFeed) error {
+	sort.SliceStable(feed.Entries, func(i, j int) bool {
+		return feed.Entries[i].Updated > feed.Entries[j].Updated
+	})
+
+	feed.Updated = time.Now().UTC().Format(time.RFC3339)
+	if len(feed.Entries) > 0 {
+		feed.Updated = feed.Entries[0].Updated
+	}
+
+	if _, err := io.WriteString(w, xml.Header); err != nil {
+		return err
+	}
+	encoder := xml.NewEncoder(w)
+	encoder.Indent("", "  ")
+	if err := encoder.Encode(feed); err != nil {
+		return fmt.Errorf("error encoding feed: %w", err)
+	}
+	_, err := io.WriteString(w, "\n")
+	return err
+}
+
+// saveAtomFeed writes the feed to stdout when output is empty or "-", otherwise replaces the
+// output file atomically so a web server never serves a partial feed
+func saveAtomFeed(output string, feed atomFeed) error {
+	if output == "" || output == "-" {
+		return writeAtomFeed(os.Stdout, feed)
+	}
+
+	var buf bytes.Buffer
+	if err := writeAtomFeed(&buf, feed); err != nil {
+		return err
+	}
+	if err := writeFileAtomic(output, buf.Bytes()); err != nil {
+		return fmt.Errorf("error writing feed: %w", err)
+	}
+	return nil
+}
+
+// buildSummariesFeed builds a feed with one entry per cached summary
+func buildSummariesFeed(summaries map[string]CachedSummary) atomFeed {
+	feed := atomFeed{
+		ID:    "urn:finance-tracker:summaries",
+		Title: "Finance Tracker - Summaries",
+	}
+	for period, summary := range summaries {
+		feed.Entries = append(feed.Entries, atomEntry{
+			ID:      "urn:finance-tracker:summary:" + period,
+			Title:   "Summary for " + period,
+			Updated: summary.CreatedAt.UTC().Format(time.RFC3339),
+			Content: atomContent{Type: "html", Body: convertMarkdownToHTML(summary.Summary)},
+		})
+	}
+	return feed
+}
+
+// buildTransactionsFeed builds a feed with one entry per transaction whose absolute amount is at
+// least minAmount
+func buildTransactionsFeed(accounts []Account, minAmount float64) atomFeed {
+	feed := atomFeed{
+		ID:    "urn:finance-tracker:transactions",
+		Title: "Finance Tracker - Transactions",
+	}
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if math.Abs(float64(tx.Amount)) < minAmount {
+				continue
+			}
+			body := fmt.Sprintf("<p>%s: %s on %s (%s)</p>",
+				html.EscapeString(accountDisplayName(account)), html.EscapeString(toMoney(tx.Amount).Format()), transactionTime(tx).Format("2006-01-02"), getTransactionStatus(tx))
+			if tx.Note != "" {
+				body += fmt.Sprintf("<p>Note: %s</p>", html.EscapeString(tx.Note))
+			}
+			feed.Entries = append(feed.Entries, atomEntry{
+				ID:      "urn:finance-tracker:transaction:" + stableTransactionID(account.ID, tx.ID),
+				Title:   fmt.Sprintf("%s %s", merchantName(tx), toMoney(tx.Amount).Format()),
+				Updated: transactionTime(tx).UTC().Format(time.RFC3339),
+				Content: atomContent{Type: "html", Body: body},
+			})
+		}
+	}
+	return feed
+}
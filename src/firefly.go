@@ -0,0 +1,210 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// FireflyClient talks to a Firefly III instance's REST API
+type FireflyClient struct {
+	baseURL string
+	token   Secret
+	client  *http.Client
+}
+
+// FireflySyncResult summarizes a sync into Firefly III
+type FireflySyncResult struct {
+	AccountsCreated     int
+	TransactionsCreated int
+	Duplicates          int
+}
+
+// newFireflyClient creates a client for the Firefly III instance configured in the settings
+func newFireflyClient(settings *Settings) (*FireflyClient, error) {
+	if settings.FireflyURL == "" || settings.FireflyToken == "" {
+		return nil, fmt.Errorf("FIREFLY_URL and FIREFLY_TOKEN must be set")
+	}
+
+	return &FireflyClient{
+		baseURL: strings.TrimSuffix(settings.FireflyURL, "/") + "/api/v1",
+		token:   settings.FireflyToken,
+		client:  newHTTPClient(60 * time.Second),
+	}, nil
+}
+
+// do sends a JSON request and decodes the JSON response into out (if non-nil). The response
+// status code is returned along with the raw body for non-2xx responses.
+func (f *FireflyClient) do(method string, path string, body interface{}, out interface{}) (int, error) {
+	var reqBody io.Reader
+	if body != nil {
+		data, err := json.Marshal(body)
+		if err != nil {
+			return 0, fmt.Errorf("error marshaling request: %w", err)
+		}
+		reqBody = bytes.NewBuffer(data)
+	}
+
+	req, err := http.NewRequest(method, f.baseURL+path, reqBody)
+	if err != nil {
+		return 0, fmt.Errorf("error creating request: %w", err)
+	}
+	req.Header.Set("Authorization", fmt.Sprintf("Bearer %s", f.token.Reveal()))
+	req.Header.Set("Accept", "application/vnd.api+json")
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := f.client.Do(req)
+	if err != nil {
+		return 0, fmt.Errorf("error sending request to Firefly III: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		respBody, _ := io.ReadAll(resp.Body)
+		return resp.StatusCode, fmt.Errorf("unexpected status %d from Firefly III: %s", resp.StatusCode, string(respBody))
+	}
+
+	if out != nil {
+		if err := json.NewDecoder(resp.Body).Decode(out); err != nil {
+			return resp.StatusCode, fmt.Errorf("error decoding Firefly III response: %w", err)
+		}
+	}
+	return resp.StatusCode, nil
+}
+
+// fireflyAccountList is the response of the account search endpoint
+type fireflyAccountList struct {
+	Data []struct {
+		ID         string `json:"id"`
+		Attributes struct {
+			Name string `json:"name"`
+		} `json:"attributes"`
+	} `json:"data"`
+}
+
+// resolveAccount returns the Firefly asset account ID for a SimpleFin account. It uses the
+// configured firefly_account_id, then an asset account with the same name, and otherwise
+// creates one. The boolean reports whether an account was created.
+func (f *FireflyClient) resolveAccount(account Account, filterConfig *FilterConfig) (string, bool, error) {
+	if id := lookupAccountSettings(account, filterConfig).FireflyAccountID; id != "" {
+		return id, false, nil
+	}
+
+	var found fireflyAccountList
+	query := url.Values{"query": {account.Name}, "type": {"asset"}, "field": {"name"}}
+	if _, err := f.do(http.MethodGet, "/search/accounts?"+query.Encode(), nil, &found); err != nil {
+		return "", false, fmt.Errorf("error searching Firefly III accounts: %w", err)
+	}
+	for _, candidate := range found.Data {
+		if strings.EqualFold(candidate.Attributes.Name, account.Name) {
+			return candidate.ID, false, nil
+		}
+	}
+
+	notes := "Mirrored from SimpleFin"
+	if account.Org.Name != nil {
+		notes = fmt.Sprintf("Mirrored from SimpleFin (%s)", *account.Org.Name)
+	}
+	request := map[string]interface{}{
+		"name":         account.Name,
+		"type":         "asset",
+		"account_role": "defaultAsset",
+		"notes":        notes,
+	}
+	if account.Currency != nil {
+		request["currency_code"] = *account.Currency
+	}
+
+	var created struct {
+		Data struct {
+			ID string `json:"id"`
+		} `json:"data"`
+	}
+	if _, err := f.do(http.MethodPost, "/accounts", request, &created); err != nil {
+		return "", false, fmt.Errorf("error creating Firefly III account: %w", err)
+	}
+	return created.Data.ID, true, nil
+}
+
+// createTransaction stores a transaction against the Firefly asset account. Firefly rejects
+// transactions whose content hash already exists, which is reported as a duplicate.
+func (f *FireflyClient) createTransaction(fireflyAccountID string, account Account, tx Transaction) (bool, error) {
+	amount := toMoney(tx.Amount)
+	split := map[string]interface{}{
+		"date":        transactionTime(tx).Format(time.RFC3339),
+		"description": tx.Description,
+		"external_id": stableTransactionID(account.ID, tx.ID),
+		"notes":       fmt.Sprintf("SimpleFin transaction %s", tx.ID),
+	}
+	if amount < 0 {
+		split["type"] = "withdrawal"
+		split["amount"] = (-amount).String()
+		split["source_id"] = fireflyAccountID
+		split["destination_name"] = tx.Description
+	} else {
+		split["type"] = "deposit"
+		split["amount"] = amount.String()
+		split["source_name"] = tx.Description
+		split["destination_id"] = fireflyAccountID
+	}
+	if tx.Category != "" {
+		split["category_name"] = tx.Category
+	}
+
+	request := map[string]interface{}{
+		"error_if_duplicate_hash": true,
+		"apply_rules":             true,
+		"transactions":            []interface{}{split},
+	}
+
+	status, err := f.do(http.MethodPost, "/transactions", request, nil)
+	if status == http.StatusUnprocessableEntity && err != nil && strings.Contains(strings.ToLower(err.Error()), "duplicate") {
+		return false, nil
+	}
+	if err != nil {
+		return false, err
+	}
+	return true, nil
+}
+
+// syncToFirefly mirrors accounts and their transactions into Firefly III
+func syncToFirefly(settings *Settings, accounts []Account, filterConfig *FilterConfig) (FireflySyncResult, error) {
+	var result FireflySyncResult
+
+	client, err := newFireflyClient(settings)
+	if err != nil {
+		return result, err
+	}
+
+	for _, account := range accounts {
+		fireflyAccountID, created, err := client.resolveAccount(account, filterConfig)
+		if err != nil {
+			return result, fmt.Errorf("error resolving account %s: %w", account.Name, err)
+		}
+		if created {
+			result.AccountsCreated++
+			log.Info().Str("account_name", account.Name).Str("firefly_id", fireflyAccountID).Msg("🏦 Created Firefly III account")
+		}
+
+		for _, tx := range account.Transactions {
+			stored, err := client.createTransaction(fireflyAccountID, account, tx)
+			if err != nil {
+				return result, fmt.Errorf("error creating transaction %s: %w", tx.ID, err)
+			}
+			if stored {
+				result.TransactionsCreated++
+			} else {
+				result.Duplicates++
+			}
+		}
+	}
+
+	return result, nil
+}
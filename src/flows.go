@@ -0,0 +1,143 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"text/tabwriter"
+)
+
+// flowIncomeNode, flowSavingsNode and flowOverspentNode name the fixed nodes of the flow report
+const (
+	flowIncomeNode    = "Income"
+	flowSavingsNode   = "Savings"
+	flowOverspentNode = "From balances"
+)
+
+// FlowLink is an amount flowing from one node to another
+type FlowLink struct {
+	Source string `json:"source"`
+	Target string `json:"target"`
+	Value  Money  `json:"value"`
+}
+
+// FlowReport holds income → category → merchant aggregates in the node/link shape used by Sankey
+// diagram libraries
+type FlowReport struct {
+	Nodes []string   `json:"nodes"`
+	Links []FlowLink `json:"links"`
+}
+
+// buildFlowReport aggregates the period's money flow: income flows into each spending category
+// (uncategorized spending into "Uncategorized") and each category into its merchants. Income left
+// over flows into "Savings"; spending beyond income is drawn from "From balances". Transfers
+// between accounts and linked refunds are left out, and refunds are netted from their purchases.
+func buildFlowReport(accounts []Account) FlowReport {
+	var income Money
+	categories := make(map[string]Money)
+	merchants := make(map[[2]string]Money)
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if tx.IsTransfer || tx.RefundOf != "" || tx.Amount == 0 {
+				continue
+			}
+			if tx.Amount > 0 {
+				income += toMoney(tx.Amount)
+				continue
+			}
+			net, ok := netExpense(tx)
+			if !ok {
+				continue
+			}
+			category, _ := splitCategory(tx.Category)
+			if category == "" {
+				category = "Uncategorized"
+			}
+			categories[category] -= toMoney(net.Amount)
+			merchants[[2]string{category, merchantName(tx)}] -= toMoney(net.Amount)
+		}
+	}
+
+	var report FlowReport
+	seen := make(map[string]bool)
+	addNode := func(name string) {
+		if !seen[name] {
+			seen[name] = true
+			report.Nodes = append(report.Nodes, name)
+		}
+	}
+
+	var spent Money
+	categoryNames := make([]string, 0, len(categories))
+	for category, total := range categories {
+		categoryNames = append(categoryNames, category)
+		spent += total
+	}
+	sort.Slice(categoryNames, func(i, j int) bool {
+		return categories[categoryNames[i]] > categories[categoryNames[j]]
+	})
+
+	addNode(flowIncomeNode)
+	if spent > income {
+		addNode(flowOverspentNode)
+	}
+	for _, category := range categoryNames {
+		addNode(category)
+	}
+
+	// Split each category between income and balances in proportion to the overspend
+	for _, category := range categoryNames {
+		total := categories[category]
+		fromIncome := total
+		if spent > income {
+			fromIncome = Money(int64(total) * int64(income) / int64(spent))
+			report.Links = append(report.Links, FlowLink{Source: flowOverspentNode, Target: category, Value: total - fromIncome})
+		}
+		if fromIncome > 0 {
+			report.Links = append(report.Links, FlowLink{Source: flowIncomeNode, Target: category, Value: fromIncome})
+		}
+	}
+	if income > spent {
+		addNode(flowSavingsNode)
+		report.Links = append(report.Links, FlowLink{Source: flowIncomeNode, Target: flowSavingsNode, Value: income - spent})
+	}
+
+	var merchantLinks []FlowLink
+	for key, total := range merchants {
+		merchantLinks = append(merchantLinks, FlowLink{Source: key[0], Target: key[1], Value: total})
+	}
+	sort.Slice(merchantLinks, func(i, j int) bool {
+		if merchantLinks[i].Source != merchantLinks[j].Source {
+			return categories[merchantLinks[i].Source] > categories[merchantLinks[j].Source]
+		}
+		return merchantLinks[i].Value > merchantLinks[j].Value
+	})
+	for _, link := range merchantLinks {
+		// Node names must be unique, so merchants named like a category get a suffix
+		if _, ok := categories[link.Target]; ok || link.Target == flowIncomeNode || link.Target == flowSavingsNode || link.Target == flowOverspentNode {
+			link.Target += " (merchant)"
+		}
+		addNode(link.Target)
+		report.Links = append(report.Links, link)
+	}
+
+	return report
+}
+
+// writeFlowReportJSON writes the flow report as indented JSON
+func writeFlowReportJSON(w io.Writer, report FlowReport) error {
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(report)
+}
+
+// writeFlowReportText writes the flow report's links as a table
+func writeFlowReportText(w io.Writer, report FlowReport) error {
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(tw, "FROM\tTO\tAMOUNT")
+	for _, link := range report.Links {
+		fmt.Fprintf(tw, "%s\t%s\t%s\n", link.Source, link.Target, link.Value)
+	}
+	return tw.Flush()
+}
@@ -0,0 +1,165 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"math"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+)
+
+// RecurringTransaction is a charge or deposit that repeats at a regular interval
+type RecurringTransaction struct {
+	Merchant string
+	Amount   Money // Average amount per occurrence
+	Interval int   // Median days between occurrences
+	Last     time.Time
+}
+
+// AccountForecast is the projected end-of-month balance for an account
+type AccountForecast struct {
+	Account   Account
+	Balance   Money
+	Recurring Money // Upcoming recurring transactions until the end of the month
+	Other     Money // Projected non-recurring activity from the historical daily average
+	Projected Money
+	Negative  bool // Projected balance is below zero (never set for credit cards)
+}
+
+// detectRecurring finds transactions with the same merchant and sign that repeat every 6 to 35
+// days with amounts within 25% of their average
+func detectRecurring(transactions []Transaction) []RecurringTransaction {
+	groups := make(map[string][]Transaction)
+	for _, tx := range transactions {
+		key := fmt.Sprintf("%s|%t", strings.ToLower(merchantName(tx)), tx.Amount < 0)
+		groups[key] = append(groups[key], tx)
+	}
+
+	var recurring []RecurringTransaction
+	for _, txs := range groups {
+		if len(txs) < 2 {
+			continue
+		}
+		sort.Slice(txs, func(i, j int) bool {
+			return transactionTime(txs[i]).Before(transactionTime(txs[j]))
+		})
+
+		var intervals []int
+		for i := 1; i < len(txs); i++ {
+			days := int(math.Round(transactionTime(txs[i]).Sub(transactionTime(txs[i-1])).Hours() / 24))
+			intervals = append(intervals, days)
+		}
+		sort.Ints(intervals)
+		interval := intervals[len(intervals)/2]
+		if interval < 6 || interval > 35 {
+			continue
+		}
+
+		total := sumMoney(txs)
+		average := Money(math.Round(float64(total) / float64(len(txs))))
+		regular := true
+		for _, tx := range txs {
+			if math.Abs(float64(toMoney(tx.Amount)-average)) > math.Abs(float64(average))*0.25 {
+				regular = false
+				break
+			}
+		}
+		if !regular {
+			continue
+		}
+
+		recurring = append(recurring, RecurringTransaction{
+			Merchant: merchantName(txs[0]),
+			Amount:   average,
+			Interval: interval,
+			Last:     transactionTime(txs[len(txs)-1]),
+		})
+	}
+
+	sort.Slice(recurring, func(i, j int) bool {
+		return recurring[i].Merchant < recurring[j].Merchant
+	})
+	return recurring
+}
+
+// isRecurringTransaction reports whether a transaction belongs to one of the recurring series
+func isRecurringTransaction(tx Transaction, recurring []RecurringTransaction) bool {
+	for _, r := range recurring {
+		if strings.EqualFold(merchantName(tx), r.Merchant) && (tx.Amount < 0) == (r.Amount < 0) {
+			return true
+		}
+	}
+	return false
+}
+
+// forecastAccounts projects each account's balance at the end of the current month. Recurring
+// transactions are projected at their detected interval; everything else uses the account's
+// average daily net activity over the history window between start and end.
+func forecastAccounts(accounts []Account, start, end, now time.Time) []AccountForecast {
+	monthEnd := time.Date(now.Year(), now.Month()+1, 1, 0, 0, 0, 0, now.Location())
+	historyDays := math.Max(1, end.Sub(start).Hours()/24)
+	remainingDays := monthEnd.Sub(now).Hours() / 24
+
+	var forecasts []AccountForecast
+	for _, account := range accounts {
+		recurring := detectRecurring(account.Transactions)
+
+		var upcoming Money
+		for _, r := range recurring {
+			step := time.Duration(r.Interval) * 24 * time.Hour
+			for next := r.Last.Add(step); next.Before(monthEnd); next = next.Add(step) {
+				if !next.Before(now) {
+					upcoming += r.Amount
+				}
+			}
+		}
+
+		var other Money
+		for _, tx := range account.Transactions {
+			if !isRecurringTransaction(tx, recurring) {
+				other += toMoney(tx.Amount)
+			}
+		}
+		other = Money(math.Round(float64(other) / historyDays * remainingDays))
+
+		forecast := AccountForecast{
+			Account:   account,
+			Balance:   toMoney(account.Balance),
+			Recurring: upcoming,
+			Other:     other,
+		}
+		forecast.Projected = forecast.Balance + forecast.Recurring + forecast.Other
+		forecast.Negative = forecast.Projected < 0 && !isCreditCard(account)
+
+		forecasts = append(forecasts, forecast)
+	}
+	return forecasts
+}
+
+// writeForecast prints the forecast as a table, followed by the accounts predicted to go negative
+func writeForecast(w io.Writer, forecasts []AccountForecast, now time.Time) error {
+	monthEnd := time.Date(now.Year(), now.Month()+1, 0, 0, 0, 0, 0, now.Location())
+	fmt.Fprintf(w, "Projected balances on %s\n\n", monthEnd.Format("2006-01-02"))
+
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(tw, "ACCOUNT\tBALANCE\tRECURRING\tOTHER\tPROJECTED")
+	var negative []string
+	for _, f := range forecasts {
+		flag := ""
+		if f.Negative {
+			flag = " ⚠️"
+			negative = append(negative, accountDisplayName(f.Account))
+		}
+		fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s%s\n", accountDisplayName(f.Account), f.Balance, f.Recurring, f.Other, f.Projected, flag)
+	}
+	if err := tw.Flush(); err != nil {
+		return err
+	}
+
+	if len(negative) > 0 {
+		fmt.Fprintf(w, "\n⚠️ Predicted to go negative: %s\n", strings.Join(negative, ", "))
+	}
+	return nil
+}
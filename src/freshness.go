@@ -0,0 +1,97 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// staleAccount describes an account whose data hasn't been refreshed within its threshold
+type staleAccount struct {
+	Account   Account
+	LastSync  time.Time
+	Threshold int // Days
+}
+
+// findStaleAccounts returns accounts whose balance date is older than their staleness threshold.
+// The per-account stale_after_days setting overrides the global threshold.
+func findStaleAccounts(accounts []Account, defaultThresholdDays int, filterConfig *FilterConfig, now time.Time) []staleAccount {
+	var stale []staleAccount
+	for _, account := range accounts {
+		threshold := defaultThresholdDays
+		if days := lookupAccountSettings(account, filterConfig).StaleAfterDays; days > 0 {
+			threshold = days
+		}
+		if threshold <= 0 {
+			continue
+		}
+
+		lastSync := time.Unix(account.BalanceDate, 0)
+		if now.Sub(lastSync) > time.Duration(threshold)*24*time.Hour {
+			stale = append(stale, staleAccount{Account: account, LastSync: lastSync, Threshold: threshold})
+		}
+	}
+	return stale
+}
+
+// checkFreshness warns about stale accounts through the warning channel. Each account is only
+// reported once per stale balance date so repeated runs don't repeat the same alert.
+func checkFreshness(settings *Settings, config RunConfig, accounts []Account, filterConfig *FilterConfig) error {
+	stale := findStaleAccounts(accounts, settings.StaleAccountDays, filterConfig, time.Now())
+	state := loadAccountState(settings.CacheDir)
+
+	var lines []string
+	var alerted []Account
+	for _, s := range stale {
+		log.Warn().
+			Str("account_name", s.Account.Name).
+			Str("last_sync", s.LastSync.Format("2006-01-02 15:04")).
+			Int("threshold_days", s.Threshold).
+			Msg("⏰ Account data is stale")
+
+		if lookupAccountSettings(s.Account, filterConfig).IsExcludedFrom(AccountScopeNotifications) {
+			continue
+		}
+		if state.AlreadyAlertedStale(s.Account) {
+			log.Debug().Str("account_name", s.Account.Name).Msg("Stale account already reported, skipping alert")
+			continue
+		}
+
+		lines = append(lines, fmt.Sprintf("- %s: last synced %s (threshold: %d days)",
+			accountDisplayName(s.Account), s.LastSync.Format("2006-01-02"), s.Threshold))
+		alerted = append(alerted, s.Account)
+	}
+
+	if len(lines) > 0 && !config.DisableNotifications {
+		message := fmt.Sprintf("⏰ **Stale account data**\n\nThe following accounts haven't synced recently. The connection may need attention:\n%s", strings.Join(lines, "\n"))
+		if config.DryRun {
+			return renderNotification(settings, message, nil, nil, "warning", config.Notifications)
+		}
+		_, err := sendNotification(settings, message, nil, nil, "warning", config.Notifications)
+		if errors.Is(err, errNotificationDeferred) {
+			// Not marked as reported, so it is sent again by a later run
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("error sending stale account warning: %w", err)
+		}
+		for _, account := range alerted {
+			state.MarkStaleAlerted(account)
+		}
+	}
+
+	if config.DryRun || state == nil {
+		return nil
+	}
+
+	// Forget alerts for accounts that are fresh again
+	state.ClearFreshAccounts(accounts, stale)
+	if err := state.Save(); err != nil {
+		log.Warn().Err(err).Msg("Failed to save stale account alert state")
+	}
+
+	return nil
+}
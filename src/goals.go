@@ -0,0 +1,143 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// Goal is a savings target tracked against an account balance
+type Goal struct {
+	Name     string  `yaml:"name"`
+	Account  string  `yaml:"account"`  // Account name or ID whose balance counts toward the goal
+	Target   float64 `yaml:"target"`   // Target balance
+	Deadline string  `yaml:"deadline"` // Optional target date (YYYY-MM-DD)
+}
+
+// GoalProgress is the computed progress toward a goal
+type GoalProgress struct {
+	Name          string
+	AccountName   string
+	Balance       Money
+	Target        Money
+	Percent       float64
+	Remaining     Money
+	NetChange     Money      // Net change of the account over the analyzed period
+	MonthlyPace   Money      // Net change per month over the analyzed period
+	Deadline      *time.Time // Nil when the goal has no deadline
+	MonthlyNeeded *Money     // Monthly savings needed to reach the target by the deadline
+}
+
+// OnTrack reports whether the current pace reaches the target by the deadline. Goals without a
+// deadline are on track as long as the balance is growing.
+func (p GoalProgress) OnTrack() bool {
+	if p.Remaining <= 0 {
+		return true
+	}
+	if p.MonthlyNeeded == nil {
+		return p.MonthlyPace > 0
+	}
+	return p.MonthlyPace >= *p.MonthlyNeeded
+}
+
+// computeGoalProgress computes progress for each configured goal from the linked account's balance
+// and its net change between start and end. Goals whose account isn't found are skipped.
+func computeGoalProgress(accounts []Account, filterConfig *FilterConfig, start, end time.Time, now time.Time) []GoalProgress {
+	if filterConfig == nil || len(filterConfig.Goals) == 0 {
+		return nil
+	}
+
+	months := end.Sub(start).Hours() / 24 / 30.44
+	if months < 1 {
+		months = 1
+	}
+
+	var progress []GoalProgress
+	for _, goal := range filterConfig.Goals {
+		account, ok := findAccount(accounts, goal.Account)
+		if !ok {
+			log.Warn().Str("goal", goal.Name).Str("account", goal.Account).Msg("Goal account not found, skipping goal")
+			continue
+		}
+
+		balance := toMoney(account.Balance)
+		target := toMoney(Balance(goal.Target))
+		netChange := sumMoney(account.Transactions)
+
+		p := GoalProgress{
+			Name:        goal.Name,
+			AccountName: accountDisplayName(account),
+			Balance:     balance,
+			Target:      target,
+			Remaining:   target - balance,
+			NetChange:   netChange,
+			MonthlyPace: Money(math.Round(float64(netChange) / months)),
+		}
+		if target > 0 {
+			p.Percent = math.Min(100, float64(balance)/float64(target)*100)
+		}
+
+		if goal.Deadline != "" {
+			deadline, err := time.ParseInLocation("2006-01-02", goal.Deadline, periodLocation)
+			if err != nil {
+				log.Warn().Err(err).Str("goal", goal.Name).Msg("Invalid goal deadline, ignoring it")
+			} else {
+				p.Deadline = &deadline
+				monthsLeft := deadline.Sub(now).Hours() / 24 / 30.44
+				if p.Remaining > 0 {
+					needed := p.Remaining
+					if monthsLeft > 1 {
+						needed = Money(math.Ceil(float64(p.Remaining) / monthsLeft))
+					}
+					p.MonthlyNeeded = &needed
+				}
+			}
+		}
+
+		progress = append(progress, p)
+	}
+	return progress
+}
+
+// findAccount finds an account by ID, name or nickname (case-insensitive)
+func findAccount(accounts []Account, key string) (Account, bool) {
+	for _, account := range accounts {
+		if account.ID == key || strings.EqualFold(account.Name, key) || (account.Nickname != "" && strings.EqualFold(account.Nickname, key)) {
+			return account, true
+		}
+	}
+	return Account{}, false
+}
+
+// formatGoalsSummary renders goal progress as a markdown section for notifications
+func formatGoalsSummary(progress []GoalProgress) string {
+	if len(progress) == 0 {
+		return ""
+	}
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "## %s\n\n", currentLocale.GoalsHeading)
+	for _, p := range progress {
+		status := currentLocale.GoalOnTrack
+		if !p.OnTrack() {
+			status = currentLocale.GoalBehind
+		}
+		if p.Remaining <= 0 {
+			status = currentLocale.GoalReached
+		}
+
+		fmt.Fprintf(&b, "- **%s** (%s): %s / %s (%.0f%%) - %s\n", p.Name, p.AccountName,
+			currentLocale.formatAmount(p.Balance), currentLocale.formatAmount(p.Target), p.Percent, status)
+		if p.Remaining > 0 {
+			line := "  - " + fmt.Sprintf(currentLocale.GoalPace, currentLocale.formatAmount(p.MonthlyPace))
+			if p.MonthlyNeeded != nil {
+				line += fmt.Sprintf(currentLocale.GoalNeeded, currentLocale.formatAmount(*p.MonthlyNeeded), currentLocale.formatDate(p.Deadline))
+			}
+			b.WriteString(line + "\n")
+		}
+	}
+	return b.String()
+}
@@ -0,0 +1,91 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// GotifyMessage is the payload of Gotify's POST /message endpoint
+type GotifyMessage struct {
+	Title    string                 `json:"title"`
+	Message  string                 `json:"message"`
+	Priority int                    `json:"priority"`
+	Extras   map[string]interface{} `json:"extras,omitempty"`
+}
+
+// gotifyConfigured reports whether both the Gotify server and app token are set
+func gotifyConfigured(settings *Settings) bool {
+	return settings.GotifyURL != "" && settings.GotifyToken != ""
+}
+
+// gotifyPriority maps the notification type to the configured Gotify priority
+func gotifyPriority(settings *Settings, notificationTopic string) int {
+	if notificationTopic == "warning" {
+		return settings.GotifyWarnPriority
+	}
+	return settings.GotifyPriority
+}
+
+// newGotifyMessage builds the Gotify payload; the message is sent as markdown, which the
+// Gotify web UI and Android app render
+func newGotifyMessage(settings *Settings, message string, transactions []Transaction, period *SummaryPeriod, notificationTopic string) (GotifyMessage, error) {
+	title, err := notificationTitle(settings, transactions, period, notificationTopic)
+	if err != nil {
+		return GotifyMessage{}, err
+	}
+	return GotifyMessage{
+		Title:    title,
+		Message:  message,
+		Priority: gotifyPriority(settings, notificationTopic),
+		Extras: map[string]interface{}{
+			"client::display": map[string]string{"contentType": "text/markdown"},
+		},
+	}, nil
+}
+
+// sendGotifyNotification sends a notification to the Gotify server with the priority of the notification type
+func sendGotifyNotification(settings *Settings, message string, transactions []Transaction, period *SummaryPeriod, notificationTopic string) error {
+	if !gotifyConfigured(settings) {
+		return nil
+	}
+
+	gotifyMessage, err := newGotifyMessage(settings, message, transactions, period, notificationTopic)
+	if err != nil {
+		return err
+	}
+	body, err := json.Marshal(gotifyMessage)
+	if err != nil {
+		return fmt.Errorf("error encoding gotify message: %w", err)
+	}
+
+	url := strings.TrimSuffix(settings.GotifyURL, "/") + "/message"
+	req, err := http.NewRequest(http.MethodPost, url, bytes.NewReader(body))
+	if err != nil {
+		return fmt.Errorf("error creating request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("X-Gotify-Key", settings.GotifyToken.Reveal())
+
+	client := newHTTPClient(10 * time.Second)
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return fmt.Errorf("error sending notification: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		respBody, _ := io.ReadAll(resp.Body)
+		return fmt.Errorf("notification failed with status %d: %s", resp.StatusCode, string(respBody))
+	}
+
+	log.Debug().Int("priority", gotifyPriority(settings, notificationTopic)).Msg("Gotify notification sent successfully")
+	return nil
+}
@@ -0,0 +1,183 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// amountMatches reports whether an amount is within one unit of the currency (e.g. $1) or 1% of a computed amount
+func amountMatches(amount float64, computed []float64) bool {
+	for _, c := range computed {
+		if math.Abs(amount-c) <= math.Max(1, c*0.01) {
+			return true
+		}
+	}
+	return false
+}
+
+// analyticsFigures returns the amounts formatAnalytics writes: the total and the category,
+// subcategory, uncategorized and merchant totals
+func analyticsFigures(analytics SpendingAnalytics) []float64 {
+	amounts := []Money{analytics.Total, analytics.Uncategorized.Total}
+	for _, category := range analytics.Categories {
+		amounts = append(amounts, category.Total)
+		for _, child := range category.Children {
+			amounts = append(amounts, child.Total)
+		}
+	}
+	for _, merchant := range analytics.Merchants {
+		amounts = append(amounts, merchant.Total)
+	}
+
+	figures := make([]float64, 0, len(amounts))
+	for _, amount := range amounts {
+		figures = append(figures, amount.Float64())
+	}
+	return figures
+}
+
+// summaryFigures returns the only figures the summary may quote: the pre-calculated amounts
+// generateAnalysisPrompt wrote into the prompt, plus those of the comparison, income and interest
+// sections. The prompt's raw transaction amounts and balances are left out, so a made-up figure
+// can't pass for one of them.
+func summaryFigures(promptFigures []float64, comparison *PeriodComparison, income IncomeSummary, interest []InterestSummary) []float64 {
+	var amounts []Money
+	if comparison != nil {
+		amounts = append(amounts, comparison.Total, comparison.PreviousTotal, comparison.Total-comparison.PreviousTotal)
+		for _, delta := range comparison.Categories {
+			amounts = append(amounts, delta.Current, delta.Previous, delta.Current-delta.Previous)
+		}
+	}
+
+	if _, ok := income.SavingsRate(); ok {
+		amounts = append(amounts, income.Income(), income.Payroll, income.Other, income.Spent, income.Income()-income.Spent)
+	}
+
+	var interestTotal, interestYearToDate Money
+	for _, summary := range interest {
+		interestTotal += summary.Earned
+		amounts = append(amounts, summary.Earned)
+		if summary.Expected != nil {
+			amounts = append(amounts, *summary.Expected)
+		}
+		if summary.YearToDate != nil {
+			interestYearToDate += *summary.YearToDate
+			amounts = append(amounts, *summary.YearToDate)
+		}
+	}
+	if len(interest) > 0 {
+		amounts = append(amounts, interestTotal, interestYearToDate)
+	}
+
+	figures := make([]float64, 0, len(promptFigures)+len(amounts))
+	for _, figure := range promptFigures {
+		figures = append(figures, math.Abs(figure))
+	}
+	for _, amount := range amounts {
+		figures = append(figures, math.Abs(amount.Float64()))
+	}
+	return figures
+}
+
+// unverifiedAmounts returns the amounts in the summary (written in the current locale's
+// format) that don't match any of the computed figures
+func unverifiedAmounts(summary string, figures []float64) []string {
+	var unverified []string
+	for _, match := range currentLocale.amountPattern().FindAllStringSubmatch(summary, -1) {
+		value, ok := currentLocale.parseAmount(match[1] + match[2])
+		if !ok || amountMatches(value, figures) {
+			continue
+		}
+		unverified = append(unverified, match[0])
+	}
+	return unverified
+}
+
+// correctionPrompt asks the LLM to rewrite a summary that quoted amounts not found in the data
+func correctionPrompt(unverified []string) string {
+	return fmt.Sprintf(`
+
+IMPORTANT: A previous version of this report mentioned amounts that are not among the pre-calculated figures above (%s).
+Rewrite the report quoting only the pre-calculated figures (period totals, burn rates, projections, category, merchant, comparison, income and interest totals) and the largest expenses, exactly. Do not add up, estimate or round amounts yourself.`,
+		strings.Join(unverified, ", "))
+}
+
+// renderFallbackSummary renders a summary from the computed analytics alone, used when the LLM
+// keeps quoting amounts that don't match the data
+func renderFallbackSummary(analytics SpendingAnalytics, startDate, endDate time.Time) string {
+	l := currentLocale
+	var b strings.Builder
+	fmt.Fprintf(&b, "### %s\n\n", l.SummaryHeading)
+	fmt.Fprintf(&b, l.FallbackSpent+"\n\n",
+		l.formatAmount(analytics.Total), analytics.Count, l.formatDate(startDate), l.formatDate(endDate))
+
+	if len(analytics.Categories) > 0 {
+		fmt.Fprintf(&b, "### %s\n\n", l.CategoriesHeading)
+		for _, category := range analytics.Categories {
+			fmt.Fprintf(&b, "- %s: %s\n", category.Name, l.formatAmount(category.Total))
+			for _, child := range category.Children {
+				fmt.Fprintf(&b, "  - %s: %s\n", child.Name, l.formatAmount(child.Total))
+			}
+		}
+		if analytics.Uncategorized.Count > 0 {
+			fmt.Fprintf(&b, "- %s: %s\n", l.Uncategorized, l.formatAmount(analytics.Uncategorized.Total))
+		}
+		b.WriteString("\n")
+	}
+
+	if len(analytics.Merchants) > 0 {
+		fmt.Fprintf(&b, "### %s\n\n", l.MerchantsHeading)
+		for _, merchant := range analytics.Merchants {
+			fmt.Fprintf(&b, "- "+l.MerchantLine+"\n", merchant.Name, l.formatAmount(merchant.Total), merchant.Count)
+		}
+		b.WriteString("\n")
+	}
+
+	if len(analytics.Largest) > 0 {
+		fmt.Fprintf(&b, "### %s\n\n", l.LargestHeading)
+		for _, txn := range analytics.Largest {
+			fmt.Fprintf(&b, "- "+l.LargestLine+"\n", l.formatAmount(-toMoney(txn.Amount)), merchantName(txn), l.formatDate(transactionTime(txn)))
+		}
+		b.WriteString("\n")
+	}
+
+	fmt.Fprintf(&b, "---\n*%s*", l.FallbackNote)
+	return b.String()
+}
+
+// verifySummary checks that the amounts in the LLM summary match the computed figures (see
+// summaryFigures). On a mismatch the summary is regenerated once with a correction; if that still
+// doesn't match, a summary rendered from the analytics is returned instead. The returned flag
+// reports whether the summary was replaced.
+func verifySummary(settings *Settings, prompt, summary string, isComplexAnalysis bool, figures []float64, analytics SpendingAnalytics, startDate, endDate time.Time) (string, bool) {
+	unverified := unverifiedAmounts(summary, figures)
+	if len(unverified) == 0 {
+		return summary, false
+	}
+
+	log.Warn().
+		Strs("amounts", unverified).
+		Msg("⚠️ AI summary mentions amounts that don't match the computed figures, regenerating")
+	log.Debug().Str("analysis", summary).Msg("Rejected AI analysis")
+
+	corrected, err := getLLMResponse(llmSettings(settings, LLMTaskSummary), prompt+correctionPrompt(unverified), isComplexAnalysis, nil)
+	if err != nil {
+		log.Warn().Err(err).Msg("Failed to regenerate AI summary, using the computed summary")
+		return renderFallbackSummary(analytics, startDate, endDate), true
+	}
+
+	if unverified := unverifiedAmounts(corrected, figures); len(unverified) > 0 {
+		log.Warn().
+			Strs("amounts", unverified).
+			Msg("⚠️ Regenerated AI summary still mentions unmatched amounts, using the computed summary")
+		log.Debug().Str("analysis", corrected).Msg("Rejected AI analysis")
+		return renderFallbackSummary(analytics, startDate, endDate), true
+	}
+
+	log.Info().Msg("✅ Regenerated AI summary matches the computed figures")
+	return corrected, true
+}
@@ -0,0 +1,82 @@
+package main
+
+import (
+	"reflect"
+	"testing"
+	"time"
+)
+
+// TestSummaryFiguresMultiCycle checks that a summary of the default three-cycle range may quote
+// the per-cycle totals, burn rates, projection and latest-cycle category totals from the prompt,
+// but not totals the prompt never gave, like a category summed over the whole range
+func TestSummaryFiguresMultiCycle(t *testing.T) {
+	withMoneyConfig(t, "USD", RoundingModeHalfUp)
+	previousLocation := periodLocation
+	t.Cleanup(func() { periodLocation = previousLocation })
+	periodLocation = time.UTC
+
+	at := func(month time.Month, day int) int64 {
+		return time.Date(2026, month, day, 12, 0, 0, 0, time.UTC).Unix()
+	}
+	transactions := []Transaction{
+		{ID: "1", Description: "Market", Amount: -100, Posted: at(time.January, 10), Category: "Groceries"},
+		{ID: "2", Description: "Cafe", Amount: -50, Posted: at(time.January, 20), Category: "Dining"},
+		{ID: "3", Description: "Market", Amount: -80, Posted: at(time.February, 5), Category: "Groceries"},
+		{ID: "4", Description: "Cafe", Amount: -40, Posted: at(time.February, 15), Category: "Dining"},
+		{ID: "5", Description: "Market", Amount: -60, Posted: at(time.March, 3), Category: "Groceries"},
+		{ID: "6", Description: "Cafe", Amount: -20, Posted: at(time.March, 10), Category: "Dining"},
+	}
+	start := time.Date(2026, time.January, 1, 0, 0, 0, 0, time.UTC)
+	end := time.Date(2026, time.March, 15, 0, 0, 0, 0, time.UTC)
+
+	_, promptFigures := generateAnalysisPrompt(nil, transactions, start, end, DateRangeTypeCurrentAndLastMonth, 1, nil, nil)
+	figures := summaryFigures(promptFigures, nil, IncomeSummary{}, nil)
+
+	tests := []struct {
+		summary string
+		want    []string
+	}{
+		// Cycle totals, grand total, burn rates and projection from the period description
+		{"You spent $150.00, $120.00 and $80.00 over the three cycles, $350.00 in total.", nil},
+		{"Completed cycles averaged $67.50/day ($75.00 and $60.00), projecting $2,025.00 a month.", nil},
+		// Latest-cycle category and merchant totals from the computed analytics
+		{"Groceries took $60.00 this cycle and Dining $20.00.", nil},
+		// Whole-range category totals and made-up amounts aren't in the prompt
+		{"Groceries took $240.00 across the period.", []string{"$240.00"}},
+		{"You could save $500 a month.", []string{"$500"}},
+	}
+
+	for _, tt := range tests {
+		if got := unverifiedAmounts(tt.summary, figures); !reflect.DeepEqual(got, tt.want) {
+			t.Errorf("unverifiedAmounts(%q) = %q, want %q", tt.summary, got, tt.want)
+		}
+	}
+}
+
+// TestUnverifiedAmountsCurrency checks that amounts are read with the report currency's symbol or
+// code in the locale's format, so euro amounts are checked too
+func TestUnverifiedAmountsCurrency(t *testing.T) {
+	withMoneyConfig(t, "EUR", RoundingModeHalfUp)
+	previousLocale := currentLocale
+	t.Cleanup(func() { currentLocale = previousLocale })
+	if err := configureLocale(LocaleFrench); err != nil {
+		t.Fatal(err)
+	}
+
+	figures := []float64{1234.56}
+	tests := []struct {
+		summary string
+		want    []string
+	}{
+		{"Vous avez dépensé " + currentLocale.formatAmount(123456) + ".", nil},
+		{"Vous avez dépensé 1 234,56 EUR.", nil},
+		{"Vous pourriez économiser 500 € par mois.", []string{"500 €"}},
+		{"Vous pourriez économiser €500 par mois.", []string{"€500"}},
+	}
+
+	for _, tt := range tests {
+		if got := unverifiedAmounts(tt.summary, figures); !reflect.DeepEqual(got, tt.want) {
+			t.Errorf("unverifiedAmounts(%q) = %q, want %q", tt.summary, got, tt.want)
+		}
+	}
+}
@@ -1,6 +1,7 @@
 package main
 
 import (
+	"errors"
 	"fmt"
 	"os"
 	"regexp"
@@ -14,9 +15,15 @@ import (
 	"github.com/rs/zerolog/log"
 )
 
+// logFormat selects the log output: "console" (human-readable, default) or "json" (one JSON object per line)
+var logFormat = "console"
+
+// logFormatFlag is set when --log-format was passed, which takes precedence over LOG_FORMAT
+var logFormatFlag bool
+
 // initLogger initializes the Zerolog logger with the appropriate level
 func initLogger(verbose bool) {
-	log.Logger = log.Output(zerolog.ConsoleWriter{Out: os.Stderr})
+	setLogOutput()
 	zerolog.TimeFieldFormat = zerolog.TimeFormatUnix
 	zerolog.SetGlobalLevel(zerolog.InfoLevel)
 	if verbose {
@@ -24,6 +31,42 @@ func initLogger(verbose bool) {
 	}
 }
 
+// setLogOutput points the logger at stderr in the current log format
+func setLogOutput() {
+	if logFormat == "json" {
+		log.Logger = zerolog.New(os.Stderr).With().Timestamp().Logger()
+	} else {
+		log.Logger = log.Output(zerolog.ConsoleWriter{Out: os.Stderr})
+	}
+}
+
+// configureLogFormat applies LOG_FORMAT from the loaded settings (environment, .env or config
+// file), unless --log-format was passed. Only the logs written before the settings were loaded
+// use the default format.
+func configureLogFormat(format string) {
+	if format == "" || logFormatFlag || format == logFormat {
+		return
+	}
+	logFormat = format
+	setLogOutput()
+}
+
+// startSpan returns a logger whose events are tagged with the span name and context, and a
+// function that logs the span's duration and outcome when the operation finishes
+func startSpan(name string, context map[string]interface{}) (zerolog.Logger, func(error)) {
+	logger := log.With().Str("span", name).Fields(context).Logger()
+	start := time.Now()
+	logger.Debug().Msg("Span started")
+
+	return logger, func(err error) {
+		event := logger.Debug()
+		if err != nil {
+			event = logger.Warn().Err(err)
+		}
+		event.Int64("duration_ms", time.Since(start).Milliseconds()).Msg("Span finished")
+	}
+}
+
 // retryWithBackoff implements a retry mechanism with exponential backoff
 func retryWithBackoff[T any](
 	operation func() (T, error),
@@ -40,6 +83,10 @@ func retryWithBackoff[T any](
 		if lastErr == nil {
 			return result, nil
 		}
+		// Retrying is pointless while the host's circuit breaker is open
+		if errors.Is(lastErr, errCircuitOpen) {
+			break
+		}
 
 		log.Warn().
 			Err(lastErr).
@@ -55,6 +102,9 @@ func retryWithBackoff[T any](
 
 	// Return zero value and error if all retries failed
 	var zero T
+	if errors.Is(lastErr, errCircuitOpen) {
+		return zero, fmt.Errorf("%s failed: %w", operationName, lastErr)
+	}
 	return zero, fmt.Errorf("all %d retry attempts failed for %s. Last error: %w", maxRetries, operationName, lastErr)
 }
 
@@ -111,7 +161,7 @@ func countTransactionDays(transactions []Transaction, startDate, endDate time.Ti
 		if timestamp == nil {
 			timestamp = &txn.Posted
 		}
-		txnDate := time.Unix(*timestamp, 0)
+		txnDate := time.Unix(*timestamp, 0).In(periodLocation)
 
 		// Only count transactions within the period
 		if !txnDate.Before(startDate) && !txnDate.After(endDate) {
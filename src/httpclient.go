@@ -0,0 +1,105 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+const (
+	hostRequestInterval     = 250 * time.Millisecond // Minimum spacing between requests to the same host
+	circuitFailureThreshold = 5                      // Consecutive failures before a host's circuit opens
+	circuitCooldown         = time.Minute            // How long an open circuit rejects requests
+)
+
+// errCircuitOpen is returned for requests to a host whose circuit breaker is open
+var errCircuitOpen = errors.New("circuit breaker open")
+
+// hostState tracks rate limiting and failures for a single host
+type hostState struct {
+	next      time.Time // Earliest time the next request may start
+	failures  int       // Consecutive failed requests
+	openUntil time.Time // Requests are rejected until this time
+}
+
+// outboundGovernor is an http.RoundTripper shared by all outbound clients. It spaces requests to
+// each host by hostRequestInterval and opens a circuit breaker after circuitFailureThreshold
+// consecutive failures (transport errors, 429 or 5xx). Once the cooldown has passed, a single
+// failure reopens the circuit until a request succeeds.
+type outboundGovernor struct {
+	transport http.RoundTripper
+	mu        sync.Mutex
+	hosts     map[string]*hostState
+}
+
+// httpGovernor is the governor used by every client created with newHTTPClient
+var httpGovernor = &outboundGovernor{
+	transport: http.DefaultTransport,
+	hosts:     make(map[string]*hostState),
+}
+
+// newHTTPClient returns an HTTP client whose requests go through the shared governor
+func newHTTPClient(timeout time.Duration) *http.Client {
+	return &http.Client{
+		Timeout:   timeout,
+		Transport: httpGovernor,
+	}
+}
+
+// RoundTrip waits for the host's rate limit, rejects the request if the circuit is open, and
+// records the outcome
+func (g *outboundGovernor) RoundTrip(req *http.Request) (*http.Response, error) {
+	host := req.URL.Host
+
+	g.mu.Lock()
+	state, ok := g.hosts[host]
+	if !ok {
+		state = &hostState{}
+		g.hosts[host] = state
+	}
+	now := time.Now()
+	if now.Before(state.openUntil) {
+		retryAfter := state.openUntil.Sub(now).Round(time.Second)
+		g.mu.Unlock()
+		return nil, fmt.Errorf("%s: %w (retry in %s)", host, errCircuitOpen, retryAfter)
+	}
+	wait := state.next.Sub(now)
+	if wait < 0 {
+		wait = 0
+	}
+	state.next = now.Add(wait + hostRequestInterval)
+	g.mu.Unlock()
+
+	if wait > 0 {
+		select {
+		case <-time.After(wait):
+		case <-req.Context().Done():
+			return nil, req.Context().Err()
+		}
+	}
+
+	resp, err := g.transport.RoundTrip(req)
+	failed := err != nil || resp.StatusCode == http.StatusTooManyRequests || resp.StatusCode >= 500
+
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	if !failed {
+		state.failures = 0
+		return resp, err
+	}
+
+	state.failures++
+	if state.failures >= circuitFailureThreshold {
+		state.openUntil = time.Now().Add(circuitCooldown)
+		log.Warn().
+			Str("host", host).
+			Int("consecutive_failures", state.failures).
+			Dur("cooldown", circuitCooldown).
+			Msg("Circuit breaker opened for host")
+	}
+	return resp, err
+}
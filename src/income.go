@@ -0,0 +1,97 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+)
+
+// payrollKeywords identifies paychecks in transaction descriptions
+var payrollKeywords = []string{
+	"payroll",
+	"salary",
+	"direct dep",
+	"dir dep",
+	"paycheck",
+}
+
+// IncomeSummary holds the income and spending of the analyzed period across all accounts
+type IncomeSummary struct {
+	Payroll  Money // Deposits matching payroll patterns
+	Other    Money // Other deposits (e.g. interest, reimbursements, side income)
+	Deposits int   // Number of income transactions
+	Spent    Money // Spending across all accounts (positive)
+}
+
+// Income returns the total income
+func (s IncomeSummary) Income() Money {
+	return s.Payroll + s.Other
+}
+
+// SavingsRate returns the share of income that wasn't spent, in percent, and false without income
+func (s IncomeSummary) SavingsRate() (float64, bool) {
+	income := s.Income()
+	if income <= 0 {
+		return 0, false
+	}
+	return float64(income-s.Spent) / float64(income) * 100, true
+}
+
+// isIncome determines if a transaction is income: a deposit into an account other than a credit
+// card (where credits are payments or refunds) that isn't a transfer or a linked refund
+func isIncome(account Account, tx Transaction) bool {
+	return tx.Amount > 0 && !tx.IsTransfer && tx.RefundOf == "" && !isCreditCard(account)
+}
+
+// isPayroll determines if an income transaction looks like a paycheck
+func isPayroll(tx Transaction) bool {
+	descLower := strings.ToLower(tx.Description)
+	for _, keyword := range payrollKeywords {
+		if strings.Contains(descLower, keyword) {
+			return true
+		}
+	}
+	return false
+}
+
+// summarizeIncome totals income and spending over the given accounts. Transfers between accounts
+// are ignored and linked refunds are netted from their purchases.
+func summarizeIncome(accounts []Account) IncomeSummary {
+	var summary IncomeSummary
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			switch {
+			case isIncome(account, tx):
+				summary.Deposits++
+				if isPayroll(tx) {
+					summary.Payroll += toMoney(tx.Amount)
+				} else {
+					summary.Other += toMoney(tx.Amount)
+				}
+			case tx.Amount < 0 && !tx.IsTransfer:
+				if net, ok := netExpense(tx); ok {
+					summary.Spent -= toMoney(net.Amount)
+				}
+			}
+		}
+	}
+	return summary
+}
+
+// formatIncomeSummary formats income and the savings rate for the LLM prompt, or returns an empty
+// string if no income was found
+func formatIncomeSummary(summary IncomeSummary) string {
+	rate, ok := summary.SavingsRate()
+	if !ok {
+		return ""
+	}
+
+	result := "Income and Savings Rate (all accounts, this period):\n"
+	result += fmt.Sprintf("- Total Income: %s from %d deposit(s)\n", summary.Income().Format(), summary.Deposits)
+	result += fmt.Sprintf("   - Payroll: %s\n", summary.Payroll.Format())
+	result += fmt.Sprintf("   - Other Income: %s\n", summary.Other.Format())
+	result += fmt.Sprintf("- Total Spending (all accounts): %s\n", summary.Spent.Format())
+	result += fmt.Sprintf("- Saved: %s\n", (summary.Income() - summary.Spent).Format())
+	result += fmt.Sprintf("- Savings Rate: %.1f%% (income minus spending, over income)\n", rate)
+
+	return result
+}
@@ -0,0 +1,152 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// interestKeywords identifies interest credits in transaction descriptions
+var interestKeywords = []string{
+	"interest",
+	"int earned",
+	"int pymt",
+	"int paid",
+}
+
+// InterestSummary holds the interest detected for a single account during the analyzed period
+type InterestSummary struct {
+	AccountID   string
+	AccountName string
+	APY         *float64 // Configured APY in percent (e.g. 4.5), if known
+	Earned      Money    // Sum of interest credits in the period
+	Expected    *Money   // Expected monthly interest from balance and APY, if APY is configured
+	Credits     int      // Number of interest credit transactions
+	YearToDate  *Money   // Interest credits from January 1 of the period's year to its end, if fetched
+}
+
+// isInterestCredit determines if a transaction is an interest payment into the account
+func isInterestCredit(tx Transaction) bool {
+	if tx.Amount <= 0 {
+		return false
+	}
+
+	descLower := strings.ToLower(tx.Description)
+	for _, keyword := range interestKeywords {
+		if strings.Contains(descLower, keyword) {
+			return true
+		}
+	}
+	return false
+}
+
+// lookupAPY returns the configured APY for an account, matched by account ID or name (case-insensitive)
+func lookupAPY(account Account, filterConfig *FilterConfig) *float64 {
+	if filterConfig == nil {
+		return nil
+	}
+
+	for key, apy := range filterConfig.AccountAPY {
+		if key == account.ID || strings.EqualFold(key, account.Name) {
+			apy := apy
+			return &apy
+		}
+	}
+	return nil
+}
+
+// summarizeInterest detects interest credits per account. Accounts with neither detected interest
+// nor a configured APY are skipped.
+func summarizeInterest(accounts []Account, filterConfig *FilterConfig) []InterestSummary {
+	var summaries []InterestSummary
+
+	for _, account := range accounts {
+		summary := InterestSummary{
+			AccountID:   account.ID,
+			AccountName: accountDisplayName(account),
+			APY:         lookupAPY(account, filterConfig),
+		}
+
+		for _, tx := range account.Transactions {
+			if isInterestCredit(tx) {
+				summary.Earned += toMoney(tx.Amount)
+				summary.Credits++
+			}
+		}
+
+		if summary.Credits == 0 && summary.APY == nil {
+			continue
+		}
+
+		if summary.APY != nil && account.Balance > 0 {
+			expected := toMoney(account.Balance * Balance(*summary.APY/100/12))
+			summary.Expected = &expected
+		}
+
+		log.Debug().
+			Str("account_name", account.Name).
+			Int("interest_credits", summary.Credits).
+			Str("interest_earned", summary.Earned.String()).
+			Msg("Detected interest credits")
+
+		summaries = append(summaries, summary)
+	}
+
+	return summaries
+}
+
+// addInterestYearToDate sets the interest credited from January 1 of the period's year to its end
+// on each summary, from the summary's lookback (widened to January 1 when INTEREST_YEAR_TO_DATE is
+// set, see lookbackStart)
+func addInterestYearToDate(summaries []InterestSummary, lookback []Account, end time.Time) {
+	yearStart := time.Date(end.Year(), time.January, 1, 0, 0, 0, 0, end.Location())
+	earned := make(map[string]Money)
+	for _, account := range lookback {
+		for _, tx := range account.Transactions {
+			txTime := transactionTime(tx)
+			if isInterestCredit(tx) && !txTime.Before(yearStart) && !txTime.After(end) {
+				earned[account.ID] += toMoney(tx.Amount)
+			}
+		}
+	}
+
+	for i := range summaries {
+		yearToDate := earned[summaries[i].AccountID]
+		summaries[i].YearToDate = &yearToDate
+	}
+}
+
+// formatInterestSummary formats detected interest as a prompt section
+func formatInterestSummary(summaries []InterestSummary) string {
+	if len(summaries) == 0 {
+		return ""
+	}
+
+	var total, totalYearToDate Money
+	hasYearToDate := false
+	result := "Interest Earned (savings accounts, this period):\n"
+	for _, summary := range summaries {
+		total += summary.Earned
+		line := fmt.Sprintf("- %s: %s from %d interest credit(s)", summary.AccountName, summary.Earned.Format(), summary.Credits)
+		if summary.APY != nil {
+			line += fmt.Sprintf(" - APY: %.2f%%", *summary.APY)
+		}
+		if summary.Expected != nil {
+			line += fmt.Sprintf(" - Expected monthly interest: %s", summary.Expected.Format())
+		}
+		if summary.YearToDate != nil {
+			totalYearToDate += *summary.YearToDate
+			hasYearToDate = true
+			line += fmt.Sprintf(" - Earned year to date: %s", summary.YearToDate.Format())
+		}
+		result += line + "\n"
+	}
+	result += fmt.Sprintf("- Total Interest Earned: %s\n", total.Format())
+	if hasYearToDate {
+		result += fmt.Sprintf("- Total Interest Earned Year to Date: %s\n", totalYearToDate.Format())
+	}
+
+	return result
+}
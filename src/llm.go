@@ -1,8 +1,10 @@
 package main
 
 import (
+	"bufio"
 	"bytes"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
 	"math/rand"
@@ -20,6 +22,7 @@ type OpenRouterRequest struct {
 	Messages    []Message `json:"messages"`
 	Reasoning   Reasoning `json:"reasoning"`
 	Temperature float64   `json:"temperature,omitempty"`
+	Stream      bool      `json:"stream,omitempty"`
 }
 
 // Message represents a message in the OpenRouter API request/response
@@ -51,6 +54,21 @@ type Choice struct {
 	NativeFinishReason string  `json:"native_finish_reason"`
 }
 
+// StreamChunk represents a single server-sent event from a streaming OpenRouter response
+type StreamChunk struct {
+	Id       string         `json:"id"`
+	Choices  []StreamChoice `json:"choices"`
+	Error    *Error         `json:"error,omitempty"`
+	Provider string         `json:"provider"`
+	Model    string         `json:"model"`
+}
+
+// StreamChoice represents a choice delta in a streaming OpenRouter response
+type StreamChoice struct {
+	Delta        Message `json:"delta"`
+	FinishReason *string `json:"finish_reason"`
+}
+
 type Usage struct {
 	PromptTokens     int `json:"prompt_tokens"`
 	CompletionTokens int `json:"completion_tokens"`
@@ -63,6 +81,10 @@ type Error struct {
 	Code    int    `json:"code"`
 }
 
+// errStreamInterrupted marks a streaming response that failed after part of it was already written
+// to the output, so retrying it with the same output would write that part twice
+var errStreamInterrupted = errors.New("stream interrupted after output was written")
+
 // shuffleModels randomly shuffles the models slice
 func shuffleModels(models []string) {
 	rand.Seed(time.Now().UnixNano())
@@ -71,11 +93,20 @@ func shuffleModels(models []string) {
 	})
 }
 
-// getLLMResponse sends a prompt to the OpenRouter API and returns the response
-func getLLMResponse(settings *Settings, prompt string, isComplexAnalysis bool) (string, error) {
+// getLLMResponse sends a prompt to the OpenRouter API (or the local model server of settings from
+// llmSettings) and returns the response.
+// When output is non-nil the response is streamed and written to output as it is generated.
+func getLLMResponse(settings *Settings, prompt string, isComplexAnalysis bool, output io.Writer) (result string, err error) {
 	models := strings.Split(settings.OpenRouterModel, ",")
 
-	log.Debug().Msgf("Using models in order: %v", models)
+	logger, finish := startSpan("llm_request", map[string]interface{}{
+		"models":    models,
+		"streaming": output != nil,
+		"reasoning": isComplexAnalysis,
+	})
+	defer func() { finish(err) }()
+
+	logger.Debug().Msgf("Using models in order: %v", models)
 
 	// System message to prime the model with financial analyst role
 	systemMessage := Message{
@@ -87,6 +118,7 @@ and use data to support your observations.`,
 	}
 
 	reqBody := OpenRouterRequest{
+		Model:       models[0], // Servers without model fallbacks (e.g. Ollama) only read this
 		Models:      models,
 		Temperature: 0.4, // Lower temperature for more consistent, factual responses
 		Messages: []Message{
@@ -99,6 +131,7 @@ and use data to support your observations.`,
 		Reasoning: Reasoning{
 			Exclude: !isComplexAnalysis, // Enable reasoning for complex analysis (multi-month, etc.)
 		},
+		Stream: output != nil,
 	}
 
 	jsonData, err := json.Marshal(reqBody)
@@ -111,20 +144,25 @@ and use data to support your observations.`,
 		return "", fmt.Errorf("error creating request: %w", err)
 	}
 
-	req.Header.Set("Authorization", fmt.Sprintf("Bearer %s", settings.OpenRouterAPIKey))
+	// Local model servers take no API key
+	if settings.OpenRouterAPIKey != "" {
+		req.Header.Set("Authorization", fmt.Sprintf("Bearer %s", settings.OpenRouterAPIKey.Reveal()))
+	}
 	req.Header.Set("Content-Type", "application/json")
 
-	client := &http.Client{
-		Timeout: 360 * time.Second,
-	}
+	client := newHTTPClient(360 * time.Second)
 	resp, err := client.Do(req)
 	if err != nil {
 		return "", fmt.Errorf("error making request: %w", err)
 	}
 	defer resp.Body.Close()
 
+	if output != nil {
+		return readStreamingResponse(resp, output)
+	}
+
 	// Create a comprehensive debug message
-	event := log.Debug().
+	event := logger.Debug().
 		Int("status_code", resp.StatusCode).
 		Str("status", resp.Status).
 		Interface("headers", resp.Header)
@@ -152,7 +190,7 @@ and use data to support your observations.`,
 		return "", fmt.Errorf("error decoding response: %w", err)
 	}
 
-	log.Info().Str("model", openRouterResp.Model).Str("provider", openRouterResp.Provider).Msg(" └ OpenRouter response")
+	logger.Info().Str("model", openRouterResp.Model).Str("provider", openRouterResp.Provider).Msg(" └ OpenRouter response")
 
 	// Check for error in the response
 	if openRouterResp.Error != nil {
@@ -174,19 +212,102 @@ and use data to support your observations.`,
 	return content, nil
 }
 
+// readStreamingResponse consumes a server-sent event stream from OpenRouter, writing each
+// content delta to output and returning the complete response. Errors after content was written
+// wrap errStreamInterrupted.
+func readStreamingResponse(resp *http.Response, output io.Writer) (result string, err error) {
+	log.Debug().
+		Int("status_code", resp.StatusCode).
+		Str("status", resp.Status).
+		Interface("headers", resp.Header).
+		Msg("OpenRouter streaming response")
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return "", fmt.Errorf("API request failed with status %d: %s", resp.StatusCode, string(body))
+	}
+
+	var content strings.Builder
+	var model, provider string
+	defer func() {
+		if err != nil && content.Len() > 0 {
+			err = fmt.Errorf("%w: %w", errStreamInterrupted, err)
+		}
+	}()
+
+	scanner := bufio.NewScanner(resp.Body)
+	scanner.Buffer(make([]byte, 0, 64*1024), 1024*1024)
+	for scanner.Scan() {
+		line := scanner.Text()
+
+		// Skip blank separators and SSE comments (e.g. ": OPENROUTER PROCESSING" keep-alives)
+		if !strings.HasPrefix(line, "data: ") {
+			continue
+		}
+
+		data := strings.TrimPrefix(line, "data: ")
+		if data == "[DONE]" {
+			break
+		}
+
+		var chunk StreamChunk
+		if err := json.Unmarshal([]byte(data), &chunk); err != nil {
+			return "", fmt.Errorf("error decoding stream chunk: %w", err)
+		}
+
+		if chunk.Error != nil {
+			return "", fmt.Errorf("OpenRouter API error: %s (code: %d)", chunk.Error.Message, chunk.Error.Code)
+		}
+
+		if chunk.Model != "" {
+			model = chunk.Model
+		}
+		if chunk.Provider != "" {
+			provider = chunk.Provider
+		}
+
+		for _, choice := range chunk.Choices {
+			if choice.Delta.Content == "" {
+				continue
+			}
+			content.WriteString(choice.Delta.Content)
+			fmt.Fprint(output, choice.Delta.Content)
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return "", fmt.Errorf("error reading response stream: %w", err)
+	}
+
+	if content.Len() == 0 {
+		return "", fmt.Errorf("received empty analysis from LLM")
+	}
+
+	// Add model information as a small note at the bottom
+	footer := fmt.Sprintf("\n\n---\n*Generated by %s*", model)
+	fmt.Fprintln(output, footer)
+
+	log.Info().Str("model", model).Str("provider", provider).Msg(" └ OpenRouter response")
+
+	return content.String() + footer, nil
+}
+
 // formatTransactions formats the transactions as a markdown table
 func formatTransactions(transactions []Transaction) string {
 	var result string
-	result += "| Description | Amount | Date |\n"
-	result += "|------------|---------|------|\n"
+	result += "| Merchant | Amount | Date | Category |\n"
+	result += "|------------|---------|------|----------|\n"
 
 	for _, txn := range transactions {
 		timestamp := txn.TransactedAt
 		if timestamp == nil {
 			timestamp = &txn.Posted
 		}
-		date := time.Unix(*timestamp, 0).Format("2006-01-02")
-		result += fmt.Sprintf("| %s | %.2f | %s |\n", txn.Description, txn.Amount, date)
+		date := time.Unix(*timestamp, 0).In(periodLocation).Format("2006-01-02")
+		merchant := merchantName(txn)
+		if txn.Note != "" {
+			merchant += fmt.Sprintf(" (note: %s)", txn.Note)
+		}
+		result += fmt.Sprintf("| %s | %s | %s | %s |\n", merchant, txn.Amount, date, txn.Category)
 	}
 
 	return result
@@ -195,11 +316,11 @@ func formatTransactions(transactions []Transaction) string {
 // formatAccounts formats the accounts as a markdown table
 func formatAccounts(accounts []Account) string {
 	var result string
-	result += "| Account | Balance | Last Synced |\n"
-	result += "|------------|---------|------|\n"
+	result += "| Account | Balance | Credit Utilization | Last Synced |\n"
+	result += "|------------|---------|---------|------|\n"
 
 	for _, account := range accounts {
-		result += fmt.Sprintf("| %s | %.2f | %s |\n", account.Name, account.Balance, time.Unix(account.BalanceDate, 0).Format("2006-01-02"))
+		result += fmt.Sprintf("| %s | %s | %s | %s |\n", accountDisplayName(account), account.Balance, formatUtilization(account), time.Unix(account.BalanceDate, 0).Format("2006-01-02"))
 	}
 
 	return result
@@ -241,8 +362,8 @@ func formatTopExpenses(transactions []Transaction) string {
 		if timestamp == nil {
 			timestamp = &txn.Posted
 		}
-		date := time.Unix(*timestamp, 0).Format("Jan 2")
-		result += fmt.Sprintf("   - $%.2f at %s on %s\n", -txn.Amount, txn.Description, date)
+		date := time.Unix(*timestamp, 0).In(periodLocation).Format("Jan 2")
+		result += fmt.Sprintf("   - %s at %s on %s\n", (-toMoney(txn.Amount)).Format(), merchantName(txn), date)
 	}
 
 	return result
@@ -260,7 +381,7 @@ func formatChange(percentChange float64) string {
 
 // calculateBillingPeriodTotals calculates expense totals for each billing period in a multi-month analysis (3 periods)
 func calculateBillingPeriodTotals(transactions []Transaction, split1 time.Time, split2 time.Time) (float64, float64, float64) {
-	var period1Total, period2Total, period3Total float64
+	var period1Total, period2Total, period3Total Money
 
 	for _, txn := range transactions {
 		timestamp := txn.TransactedAt
@@ -270,28 +391,26 @@ func calculateBillingPeriodTotals(transactions []Transaction, split1 time.Time,
 		txnDate := time.Unix(*timestamp, 0)
 
 		if txnDate.Before(split1) {
-			period1Total += -float64(txn.Amount) // Convert to positive - oldest period
+			period1Total += -toMoney(txn.Amount) // Convert to positive - oldest period
 		} else if txnDate.Before(split2) {
-			period2Total += -float64(txn.Amount) // Convert to positive - middle period
+			period2Total += -toMoney(txn.Amount) // Convert to positive - middle period
 		} else {
-			period3Total += -float64(txn.Amount) // Convert to positive - current period
+			period3Total += -toMoney(txn.Amount) // Convert to positive - current period
 		}
 	}
 
-	return period1Total, period2Total, period3Total
+	return period1Total.Float64(), period2Total.Float64(), period3Total.Float64()
 }
 
 // calculateTotalExpenses calculates the total expenses for all transactions
 func calculateTotalExpenses(transactions []Transaction) float64 {
-	var total float64
-	for _, txn := range transactions {
-		total += -float64(txn.Amount) // Convert to positive
-	}
-	return total
+	return (-sumMoney(transactions)).Float64() // Convert to positive
 }
 
-// generateAnalysisPrompt generates a prompt for the AI to analyze transactions
-func generateAnalysisPrompt(accounts []Account, transactions []Transaction, startDate, endDate time.Time, dateRangeType DateRangeType, billingDay int, filterResult *FilterResult) string {
+// generateAnalysisPrompt generates a prompt for the AI to analyze transactions. It also returns the
+// pre-calculated amounts written into the prompt (period totals, burn rates, projections, computed
+// analytics, largest expenses and filtered totals), which are the figures the summary may quote.
+func generateAnalysisPrompt(accounts []Account, transactions []Transaction, startDate, endDate time.Time, dateRangeType DateRangeType, billingDay int, filterResult *FilterResult, extraSections []string) (string, []float64) {
 	transactionsFormatted := formatTransactions(transactions)
 	accountsFormatted := formatAccounts(accounts)
 	topExpensesFormatted := formatTopExpenses(transactions)
@@ -310,24 +429,42 @@ func generateAnalysisPrompt(accounts []Account, transactions []Transaction, star
 	// Calculate monthly projection (assuming 30-day month)
 	monthlyProjection := dailyBurnRate * 30
 
+	// Totals and rates are written in the report currency, rounded to its minor unit
+	formatFigure := func(value float64) string {
+		return toMoney(Balance(value)).Format()
+	}
+
 	// Determine if this is a multi-month analysis
 	isMultiMonth := dateRangeType == DateRangeTypeCurrentAndLastMonth
-	periodDescription := fmt.Sprintf("Billing Period: %s to %s (%d calendar days, %d transaction days)\nTotal Expenses: $%.2f\nDaily Burn Rate: $%.2f/day (based on transaction days)\nMonthly Projection: $%.2f (at current rate)", startDate.Format("2006-01-02"), endDate.Format("2006-01-02"), calendarDays, transactionDays, totalExpenses, dailyBurnRate, monthlyProjection)
+	periodDescription := fmt.Sprintf("Billing Period: %s to %s (%d calendar days, %d transaction days)\nTotal Expenses: %s\nDaily Burn Rate: %s/day (based on transaction days)\nMonthly Projection: %s (at current rate)", startDate.Format("2006-01-02"), endDate.Format("2006-01-02"), calendarDays, transactionDays, formatFigure(totalExpenses), formatFigure(dailyBurnRate), formatFigure(monthlyProjection))
+	figures := []float64{totalExpenses, dailyBurnRate, monthlyProjection}
 
 	summaryInstructions := "Provide a human-friendly overview of spending patterns during this period. Be specific about trends and notable observations."
 	trendAnalysisSection := ""
 
+	// Category and merchant breakdowns cover the latest billing cycle in multi-month analyses
+	analyticsTransactions := transactions
+	analyticsScope := "this period"
+
 	if isMultiMonth {
 		// Calculate the split points between billing periods (3 periods total)
 		currentYear, currentMonth, _ := endDate.Date()
 		var currentCycleStart time.Time
 		if endDate.Day() >= billingDay {
-			currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, time.UTC)
+			currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, periodLocation)
 		} else {
-			currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, time.UTC).AddDate(0, -1, 0)
+			currentCycleStart = time.Date(currentYear, currentMonth, billingDay, 0, 0, 0, 0, periodLocation).AddDate(0, -1, 0)
 		}
 		previousCycleStart := currentCycleStart.AddDate(0, -1, 0)
 
+		analyticsTransactions = nil
+		for _, txn := range transactions {
+			if !transactionTime(txn).Before(currentCycleStart) {
+				analyticsTransactions = append(analyticsTransactions, txn)
+			}
+		}
+		analyticsScope = "latest billing cycle"
+
 		// Calculate totals for each of the 3 billing periods
 		period1Total, period2Total, period3Total := calculateBillingPeriodTotals(transactions, previousCycleStart, currentCycleStart)
 
@@ -388,16 +525,20 @@ func generateAnalysisPrompt(accounts []Account, transactions []Transaction, star
 		completedMonthlyProjection := avgCompletedBurnRate * 30
 
 		periodDescription = fmt.Sprintf(`Multi-Cycle Analysis (3 Billing Periods):
-- %s: %s to %s (%d calendar/%d txn days) - $%.2f [completed] - Burn rate: $%.2f/day
-- %s: %s to %s (%d calendar/%d txn days) - $%.2f [completed] - Burn rate: $%.2f/day - Change: %.1f%% (%s)
-- %s: %s to %s (%d calendar/%d txn days) - $%.2f [in progress] - Burn rate: $%.2f/day - Change: %.1f%% (%s)
-- Grand Total: $%.2f
-- Average Burn Rate (completed cycles): $%.2f/day (based on transaction days)
-- Monthly Projection: $%.2f (based on completed cycles)`,
-			cycle1Label, period1Start.Format("2006-01-02"), period1End.Format("2006-01-02"), period1CalendarDays, period1Days, period1Total, period1BurnRate,
-			cycle2Label, period2Start.Format("2006-01-02"), period2End.Format("2006-01-02"), period2CalendarDays, period2Days, period2Total, period2BurnRate, period2Change, formatChange(period2Change),
-			cycle3Label, period3Start.Format("2006-01-02"), period3End.Format("2006-01-02"), period3CalendarDays, period3Days, period3Total, period3BurnRate, period3Change, formatChange(period3Change),
-			totalExpenses, avgCompletedBurnRate, completedMonthlyProjection)
+- %s: %s to %s (%d calendar/%d txn days) - %s [completed] - Burn rate: %s/day
+- %s: %s to %s (%d calendar/%d txn days) - %s [completed] - Burn rate: %s/day - Change: %.1f%% (%s)
+- %s: %s to %s (%d calendar/%d txn days) - %s [in progress] - Burn rate: %s/day - Change: %.1f%% (%s)
+- Grand Total: %s
+- Average Burn Rate (completed cycles): %s/day (based on transaction days)
+- Monthly Projection: %s (based on completed cycles)`,
+			cycle1Label, period1Start.Format("2006-01-02"), period1End.Format("2006-01-02"), period1CalendarDays, period1Days, formatFigure(period1Total), formatFigure(period1BurnRate),
+			cycle2Label, period2Start.Format("2006-01-02"), period2End.Format("2006-01-02"), period2CalendarDays, period2Days, formatFigure(period2Total), formatFigure(period2BurnRate), period2Change, formatChange(period2Change),
+			cycle3Label, period3Start.Format("2006-01-02"), period3End.Format("2006-01-02"), period3CalendarDays, period3Days, formatFigure(period3Total), formatFigure(period3BurnRate), period3Change, formatChange(period3Change),
+			formatFigure(totalExpenses), formatFigure(avgCompletedBurnRate), formatFigure(completedMonthlyProjection))
+		figures = []float64{
+			period1Total, period1BurnRate, period2Total, period2BurnRate, period3Total, period3BurnRate,
+			totalExpenses, avgCompletedBurnRate, completedMonthlyProjection,
+		}
 
 		summaryInstructions = fmt.Sprintf("Provide a human-friendly overview of spending patterns across the 3 billing cycles (%s, %s, %s). Focus on comparing the two completed cycles and note that the current cycle is still in progress. Use the provided billing period totals for accurate comparisons.", cycle1Label, cycle2Label, cycle3Label)
 		trendAnalysisSection = fmt.Sprintf(`4. **📈 Spending Trends** (use pre-calculated totals above):
@@ -409,43 +550,71 @@ func generateAnalysisPrompt(accounts []Account, transactions []Transaction, star
 		trendAnalysisSection = "4. "
 	}
 
+	analytics := computeAnalytics(analyticsTransactions)
+	analyticsFormatted := formatAnalytics(analytics, analyticsScope)
+	figures = append(figures, analyticsFigures(analytics)...)
+	for _, txn := range getTopExpenses(transactions, 10) {
+		figures = append(figures, (-toMoney(txn.Amount)).Float64())
+	}
+
+	// Daily and weekly recaps are short check-ins rather than full reports
+	maxWords := 180
+	if dateRangeType.isRecap() {
+		maxWords = 80
+		recapPeriod := "week"
+		if dateRangeType == DateRangeTypeYesterday {
+			recapPeriod = "day"
+		}
+		summaryInstructions = fmt.Sprintf("Give a short recap of the past %s's spending in 2-3 sentences: how much was spent, where most of it went, and anything unusual.", recapPeriod)
+	}
+
 	// Determine category description based on analysis type
-	categoryDescription := "List the top 4-5 spending categories with their totals for the LATEST billing cycle only"
+	categoryDescription := "List the top 4-5 spending categories with their totals for the LATEST billing cycle only, using the Category Totals from the Computed Analytics"
 	if !isMultiMonth {
-		categoryDescription = "List the top 4-5 spending categories with their totals for this period"
+		categoryDescription = "List the top 4-5 spending categories with their totals for this period, using the Category Totals from the Computed Analytics"
 	}
 
 	// Add filtered transactions section if any were filtered
 	filteredSection := ""
 	if filterResult != nil && filterResult.TotalFiltered > 0 {
 		// Get unique merchant names from filtered transactions
-		merchantMap := make(map[string]float64)
+		merchantMap := make(map[string]Money)
 		for _, tx := range filterResult.FilteredTransactions {
-			merchantMap[tx.Description] += float64(tx.Amount)
+			merchantMap[merchantName(tx)] += toMoney(tx.Amount)
 		}
 
 		// Build merchant summary
 		merchantSummary := ""
 		for merchant, amount := range merchantMap {
-			merchantSummary += fmt.Sprintf("   - %s: $%.2f\n", merchant, -amount)
+			merchantSummary += fmt.Sprintf("   - %s: %s\n", merchant, (-amount).Format())
+			figures = append(figures, (-amount).Float64())
 		}
+		figures = append(figures, (-toMoney(filterResult.TotalAmount)).Float64())
 
 		filteredSection = fmt.Sprintf(`
 Filtered Transactions (Excluded from Analysis):
 - Total Filtered: %d transactions
-- Total Amount: $%.2f
+- Total Amount: %s
 - Top Merchants:
 %s
 Note: These transactions were filtered per user configuration and are NOT included in the analysis above.
 
-`, filterResult.TotalFiltered, -float64(filterResult.TotalAmount), merchantSummary)
+`, filterResult.TotalFiltered, (-toMoney(filterResult.TotalAmount)).Format(), merchantSummary)
 	}
 
-	return fmt.Sprintf(`## Financial Transaction Analysis
+	// Append additional context sections (interest, etc.) after the filtered transactions
+	for _, section := range extraSections {
+		if section != "" {
+			filteredSection += "\n" + section
+		}
+	}
+
+	prompt := fmt.Sprintf(`## Financial Transaction Analysis
 %s
 
+%s
 I need a structured analysis of the provided financial transactions. Use emojis to make the report more engaging.
-Please create a concise report (max 180 words total) with the following sections:
+Please create a concise report (max %d words total) with the following sections:
 
 ### Summary
 %s
@@ -453,27 +622,34 @@ Please create a concise report (max 180 words total) with the following sections
 ### Analysis Breakdown
 1. **Total Expenses**: Per billing cycle totals shown above
 2. **Major Categories** (latest cycle only): %s
-   - Category 1: ${{amount}}
-   - Category 2: ${{amount}}
+   - Category 1: {{amount}}
+   - Category 2: {{amount}}
    - ...
 3. **Top 10 Largest Expenses** (across all periods):
 %s%s**🔍 Key Insights**: Provide 1-2 actionable insights such as:
    - Reference the daily burn rate and monthly projection provided above
    - Notable patterns or anomalies worth mentioning
    - Recurring charges or subscription reminders if relevant
+   - Notable changes vs the previous period (e.g. "Dining +34%% vs last month"), if a Comparison With Previous Period section is provided below
+   - The savings rate and how it compares to spending, if an Income and Savings Rate section is provided below
+   - Interest earned on savings, if an Interest Earned section is provided below
 
 Notes:
-- Consider only outgoing expenses in your analysis (ignore incoming payments, credits, refunds)
-- Format all monetary values consistently (e.g., $1,234.56)
+- Consider only outgoing expenses in your analysis (ignore incoming payments, credits, refunds), except for the Income and Savings Rate section
+- Format all monetary values consistently (e.g., %s)
+- Quote amounts from the period description and Computed Analytics exactly; do not add up amounts yourself
+- If there is Uncategorized spending, you may name the categories it likely falls under, but report its total as given
 - Keep insights brief and actionable
 - Use the pre-calculated burn rates and projections provided in the period description above
 - Category totals should be for the LATEST billing cycle only (not combined across periods)
 - If a category has no transactions, indicate 'No spending in this category'
+- Use the Category column as-is when it is filled in; categorize the remaining transactions yourself%s
 
 Accounts Information:
 %s
 
 All Transactions:
 %s
-%s`, periodDescription, summaryInstructions, categoryDescription, topExpensesFormatted, trendAnalysisSection, accountsFormatted, transactionsFormatted, filteredSection)
+%s`, periodDescription, analyticsFormatted, maxWords, summaryInstructions, categoryDescription, topExpensesFormatted, trendAnalysisSection, Money(123456).Format(), currentLocale.promptInstructions(), accountsFormatted, transactionsFormatted, filteredSection)
+	return prompt, figures
 }
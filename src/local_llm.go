@@ -0,0 +1,69 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+)
+
+// defaultLocalLLMURL is Ollama's OpenAI-compatible chat completions endpoint
+const defaultLocalLLMURL = "http://localhost:11434/v1/chat/completions"
+
+// LLM tasks that LOCAL_LLM_TASKS can move to the local model
+const (
+	LLMTaskSummary    = "summary"    // Period summaries, including their regeneration by the amount check
+	LLMTaskCategorize = "categorize" // LLM_CATEGORIZE batches
+	LLMTaskAsk        = "ask"        // Questions answered by `ask`
+	LLMTaskReceipt    = "receipt"    // Receipt images read by `transactions receipt` (needs a vision model)
+)
+
+// llmTasks lists the tasks accepted in LOCAL_LLM_TASKS, in the order "all" expands to
+var llmTasks = []string{LLMTaskSummary, LLMTaskCategorize, LLMTaskAsk, LLMTaskReceipt}
+
+// parseLLMTasks parses a comma-separated LOCAL_LLM_TASKS value; "all" selects every task
+func parseLLMTasks(s string) ([]string, error) {
+	var tasks []string
+	for _, task := range strings.Split(s, ",") {
+		task = strings.ToLower(strings.TrimSpace(task))
+		switch {
+		case task == "":
+			continue
+		case task == "all":
+			return llmTasks, nil
+		case !containsString(llmTasks, task):
+			return nil, fmt.Errorf("unknown task %q (supported: %s or all)", task, strings.Join(llmTasks, ", "))
+		}
+		tasks = append(tasks, task)
+	}
+	return tasks, nil
+}
+
+// containsString reports whether the slice contains the value
+func containsString(values []string, value string) bool {
+	for _, v := range values {
+		if v == value {
+			return true
+		}
+	}
+	return false
+}
+
+// localLLMTask reports whether the task runs against the local model
+func localLLMTask(settings *Settings, task string) bool {
+	return containsString(settings.LocalLLMTasks, task)
+}
+
+// llmSettings returns the settings to send an LLM task with. For tasks in LOCAL_LLM_TASKS it is a
+// copy whose endpoint and models point at LOCAL_LLM_URL and LOCAL_LLM_MODEL without the OpenRouter
+// API key, so the task's transaction data never leaves the machine.
+func llmSettings(settings *Settings, task string) *Settings {
+	if !localLLMTask(settings, task) {
+		return settings
+	}
+
+	local := *settings
+	local.OpenRouterURL = settings.LocalLLMURL
+	local.OpenRouterAPIKey = ""
+	local.OpenRouterModel = settings.LocalLLMModel
+	local.VisionModel = settings.LocalLLMModel
+	return &local
+}
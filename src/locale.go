@@ -0,0 +1,281 @@
+package main
+
+import (
+	"fmt"
+	"regexp"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Locale identifies the language summaries and notifications are written in
+type Locale string
+
+// Supported locales
+const (
+	LocaleEnglish    Locale = "en"
+	LocaleFrench     Locale = "fr"
+	LocalePortuguese Locale = "pt"
+	LocaleSpanish    Locale = "es"
+)
+
+// LocaleStrings holds the formatting conventions and notification strings for a locale
+type LocaleStrings struct {
+	Language             string // Language name the LLM is asked to write in
+	DateLayout           string // Go layout for dates
+	DateTimeLayout       string // Go layout for dates with a time
+	DecimalSeparator     string
+	ThousandsSeparator   string
+	ThousandsSeparators  string // Characters accepted as thousands separators when reading amounts
+	CurrencyAfter        bool   // Write the currency symbol after the amount (e.g. "1 234,56 €")
+	EmailSubject         string
+	EmailTitle           string
+	EmailFooter          string
+	Merchant             string
+	Amount               string
+	Date                 string
+	GoalsHeading         string
+	SubscriptionsHeading string
+	SyncIssuesHeading    string
+	SyncIssuesIntro      string
+	GoalOnTrack          string
+	GoalBehind           string
+	GoalReached          string
+	GoalPace             string // Format with the monthly savings pace
+	GoalNeeded           string // Format with the monthly amount needed and the deadline
+	SubscriptionIncrease string // Format with the merchant, new amount, previous amount and date
+	SubscriptionNew      string // Format with the merchant, amount, interval in days and date
+	SummaryHeading       string // First heading of the summary rendered when the AI one is rejected
+	FallbackSpent        string // Format with the total, transaction count, start and end dates
+	CategoriesHeading    string
+	Uncategorized        string
+	MerchantsHeading     string
+	MerchantLine         string // Format with the merchant, total and transaction count
+	LargestHeading       string
+	LargestLine          string // Format with the amount, merchant and date
+	FallbackNote         string
+}
+
+// localeStrings holds the strings of each supported locale
+var localeStrings = map[Locale]LocaleStrings{
+	LocaleEnglish: {
+		Language:             "English",
+		DateLayout:           "Jan 2, 2006",
+		DateTimeLayout:       "2006-01-02 15:04",
+		DecimalSeparator:     ".",
+		ThousandsSeparator:   ",",
+		ThousandsSeparators:  ",",
+		EmailSubject:         "Finance Tracker - Transaction Summary",
+		EmailTitle:           "Transaction Summary",
+		EmailFooter:          "This is an automated message. Please do not reply to this email.",
+		Merchant:             "Merchant",
+		Amount:               "Amount",
+		Date:                 "Date",
+		GoalsHeading:         "🎯 Savings Goals",
+		SubscriptionsHeading: "🔁 Subscription Changes",
+		SyncIssuesHeading:    "⚠️ Sync Issues",
+		SyncIssuesIntro:      "Some accounts could not be synced, so this summary may be incomplete:",
+		GoalOnTrack:          "✅ on track",
+		GoalBehind:           "⚠️ behind",
+		GoalReached:          "🎉 reached",
+		GoalPace:             "Saving %s/month over this period",
+		GoalNeeded:           ", %s/month needed by %s",
+		SubscriptionIncrease: "**%s**: %s, up from %s (charged %s)",
+		SubscriptionNew:      "**%s**: new recurring charge of %s every ~%d days (last charged %s)",
+		SummaryHeading:       "Summary",
+		FallbackSpent:        "You spent **%s** across %d transactions from %s to %s.",
+		CategoriesHeading:    "Categories",
+		Uncategorized:        "Uncategorized",
+		MerchantsHeading:     "Top Merchants",
+		MerchantLine:         "%s: %s (%d transactions)",
+		LargestHeading:       "Largest Expenses",
+		LargestLine:          "%s at %s on %s",
+		FallbackNote:         "AI summary unavailable: generated from computed totals",
+	},
+	LocaleFrench: {
+		Language:             "French",
+		DateLayout:           "02/01/2006",
+		DateTimeLayout:       "02/01/2006 15:04",
+		DecimalSeparator:     ",",
+		ThousandsSeparator:   " ",
+		ThousandsSeparators:  "   ",
+		CurrencyAfter:        true,
+		EmailSubject:         "Finance Tracker - Résumé des transactions",
+		EmailTitle:           "Résumé des transactions",
+		EmailFooter:          "Ceci est un message automatique. Merci de ne pas y répondre.",
+		Merchant:             "Commerçant",
+		Amount:               "Montant",
+		Date:                 "Date",
+		GoalsHeading:         "🎯 Objectifs d'épargne",
+		SubscriptionsHeading: "🔁 Changements d'abonnements",
+		SyncIssuesHeading:    "⚠️ Problèmes de synchronisation",
+		SyncIssuesIntro:      "Certains comptes n'ont pas pu être synchronisés, ce résumé peut donc être incomplet :",
+		GoalOnTrack:          "✅ en bonne voie",
+		GoalBehind:           "⚠️ en retard",
+		GoalReached:          "🎉 atteint",
+		GoalPace:             "Épargne de %s/mois sur cette période",
+		GoalNeeded:           ", %s/mois nécessaires d'ici le %s",
+		SubscriptionIncrease: "**%s** : %s, contre %s auparavant (prélevé le %s)",
+		SubscriptionNew:      "**%s** : nouveau prélèvement récurrent de %s tous les ~%d jours (dernier le %s)",
+		SummaryHeading:       "Résumé",
+		FallbackSpent:        "Vous avez dépensé **%s** en %d transactions du %s au %s.",
+		CategoriesHeading:    "Catégories",
+		Uncategorized:        "Non catégorisé",
+		MerchantsHeading:     "Principaux commerçants",
+		MerchantLine:         "%s : %s (%d transactions)",
+		LargestHeading:       "Plus grosses dépenses",
+		LargestLine:          "%s chez %s le %s",
+		FallbackNote:         "Résumé IA indisponible : généré à partir des totaux calculés",
+	},
+	LocalePortuguese: {
+		Language:             "Portuguese",
+		DateLayout:           "02/01/2006",
+		DateTimeLayout:       "02/01/2006 15:04",
+		DecimalSeparator:     ",",
+		ThousandsSeparator:   ".",
+		ThousandsSeparators:  ".",
+		EmailSubject:         "Finance Tracker - Resumo de transações",
+		EmailTitle:           "Resumo de transações",
+		EmailFooter:          "Esta é uma mensagem automática. Por favor, não responda a este e-mail.",
+		Merchant:             "Estabelecimento",
+		Amount:               "Valor",
+		Date:                 "Data",
+		GoalsHeading:         "🎯 Metas de economia",
+		SubscriptionsHeading: "🔁 Mudanças em assinaturas",
+		SyncIssuesHeading:    "⚠️ Problemas de sincronização",
+		SyncIssuesIntro:      "Algumas contas não puderam ser sincronizadas, então este resumo pode estar incompleto:",
+		GoalOnTrack:          "✅ no caminho certo",
+		GoalBehind:           "⚠️ atrasada",
+		GoalReached:          "🎉 atingida",
+		GoalPace:             "Economizando %s/mês neste período",
+		GoalNeeded:           ", %s/mês necessários até %s",
+		SubscriptionIncrease: "**%s**: %s, antes %s (cobrado em %s)",
+		SubscriptionNew:      "**%s**: nova cobrança recorrente de %s a cada ~%d dias (última em %s)",
+		SummaryHeading:       "Resumo",
+		FallbackSpent:        "Você gastou **%s** em %d transações de %s a %s.",
+		CategoriesHeading:    "Categorias",
+		Uncategorized:        "Sem categoria",
+		MerchantsHeading:     "Principais estabelecimentos",
+		MerchantLine:         "%s: %s (%d transações)",
+		LargestHeading:       "Maiores despesas",
+		LargestLine:          "%s em %s no dia %s",
+		FallbackNote:         "Resumo de IA indisponível: gerado a partir dos totais calculados",
+	},
+	LocaleSpanish: {
+		Language:             "Spanish",
+		DateLayout:           "02/01/2006",
+		DateTimeLayout:       "02/01/2006 15:04",
+		DecimalSeparator:     ",",
+		ThousandsSeparator:   ".",
+		ThousandsSeparators:  ".",
+		CurrencyAfter:        true,
+		EmailSubject:         "Finance Tracker - Resumen de transacciones",
+		EmailTitle:           "Resumen de transacciones",
+		EmailFooter:          "Este es un mensaje automático. Por favor, no responda a este correo.",
+		Merchant:             "Comercio",
+		Amount:               "Importe",
+		Date:                 "Fecha",
+		GoalsHeading:         "🎯 Metas de ahorro",
+		SubscriptionsHeading: "🔁 Cambios en suscripciones",
+		SyncIssuesHeading:    "⚠️ Problemas de sincronización",
+		SyncIssuesIntro:      "Algunas cuentas no se pudieron sincronizar, por lo que este resumen puede estar incompleto:",
+		GoalOnTrack:          "✅ en buen camino",
+		GoalBehind:           "⚠️ atrasada",
+		GoalReached:          "🎉 alcanzada",
+		GoalPace:             "Ahorrando %s/mes en este periodo",
+		GoalNeeded:           ", %s/mes necesarios hasta el %s",
+		SubscriptionIncrease: "**%s**: %s, antes %s (cobrado el %s)",
+		SubscriptionNew:      "**%s**: nuevo cargo recurrente de %s cada ~%d días (último el %s)",
+		SummaryHeading:       "Resumen",
+		FallbackSpent:        "Gastaste **%s** en %d transacciones del %s al %s.",
+		CategoriesHeading:    "Categorías",
+		Uncategorized:        "Sin categoría",
+		MerchantsHeading:     "Principales comercios",
+		MerchantLine:         "%s: %s (%d transacciones)",
+		LargestHeading:       "Mayores gastos",
+		LargestLine:          "%s en %s el %s",
+		FallbackNote:         "Resumen de IA no disponible: generado a partir de los totales calculados",
+	},
+}
+
+// currentLocale is the locale summaries and notifications are written in, set by configureLocale
+var currentLocale = localeStrings[LocaleEnglish]
+
+// configureLocale sets the locale used for summaries and notifications
+func configureLocale(locale Locale) error {
+	selected, ok := localeStrings[Locale(strings.ToLower(string(locale)))]
+	if !ok {
+		return fmt.Errorf("unsupported locale %q (use en, fr, pt or es)", locale)
+	}
+	currentLocale = selected
+	return nil
+}
+
+// formatAmount formats an amount with the locale's separators and the report currency's symbol
+// (see currencySymbol) in the locale's placement, e.g. "$1,234.56" or "1 234,56 €"
+func (l LocaleStrings) formatAmount(m Money) string {
+	s := m.String()
+	sign := ""
+	if strings.HasPrefix(s, "-") {
+		sign, s = "-", s[1:]
+	}
+	intPart, fracPart, hasFrac := strings.Cut(s, ".")
+
+	var grouped strings.Builder
+	for i, digit := range intPart {
+		if i > 0 && (len(intPart)-i)%3 == 0 {
+			grouped.WriteString(l.ThousandsSeparator)
+		}
+		grouped.WriteRune(digit)
+	}
+	number := grouped.String()
+	if hasFrac {
+		number += l.DecimalSeparator + fracPart
+	}
+
+	symbol := currencySymbol()
+	if l.CurrencyAfter {
+		return sign + number + " " + symbol
+	}
+	if symbol == moneyConfig.Currency {
+		// ISO codes are set apart from the number, e.g. "CHF 1,234.56"
+		symbol += " "
+	}
+	return sign + symbol + number
+}
+
+// formatDate formats a date with the locale's layout
+func (l LocaleStrings) formatDate(t time.Time) string {
+	return t.Format(l.DateLayout)
+}
+
+// promptInstructions returns the prompt instruction asking the LLM to write in the locale's
+// language and formats, or an empty string for English
+func (l LocaleStrings) promptInstructions() string {
+	if l.Language == localeStrings[LocaleEnglish].Language {
+		return ""
+	}
+	example := time.Date(2026, time.January, 31, 0, 0, 0, 0, time.UTC)
+	return fmt.Sprintf("\n- Write the entire report in %s, including section titles. Write dates like %s and amounts like %s (the data above uses English formats)",
+		l.Language, l.formatDate(example), l.formatAmount(123456))
+}
+
+// amountPattern returns a pattern matching amounts in the report currency written in the locale's
+// format, with its symbol or ISO 4217 code before or after the number, and the number in the
+// first or second group
+func (l LocaleStrings) amountPattern() *regexp.Regexp {
+	separators := regexp.QuoteMeta(l.ThousandsSeparators + l.DecimalSeparator)
+	number := fmt.Sprintf(`\d(?:[\d%s]*\d)?`, separators)
+	currency := fmt.Sprintf(`(?:%s|%s)`, regexp.QuoteMeta(currencySymbol()), regexp.QuoteMeta(moneyConfig.Currency))
+	return regexp.MustCompile(fmt.Sprintf(`%s\s?(%s)|(%s)\s?%s`, currency, number, number, currency))
+}
+
+// parseAmount parses a number written in the locale's format, e.g. "1.234,56"
+func (l LocaleStrings) parseAmount(s string) (float64, bool) {
+	for _, separator := range l.ThousandsSeparators {
+		s = strings.ReplaceAll(s, string(separator), "")
+	}
+	s = strings.Replace(s, l.DecimalSeparator, ".", 1)
+	value, err := strconv.ParseFloat(s, 64)
+	return value, err == nil
+}
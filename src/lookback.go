@@ -0,0 +1,86 @@
+package main
+
+import "time"
+
+// lookbackStart returns the date the summary fetch starts from: the period's fetch start, or the
+// earlier start of the previous period when the period is compared with it, of the
+// SUBSCRIPTION_HISTORY_MONTHS checked for subscription changes, or of the year for
+// INTEREST_YEAR_TO_DATE. The earlier transactions come in
+// the same fetch as the period's (see splitLookback) rather than in requests of their own, since
+// SimpleFin Bridge limits the requests made per day.
+func lookbackStart(settings *Settings, dateRangeType DateRangeType, fetchStart, fetchEnd time.Time) time.Time {
+	start := fetchStart
+	if previousStart, _, ok := previousPeriod(dateRangeType, fetchStart, fetchEnd); ok && previousStart.Before(start) {
+		start = previousStart
+	}
+	if historyStart := fetchStart.AddDate(0, -settings.SubscriptionMonths, 0); historyStart.Before(start) {
+		start = historyStart
+	}
+	if settings.InterestYearToDate {
+		if yearStart := time.Date(fetchStart.Year(), time.January, 1, 0, 0, 0, 0, fetchStart.Location()); yearStart.Before(start) {
+			start = yearStart
+		}
+	}
+	return start
+}
+
+// splitLookback splits accounts fetched from before periodStart into the accounts with only the
+// transactions a fetch from periodStart returns (posted since then, or pending), and the lookback:
+// the accounts with every fetched transaction
+func splitLookback(accounts []Account, periodStart time.Time) ([]Account, []Account) {
+	lookback := make([]Account, len(accounts))
+	copy(lookback, accounts)
+
+	period := make([]Account, len(accounts))
+	for i, account := range accounts {
+		period[i] = account
+		period[i].Transactions = nil
+		for _, tx := range account.Transactions {
+			if getTransactionStatus(tx) == "pending" || tx.Posted >= periodStart.Unix() {
+				period[i].Transactions = append(period[i].Transactions, tx)
+			}
+		}
+	}
+	return period, lookback
+}
+
+// prepareLookback applies to the lookback the steps run() applies to the period's accounts before
+// selecting them, leaving out those that save state: sync exclusions, merchant names, annotations,
+// transfers and refund links
+func prepareLookback(settings *Settings, lookback []Account, filterConfig *FilterConfig) []Account {
+	lookback = excludeAccounts(lookback, filterConfig, AccountScopeSync)
+	notes := loadTransactionNotes(settings.CacheDir)
+	normalizeMerchants(lookback, filterConfig)
+	applyTransactionNotes(lookback, notes)
+	markTransfers(lookback)
+	applyRefundLinks(lookback, notes)
+	return lookback
+}
+
+// lookbackExpenses returns the expenses of the included accounts within each account's window,
+// net of linked refunds and categorized and filtered like the period's
+func lookbackExpenses(settings *Settings, lookback []Account, included map[string]bool, filterConfig *FilterConfig, window func(Account) (time.Time, time.Time)) []Transaction {
+	var expenses []Transaction
+	for _, account := range lookback {
+		if !included[account.ID] {
+			continue
+		}
+		start, end := window(account)
+		for _, tx := range account.Transactions {
+			txTime := transactionTime(tx)
+			if tx.Amount >= 0 || tx.IsTransfer || txTime.Before(start) || txTime.After(end) {
+				continue
+			}
+			if net, ok := netExpense(tx); ok {
+				expenses = append(expenses, net)
+			}
+		}
+	}
+
+	applyProviderCategories(expenses, filterConfig)
+	if settings.LLMCategorize {
+		applyLLMCategories(expenses, loadLLMCategories(settings.CacheDir), filterConfig)
+	}
+	expenses, _ = filterTransactions(expenses, filterConfig)
+	return expenses
+}
@@ -1,7 +1,10 @@
 package main
 
 import (
+	"errors"
 	"fmt"
+	"io"
+	"os"
 	"strconv"
 	"strings"
 	"time"
@@ -15,15 +18,23 @@ type RunConfig struct {
 	Notifications        []string
 	DisableNotifications bool
 	Verbose              bool
-	DateRange            string
+	DateRange            string // Empty uses the summary cadence's date range
+	Cadence              string // Empty uses SUMMARY_CADENCE
+	Profile              string // Summary profile from the filter config (optional)
 	StartDate            string
 	EndDate              string
 	EnvFile              string
+	ConfigFile           string
 	Version              string
 	MaxRetries           int
 	RetryDelay           int
 	BillingDay           int
 	AllAccounts          bool
+	IncludeArchived      bool
+	Stream               bool
+	ForceLLM             bool
+	DryRun               bool
+	Force                bool // Run even if another run holds the run lock
 }
 
 func main() {
@@ -38,6 +49,7 @@ The tool supports multiple notification channels and can analyze transactions fo
 time periods, providing detailed breakdowns of your spending habits.
 
 By default, only credit card accounts are analyzed. Use --all-accounts to include all account types.
+Running without a subcommand is equivalent to "finance_tracker summarize".
 
 Version: %s
 
@@ -48,57 +60,174 @@ Example usage:
   finance_tracker --date-range last_month     # Analyze only previous billing cycle
   finance_tracker --all-accounts              # Include all account types (not just credit cards)
   finance_tracker --notifications ntfy        # Send notifications via ntfy
+  finance_tracker --notifications gotify      # Send notifications via Gotify
   finance_tracker --max-retries 5             # Set maximum number of retries for LLM calls
-  finance_tracker --retry-delay 2             # Set initial retry delay in seconds`, GetVersion()),
+  finance_tracker --retry-delay 2             # Set initial retry delay in seconds
+  finance_tracker --stream=false              # Print the AI summary only once it is complete
+  finance_tracker --force-llm                 # Ignore the cached summary and call the LLM again
+  finance_tracker --dry-run                   # Render notifications locally instead of sending them
+  finance_tracker --profile family            # Run a summary profile from the filter config
+  finance_tracker --cadence weekly            # Short recap of the last 7 days (e.g. from a Friday cron job)
+  finance_tracker accounts                    # List account balances without AI analysis
+  finance_tracker transactions                # List transactions for the period
+  finance_tracker export --anonymize          # Export a shareable, anonymized dataset for bug reports
+  finance_tracker cache clear                 # Remove cached AI summaries
+  finance_tracker history                     # Show recent runs with status and duration
+  finance_tracker notify test --channel email # Send a test message to verify a notification channel
+  finance_tracker notify preview              # Render the latest cached summary with the current templates
+  finance_tracker config init                 # Write a starter config file
+  finance_tracker status --accounts Visa      # One-line balance summary for status bars
+  finance_tracker doctor                      # Check settings and connectivity to every service
+  finance_tracker ynab push --all-accounts    # Push transactions into a YNAB budget
+  finance_tracker firefly sync --all-accounts # Mirror accounts and transactions into Firefly III
+  finance_tracker goals                       # Show progress toward savings goals
+  finance_tracker forecast                    # Project end-of-month balances
+  finance_tracker trends --category Dining    # Monthly spending with a moving average
+  finance_tracker flows --json                # Income → category → merchant flows for Sankey diagrams
+  finance_tracker tax-report --year 2025      # Year's transactions by tax line as CSV
+  finance_tracker orgs                        # Institutions with their accounts, last sync and errors
+  finance_tracker networth                    # Net worth across synced and manual accounts
+  finance_tracker reconcile                   # Flag balances that don't match their transactions
+  finance_tracker ask "spent on dining?"      # Ask a question about your transactions
+  finance_tracker digest                      # Send only the transactions posted since the last digest
+  finance_tracker backfill --months 36        # Fetch years of history month by month into backfill.json
+  finance_tracker feed summaries -o feed.xml  # Write an Atom feed of summaries for feed readers
+  finance_tracker backup -o state.json        # Archive local state (restore with "restore state.json")`, GetVersion()),
+		PersistentPreRunE: func(cmd *cobra.Command, args []string) error {
+			// LOG_FORMAT is applied by loadSettings, once the .env and config files are read
+			if !cmd.Flags().Changed("log-format") {
+				return nil
+			}
+			format, _ := cmd.Flags().GetString("log-format")
+			if format != "console" && format != "json" {
+				return fmt.Errorf("invalid log format %q (supported: console, json)", format)
+			}
+			logFormat = format
+			logFormatFlag = true
+			return nil
+		},
 		RunE: func(cmd *cobra.Command, args []string) error {
-			notifications, _ := cmd.Flags().GetStringSlice("notifications")
-			disableNotifications, _ := cmd.Flags().GetBool("disable-notifications")
-			verbose, _ := cmd.Flags().GetBool("verbose")
-			dateRange, _ := cmd.Flags().GetString("date-range")
-			startDate, _ := cmd.Flags().GetString("start-date")
-			endDate, _ := cmd.Flags().GetString("end-date")
-			env_file, _ := cmd.Flags().GetString("env-file")
-			maxRetries, _ := cmd.Flags().GetInt("max-retries")
-			retryDelay, _ := cmd.Flags().GetInt("retry-delay")
-			billingDay, _ := cmd.Flags().GetInt("billing-day")
-			allAccounts, _ := cmd.Flags().GetBool("all-accounts")
-
-			return run(RunConfig{
-				Notifications:        notifications,
-				DisableNotifications: disableNotifications,
-				Verbose:              verbose,
-				DateRange:            dateRange,
-				StartDate:            startDate,
-				EndDate:              endDate,
-				EnvFile:              env_file,
-				Version:              GetVersion(),
-				MaxRetries:           maxRetries,
-				RetryDelay:           retryDelay,
-				BillingDay:           billingDay,
-				AllAccounts:          allAccounts,
-			})
+			return run(runConfigFromFlags(cmd))
 		},
 	}
 
-	rootCmd.Flags().StringSliceP("notifications", "n", []string{"email", "ntfy"}, "Notification types to send")
-	rootCmd.Flags().Bool("disable-notifications", false, "Disable all notifications")
-	rootCmd.Flags().Bool("verbose", false, "Enable verbose logging")
-	rootCmd.Flags().String("date-range", string(DateRangeTypeCurrentAndLastMonth), "Date range type (default: 3 billing cycles)")
-	rootCmd.Flags().String("start-date", "", "Start date for custom range (YYYY-MM-DD)")
-	rootCmd.Flags().String("end-date", "", "End date for custom range (YYYY-MM-DD)")
-	rootCmd.Flags().String("env-file", ".env", "Path to environment file")
+	rootCmd.PersistentFlags().Bool("verbose", false, "Enable verbose logging")
+	rootCmd.PersistentFlags().String("log-format", "console", "Log output format: console or json (overrides LOG_FORMAT)")
+	rootCmd.PersistentFlags().String("env-file", ".env", "Path to environment file")
+	rootCmd.PersistentFlags().String("config", "", "Path to config file (default: $XDG_CONFIG_HOME/finance_tracker/config.yaml)")
 	rootCmd.Flags().Bool("version", false, "Show version information")
-	rootCmd.Flags().Int("max-retries", 5, "Maximum number of retries for LLM calls")
-	rootCmd.Flags().Int("retry-delay", 2, "Initial retry delay in seconds")
-	rootCmd.Flags().Int("billing-day", 15, "Day of the month for the billing cycle start (1-28)")
-	rootCmd.Flags().Bool("all-accounts", false, "Include all account types (default: credit cards only)")
+	addRunFlags(rootCmd)
 	rootCmd.SetVersionTemplate(GetVersion() + "\n")
 
+	rootCmd.AddCommand(newSummarizeCommand())
+	rootCmd.AddCommand(newAccountsCommand())
+	rootCmd.AddCommand(newTransactionsCommand())
+	rootCmd.AddCommand(newExportCommand())
+	rootCmd.AddCommand(newCacheCommand())
+	rootCmd.AddCommand(newHistoryCommand())
+	rootCmd.AddCommand(newNotifyCommand())
+	rootCmd.AddCommand(newConfigCommand())
+	rootCmd.AddCommand(newStatusCommand())
+	rootCmd.AddCommand(newDoctorCommand())
+	rootCmd.AddCommand(newYNABCommand())
+	rootCmd.AddCommand(newFireflyCommand())
+	rootCmd.AddCommand(newGoalsCommand())
+	rootCmd.AddCommand(newForecastCommand())
+	rootCmd.AddCommand(newTrendsCommand())
+	rootCmd.AddCommand(newFlowsCommand())
+	rootCmd.AddCommand(newTaxReportCommand())
+	rootCmd.AddCommand(newOrgsCommand())
+	rootCmd.AddCommand(newNetWorthCommand())
+	rootCmd.AddCommand(newReconcileCommand())
+	rootCmd.AddCommand(newAskCommand())
+	rootCmd.AddCommand(newDigestCommand())
+	rootCmd.AddCommand(newBackfillCommand())
+	rootCmd.AddCommand(newFeedCommand())
+	rootCmd.AddCommand(newBackupCommand())
+	rootCmd.AddCommand(newRestoreCommand())
+
 	if err := rootCmd.Execute(); err != nil {
 		log.Fatal().Err(err).Msg("Error executing root command")
 	}
 }
 
+// addPeriodFlags registers the flags that select the analyzed period and accounts
+func addPeriodFlags(cmd *cobra.Command) {
+	cmd.Flags().String("date-range", string(DateRangeTypeCurrentAndLastMonth), "Date range type (default: 3 billing cycles)")
+	cmd.Flags().String("start-date", "", "Start date for custom range (YYYY-MM-DD)")
+	cmd.Flags().String("end-date", "", "End date for custom range (YYYY-MM-DD)")
+	cmd.Flags().Int("billing-day", 0, "Day of the month for the billing cycle start (1-28, default: BILLING_CYCLE_DAY or 15)")
+	cmd.Flags().Bool("all-accounts", false, "Include all account types (default: credit cards only)")
+	cmd.Flags().Bool("include-archived", false, "Include archived accounts")
+}
+
+// addRunFlags registers the flags used by the summarize flow
+func addRunFlags(cmd *cobra.Command) {
+	addPeriodFlags(cmd)
+	cmd.Flags().StringSliceP("notifications", "n", []string{"email", "ntfy"}, "Notification types to send (email, ntfy, gotify)")
+	cmd.Flags().Bool("disable-notifications", false, "Disable all notifications")
+	cmd.Flags().Int("max-retries", 5, "Maximum number of retries for LLM calls")
+	cmd.Flags().Int("retry-delay", 2, "Initial retry delay in seconds")
+	cmd.Flags().Bool("stream", true, "Stream the AI summary to the terminal as it is generated")
+	cmd.Flags().Bool("force-llm", false, "Regenerate the AI summary even if transactions are unchanged since the last run")
+	cmd.Flags().Bool("dry-run", false, "Render all notifications locally without sending them")
+	cmd.Flags().Bool("force", false, "Run even if another run is in progress")
+	cmd.Flags().String("cadence", "", "Summary cadence: daily, weekly or monthly (default: SUMMARY_CADENCE or monthly); sets the default date range and summary length")
+	cmd.Flags().String("profile", "", "Summary profile from the filter config's summaries section (accounts, cadence, channels and recipients)")
+}
+
+// runConfigFromFlags builds a RunConfig from the command's flags. Flags not registered on
+// the command are left at their zero value.
+func runConfigFromFlags(cmd *cobra.Command) RunConfig {
+	notifications, _ := cmd.Flags().GetStringSlice("notifications")
+	disableNotifications, _ := cmd.Flags().GetBool("disable-notifications")
+	verbose, _ := cmd.Flags().GetBool("verbose")
+	dateRange, _ := cmd.Flags().GetString("date-range")
+	startDate, _ := cmd.Flags().GetString("start-date")
+	endDate, _ := cmd.Flags().GetString("end-date")
+	env_file, _ := cmd.Flags().GetString("env-file")
+	configFile, _ := cmd.Flags().GetString("config")
+	maxRetries, _ := cmd.Flags().GetInt("max-retries")
+	retryDelay, _ := cmd.Flags().GetInt("retry-delay")
+	billingDay, _ := cmd.Flags().GetInt("billing-day")
+	allAccounts, _ := cmd.Flags().GetBool("all-accounts")
+	includeArchived, _ := cmd.Flags().GetBool("include-archived")
+	stream, _ := cmd.Flags().GetBool("stream")
+	forceLLM, _ := cmd.Flags().GetBool("force-llm")
+	dryRun, _ := cmd.Flags().GetBool("dry-run")
+	force, _ := cmd.Flags().GetBool("force")
+	cadence, _ := cmd.Flags().GetString("cadence")
+	profile, _ := cmd.Flags().GetString("profile")
+
+	// Commands with a cadence derive the date range from it unless one is given explicitly
+	if cmd.Flags().Lookup("cadence") != nil && !cmd.Flags().Changed("date-range") {
+		dateRange = ""
+	}
+
+	return RunConfig{
+		Notifications:        notifications,
+		DisableNotifications: disableNotifications,
+		Verbose:              verbose,
+		DateRange:            dateRange,
+		Cadence:              cadence,
+		Profile:              profile,
+		StartDate:            startDate,
+		EndDate:              endDate,
+		EnvFile:              env_file,
+		ConfigFile:           configFile,
+		Version:              GetVersion(),
+		MaxRetries:           maxRetries,
+		RetryDelay:           retryDelay,
+		BillingDay:           billingDay,
+		AllAccounts:          allAccounts,
+		IncludeArchived:      includeArchived,
+		Stream:               stream,
+		ForceLLM:             forceLLM,
+		DryRun:               dryRun,
+		Force:                force,
+	}
+}
+
 // isCreditCard determines if an account is a credit card based on available data
 func isCreditCard(account Account) bool {
 	// First, check if the "extra" field contains type information
@@ -141,6 +270,8 @@ func matchesRule(description string, rule FilterRule) bool {
 		return strings.HasPrefix(descLower, patternLower)
 	case MatchTypeSuffix:
 		return strings.HasSuffix(descLower, patternLower)
+	case MatchTypeExact:
+		return descLower == patternLower
 	default:
 		log.Warn().
 			Str("match_type", string(rule.MatchType)).
@@ -149,6 +280,25 @@ func matchesRule(description string, rule FilterRule) bool {
 	}
 }
 
+// ruleFieldValue returns the transaction field a filter rule is matched against
+func ruleFieldValue(tx Transaction, rule FilterRule) string {
+	switch rule.Field {
+	case FilterFieldMerchant:
+		return merchantName(tx)
+	case FilterFieldCategory:
+		return tx.Category
+	case FilterFieldNote:
+		return tx.Note
+	case FilterFieldDescription, "":
+		return tx.Description
+	default:
+		log.Warn().
+			Str("field", string(rule.Field)).
+			Msg("Unknown filter field, matching the description")
+		return tx.Description
+	}
+}
+
 // filterTransactions filters out transactions based on the provided filter config
 func filterTransactions(transactions []Transaction, filterConfig *FilterConfig) ([]Transaction, FilterResult) {
 	if filterConfig == nil || len(filterConfig.ExcludedTransactions) == 0 {
@@ -162,15 +312,17 @@ func filterTransactions(transactions []Transaction, filterConfig *FilterConfig)
 
 	var included []Transaction
 	var filtered []Transaction
-	var totalAmount Balance = 0
+	var totalAmount Money
 
 	for _, tx := range transactions {
 		shouldFilter := false
 		for _, rule := range filterConfig.ExcludedTransactions {
-			if matchesRule(tx.Description, rule) {
+			value := ruleFieldValue(tx, rule)
+			if value != "" && matchesRule(value, rule) {
 				shouldFilter = true
 				log.Debug().
 					Str("description", tx.Description).
+					Str("field", string(rule.Field)).
 					Str("pattern", rule.Pattern).
 					Str("match_type", string(rule.MatchType)).
 					Float64("amount", float64(tx.Amount)).
@@ -181,7 +333,7 @@ func filterTransactions(transactions []Transaction, filterConfig *FilterConfig)
 
 		if shouldFilter {
 			filtered = append(filtered, tx)
-			totalAmount += tx.Amount
+			totalAmount += toMoney(tx.Amount)
 		} else {
 			included = append(included, tx)
 		}
@@ -190,13 +342,13 @@ func filterTransactions(transactions []Transaction, filterConfig *FilterConfig)
 	result := FilterResult{
 		FilteredTransactions: filtered,
 		TotalFiltered:        len(filtered),
-		TotalAmount:          totalAmount,
+		TotalAmount:          Balance(totalAmount.Float64()),
 	}
 
 	if len(filtered) > 0 {
 		log.Info().
 			Int("filtered_count", len(filtered)).
-			Float64("total_amount", float64(totalAmount)).
+			Float64("total_amount", totalAmount.Float64()).
 			Int("remaining_count", len(included)).
 			Msg("🚫 Filtered transactions based on rules")
 	}
@@ -204,56 +356,70 @@ func filterTransactions(transactions []Transaction, filterConfig *FilterConfig)
 	return included, result
 }
 
-// run is the main function that runs the finance tracker
-func run(config RunConfig) error {
-	// Initialize logger
-	initLogger(config.Verbose)
-
-	log.Info().Msg("🔧 Starting " + GetVersion())
-
-	log.Debug().Interface("config", config).Msg("Starting finance tracker")
-
-	log.Info().Msg("🔧 Loading configuration...")
-	settings, err := NewSettings(config.EnvFile)
+// loadSettings loads settings from the environment and applies the global configuration derived from them
+func loadSettings(envFile string, configFile string) (*Settings, error) {
+	settings, err := NewSettings(envFile, configFile)
 	if err != nil {
-		return fmt.Errorf("error loading settings: %w", err)
+		return nil, fmt.Errorf("error loading settings: %w", err)
 	}
+	configureLogFormat(settings.LogFormat)
 
 	// Log settings in a structured way
 	log.Debug().Interface("settings", settings).Msg("Configuration loaded successfully")
 
-	// Load filter config if configured
-	var filterConfig *FilterConfig
-	if settings.FilterConfigPath != nil {
-		log.Info().Str("config_path", *settings.FilterConfigPath).Msg("📋 Loading filter configuration...")
-		fc, err := LoadFilterConfig(*settings.FilterConfigPath)
-		if err != nil {
-			log.Warn().
-				Err(err).
-				Str("config_path", *settings.FilterConfigPath).
-				Msg("Failed to load filter config, continuing without filtering")
-		} else {
-			filterConfig = fc
-		}
+	if err := configureMoney(settings.Currency, settings.RoundingMode); err != nil {
+		return nil, fmt.Errorf("error configuring money: %w", err)
+	}
+
+	if err := configureTimezone(settings.Timezone); err != nil {
+		return nil, fmt.Errorf("error configuring timezone: %w", err)
+	}
+
+	if err := configureLocale(settings.Locale); err != nil {
+		return nil, fmt.Errorf("error configuring locale: %w", err)
+	}
+
+	return settings, nil
+}
+
+// loadFilterConfig loads the filter config if configured, returning nil if it is unset or can't be loaded
+func loadFilterConfig(settings *Settings) *FilterConfig {
+	if settings.FilterConfigPath == nil {
+		return nil
 	}
 
+	log.Info().Str("config_path", *settings.FilterConfigPath).Msg("📋 Loading filter configuration...")
+	filterConfig, err := LoadFilterConfig(*settings.FilterConfigPath)
+	if err != nil {
+		log.Warn().
+			Err(err).
+			Str("config_path", *settings.FilterConfigPath).
+			Msg("Failed to load filter config, continuing without filtering")
+		return nil
+	}
+
+	return filterConfig
+}
+
+// resolvePeriod parses the date range options and returns the validated period to analyze
+func resolvePeriod(config RunConfig) (DateRangeType, time.Time, time.Time, error) {
 	// Parse date range
 	dateRangeType := DateRangeType(config.DateRange)
 
 	// Parse custom dates if provided
 	var parsedStartDate, parsedEndDate *time.Time
 	if config.StartDate != "" {
-		parsed, err := time.Parse("2006-01-02", config.StartDate)
+		parsed, err := time.ParseInLocation("2006-01-02", config.StartDate, periodLocation)
 		if err != nil {
-			return fmt.Errorf("error parsing start date: %w", err)
+			return "", time.Time{}, time.Time{}, fmt.Errorf("error parsing start date: %w", err)
 		}
 		parsedStartDate = &parsed
 		log.Debug().Str("start_date", parsed.Format("2006-01-02")).Msg("Parsed start date")
 	}
 	if config.EndDate != "" {
-		parsed, err := time.Parse("2006-01-02", config.EndDate)
+		parsed, err := time.ParseInLocation("2006-01-02", config.EndDate, periodLocation)
 		if err != nil {
-			return fmt.Errorf("error parsing end date: %w", err)
+			return "", time.Time{}, time.Time{}, fmt.Errorf("error parsing end date: %w", err)
 		}
 		parsedEndDate = &parsed
 		log.Debug().Str("end_date", parsed.Format("2006-01-02")).Msg("Parsed end date")
@@ -262,7 +428,7 @@ func run(config RunConfig) error {
 	// Calculate date range
 	billingStart, billingEnd, err := calculateDateRange(dateRangeType, parsedStartDate, parsedEndDate, config.BillingDay)
 	if err != nil {
-		return fmt.Errorf("error calculating date range: %w", err)
+		return "", time.Time{}, time.Time{}, fmt.Errorf("error calculating date range: %w", err)
 	}
 	log.Debug().
 		Str("start", billingStart.Format("2006-01-02")).
@@ -271,65 +437,300 @@ func run(config RunConfig) error {
 
 	// Validate billing period
 	if err := validateBillingPeriod(billingStart, billingEnd); err != nil {
-		return fmt.Errorf("error validating billing period: %w", err)
+		return "", time.Time{}, time.Time{}, fmt.Errorf("error validating billing period: %w", err)
 	}
 	log.Debug().Msg("Billing period validated successfully")
 
-	// Fetch transactions
-	log.Info().Msg("📊 Fetching transactions...")
-	accounts, apiErrors, err := getTransactionsForPeriod(settings, billingStart, billingEnd)
+	return dateRangeType, billingStart, billingEnd, nil
+}
+
+// fetchAccounts fetches accounts and transactions for the period, sending any API errors
+// as warnings through the configured channels unless notifications are disabled. The API
+// errors are returned so callers can report accounts that failed to sync.
+func fetchAccounts(settings *Settings, config RunConfig, start, end time.Time) ([]Account, []string, error) {
+	accounts, apiErrors, err := getTransactionsForPeriod(settings, start, end)
 	if err != nil {
-		return fmt.Errorf("error fetching transactions: %w", err)
+		return nil, nil, fmt.Errorf("error fetching transactions: %w", err)
 	}
 	log.Debug().Int("account_count", len(accounts)).Msg("Fetched accounts")
 
-	// Handle API errors by sending warnings through configured channels
-	if len(apiErrors) > 0 && !config.DisableNotifications {
+	// Fold replaced accounts (e.g. a reissued card) into their replacements, then drop paused
+	// accounts, and archived accounts unless requested
+	state := loadAccountState(settings.CacheDir)
+	accounts = mergeReplacedAccounts(accounts, state)
+	accounts = skipInactiveAccounts(accounts, state, config.IncludeArchived)
+
+	// Apply organization renames and merges (e.g. a bank that reappeared under a new ID)
+	applyOrganizationOverrides(accounts, loadOrganizationOverrides(settings.CacheDir))
+
+	// Handle API errors (e.g. a connection that needs attention) by sending warnings through the
+	// configured channels. Each error is only reported once while the bridge keeps returning it.
+	if len(apiErrors) > 0 {
 		log.Warn().Strs("api_errors", apiErrors).Msg("Received API errors during transaction fetch")
-		for _, apiErr := range apiErrors {
+	}
+	if !config.DisableNotifications {
+		for _, apiErr := range state.NewConnectionErrors(apiErrors) {
 			warnMsg := fmt.Sprintf("API Error: %s", apiErr)
-			_, notifyErr := sendNotification(settings, warnMsg, nil, "warning", config.Notifications)
-			if notifyErr != nil {
+			if config.DryRun {
+				if err := renderNotification(settings, warnMsg, nil, nil, "warning", config.Notifications); err != nil {
+					log.Error().Err(err).Str("original_api_error", apiErr).Msg("Failed to render API error warning notification")
+				}
+				continue
+			}
+			if _, err := sendNotification(settings, warnMsg, nil, nil, "warning", config.Notifications); errors.Is(err, errNotificationDeferred) {
+				continue
+			} else if err != nil {
 				// Log the notification error but don't stop the main process
-				log.Error().Err(notifyErr).Str("original_api_error", apiErr).Msg("Failed to send API error warning notification")
+				log.Error().Err(err).Str("original_api_error", apiErr).Msg("Failed to send API error warning notification")
+				continue
 			}
+			state.MarkConnectionErrorReported(apiErr, time.Now())
 		}
-		log.Debug().Msg("Sent warning notifications for API errors")
-	}
 
-	// Filter accounts based on account type (credit cards only by default)
-	if !config.AllAccounts {
-		var creditCardAccounts []Account
-		for _, account := range accounts {
-			if isCreditCard(account) {
-				creditCardAccounts = append(creditCardAccounts, account)
-				log.Debug().
-					Str("account_id", account.ID).
-					Str("account_name", account.Name).
-					Msg("Included credit card account")
-			} else {
-				log.Debug().
-					Str("account_id", account.ID).
-					Str("account_name", account.Name).
-					Msg("Filtered out non-credit card account")
+		if !config.DryRun && state != nil && (len(apiErrors) > 0 || len(state.ConnectionErrors) > 0) {
+			state.ClearResolvedConnectionErrors(apiErrors)
+			if err := state.Save(); err != nil {
+				log.Warn().Err(err).Msg("Failed to save reported API errors")
 			}
 		}
+	}
+
+	return accounts, apiErrors, nil
+}
 
-		// Warn if no credit card accounts found
-		if len(creditCardAccounts) == 0 {
-			log.Warn().
-				Int("total_accounts", len(accounts)).
-				Msg("No credit card accounts found. Use --all-accounts to include all account types.")
-			return fmt.Errorf("no credit card accounts found (use --all-accounts to include all account types)")
+// formatSyncIssues formats per-account sync errors as a markdown section for notifications
+func formatSyncIssues(syncIssues []string) string {
+	if len(syncIssues) == 0 {
+		return ""
+	}
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "## %s\n\n", currentLocale.SyncIssuesHeading)
+	b.WriteString(currentLocale.SyncIssuesIntro + "\n\n")
+	for _, issue := range syncIssues {
+		fmt.Fprintf(&b, "- %s\n", issue)
+	}
+	return b.String()
+}
+
+// selectAccounts filters accounts by type: credit cards only unless allAccounts is set
+func selectAccounts(accounts []Account, allAccounts bool) ([]Account, error) {
+	if allAccounts {
+		log.Debug().Msg("Using all accounts (--all-accounts flag set)")
+		return accounts, nil
+	}
+
+	var creditCardAccounts []Account
+	for _, account := range accounts {
+		if isCreditCard(account) {
+			creditCardAccounts = append(creditCardAccounts, account)
+			log.Debug().
+				Str("account_id", account.ID).
+				Str("account_name", account.Name).
+				Msg("Included credit card account")
+		} else {
+			log.Debug().
+				Str("account_id", account.ID).
+				Str("account_name", account.Name).
+				Msg("Filtered out non-credit card account")
 		}
+	}
 
-		log.Info().
-			Int("credit_card_accounts", len(creditCardAccounts)).
+	// Warn if no credit card accounts found
+	if len(creditCardAccounts) == 0 {
+		log.Warn().
 			Int("total_accounts", len(accounts)).
-			Msg("💳 Filtering to credit card accounts only")
-		accounts = creditCardAccounts
+			Msg("No credit card accounts found. Use --all-accounts to include all account types.")
+		return nil, fmt.Errorf("no credit card accounts found (use --all-accounts to include all account types)")
+	}
+
+	log.Info().
+		Int("credit_card_accounts", len(creditCardAccounts)).
+		Int("total_accounts", len(accounts)).
+		Msg("💳 Filtering to credit card accounts only")
+	return creditCardAccounts, nil
+}
+
+// run is the main function that runs the finance tracker
+func run(config RunConfig) (err error) {
+	// Initialize logger
+	initLogger(config.Verbose)
+
+	log.Info().Msg("🔧 Starting " + GetVersion())
+
+	log.Debug().Interface("config", config).Msg("Starting finance tracker")
+
+	log.Info().Msg("🔧 Loading configuration...")
+	startedAt := time.Now()
+	settings, err := loadSettings(config.EnvFile, config.ConfigFile)
+	if err != nil {
+		return err
+	}
+
+	// Record the outcome of this run in the run history
+	var syncIssues []string
+	defer func() {
+		recordRun(settings.CacheDir, "summarize", startedAt, err, syncIssues)
+	}()
+
+	// Only one summary runs at a time, for any profile, so a manual run can't race a scheduled one
+	// and profiles don't race on the shared state files
+	unlock, err := acquireRunLock(settings.CacheDir, "summarize", config.Profile, config.Force)
+	if err != nil {
+		return err
+	}
+	defer unlock()
+	// Events share one event bus connection, opened by the first one
+	defer func() { settings.EventBus.Close() }()
+
+	// Collect the run's warnings and send them as one message per channel at the end
+	if settings.BatchWindow != nil && !config.DryRun && !config.DisableNotifications {
+		settings.BatchWarnings = true
+		defer func() {
+			settings.BatchWarnings = false
+			if err := flushWarnings(settings, *settings.BatchWindow, time.Now()); err != nil {
+				log.Error().Err(err).Msg("Failed to send batched warnings")
+			}
+		}()
+	}
+
+	filterConfig := loadFilterConfig(settings)
+
+	// Apply the summary profile's cadence, channels and recipients
+	var profile *SummaryProfile
+	if config.Profile != "" {
+		selected, err := findSummaryProfile(filterConfig, config.Profile)
+		if err != nil {
+			return err
+		}
+		applySummaryProfile(selected, settings, &config)
+		profile = &selected
+	}
+
+	if config.BillingDay == 0 {
+		config.BillingDay = settings.BillingCycleDay
+	}
+	if config.DateRange == "" {
+		cadence := SummaryCadence(config.Cadence)
+		if cadence == "" {
+			cadence = settings.SummaryCadence
+		}
+		dateRange, err := cadence.DateRange()
+		if err != nil {
+			return err
+		}
+		config.DateRange = string(dateRange)
+	}
+
+	dateRangeType, billingStart, billingEnd, err := resolvePeriod(config)
+	if err != nil {
+		return err
+	}
+
+	// Fetch transactions, covering accounts with their own billing cycle and, in the same request,
+	// the earlier transactions the comparison, subscription check and interest year to date need
+	log.Info().Msg("📊 Fetching transactions...")
+	fetchStart, fetchEnd := widenForAccountBillingDays(dateRangeType, billingStart, billingEnd, filterConfig)
+	accounts, syncIssues, err := fetchAccounts(settings, config, lookbackStart(settings, dateRangeType, fetchStart, fetchEnd), fetchEnd)
+	if err != nil {
+		return err
+	}
+	accounts, lookback := splitLookback(accounts, fetchStart)
+	lookback = prepareLookback(settings, lookback, filterConfig)
+
+	if err := applyAccountBillingCycles(accounts, dateRangeType, billingStart, billingEnd, filterConfig); err != nil {
+		return err
+	}
+
+	// Drop accounts excluded from syncing by configuration
+	accounts = excludeAccounts(accounts, filterConfig, AccountScopeSync)
+
+	// Record changes the banks made to transactions seen in earlier runs
+	var unseenTransactions map[string]bool
+	if !config.DryRun {
+		revisions := loadTransactionRevisions(settings.CacheDir)
+		unseenTransactions = revisions.Unseen(accounts)
+		revisions.Record(accounts, fetchStart, time.Now().UTC())
+		if revisions != nil {
+			if err := revisions.Save(); err != nil {
+				log.Warn().Err(err).Msg("Failed to save transaction revisions")
+			}
+		}
+	}
+
+	// Replace raw descriptions with clean merchant names for display, and apply user annotations
+	normalizeMerchants(accounts, filterConfig)
+	transactionNotes := loadTransactionNotes(settings.CacheDir)
+	if learned := applyTransactionNotes(accounts, transactionNotes); learned > 0 {
+		log.Info().Int("rules", learned).Msg("🧠 Learned merchant categories from your corrections")
+		if !config.DryRun {
+			if err := transactionNotes.Save(); err != nil {
+				log.Warn().Err(err).Msg("Failed to save learned merchant categories")
+			}
+		}
+	}
+	applyAccountDisplaySettings(accounts, filterConfig)
+	applyCreditLimits(accounts, filterConfig)
+
+	// Let external automation know about new transactions and balance changes
+	if (len(settings.WebhookURLs) > 0 || settings.EventBusURL != "") && !config.DryRun {
+		emitAccountEvents(settings, accounts, unseenTransactions)
+	}
+
+	// Detect transfers between accounts (e.g. credit card payments from checking) before non-credit
+	// card accounts are filtered out, so neither side is counted as spending
+	if transfers := markTransfers(accounts); transfers > 0 {
+		log.Info().Int("transfers", transfers).Msg("🔁 Detected internal transfers, excluding them from spending")
+	}
+
+	// Link refunds to their purchases so returned purchases are netted out of spending. Links are
+	// saved so the purchase's period is netted too when it is analyzed again.
+	if refunds := detectRefunds(accounts, transactionNotes); refunds > 0 {
+		log.Info().Int("refunds", refunds).Msg("↩️ Linked refunds to their purchases")
+		if !config.DryRun {
+			if err := transactionNotes.Save(); err != nil {
+				log.Warn().Err(err).Msg("Failed to save refund links")
+			}
+		}
+	}
+	applyRefundLinks(accounts, transactionNotes)
+
+	// Warn about charges that look like double swipes or duplicate billing
+	if err := checkDuplicateCharges(settings, config, accounts, filterConfig); err != nil {
+		log.Error().Err(err).Msg("Failed to check for duplicate charges")
+	}
+
+	// Warn about accounts whose data hasn't been refreshed recently
+	if err := checkFreshness(settings, config, accounts, filterConfig); err != nil {
+		log.Error().Err(err).Msg("Failed to check account freshness")
+	}
+
+	// Warn about credit accounts using too much of their limit
+	if err := checkCreditUtilization(settings, config, accounts, filterConfig); err != nil {
+		log.Error().Err(err).Msg("Failed to check credit utilization")
+	}
+
+	// Detect interest earned on savings before non-credit card accounts are filtered out
+	interestSummaries := summarizeInterest(accounts, filterConfig)
+	if settings.InterestYearToDate {
+		addInterestYearToDate(interestSummaries, lookback, billingEnd)
+	}
+
+	// Total income and spending across all accounts for the savings rate, before non-credit card
+	// accounts (where paychecks land) are filtered out
+	incomeSummary := summarizeIncome(excludeAccounts(accounts, filterConfig, AccountScopeSummary))
+
+	// Track savings goals against their linked accounts, which usually aren't credit cards
+	goalProgress := computeGoalProgress(accounts, filterConfig, billingStart, billingEnd, time.Now().In(periodLocation))
+
+	// Filter accounts to the profile's, or based on account type (credit cards only by default)
+	if profile != nil && len(profile.Accounts) > 0 {
+		accounts, err = selectProfileAccounts(accounts, *profile)
 	} else {
-		log.Debug().Msg("Using all accounts (--all-accounts flag set)")
+		accounts, err = selectAccounts(accounts, config.AllAccounts)
+	}
+	if err != nil {
+		return err
 	}
 
 	if len(accounts) == 0 {
@@ -347,19 +748,39 @@ func run(config RunConfig) error {
 			Msg("└")
 	}
 
-	// Collect all transactions
+	// Collect all transactions, skipping accounts excluded from the summary (their balances are still listed)
 	var allTransactions []Transaction
-	for _, account := range accounts {
+	for _, account := range excludeAccounts(accounts, filterConfig, AccountScopeSummary) {
 		allTransactions = append(allTransactions, account.Transactions...)
 	}
 	log.Debug().Int("transaction_count", len(allTransactions)).Msg("Collected total transactions")
 
-	// Filter out positive transactions (keep only expenses)
+	// Transactions listed in notifications, skipping accounts excluded from notifications
+	notificationAccountIDs := make(map[string]bool)
+	for _, account := range excludeAccounts(accounts, filterConfig, AccountScopeNotifications) {
+		notificationAccountIDs[account.ID] = true
+	}
+	transactionAccounts := make(map[string]string)
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			transactionAccounts[tx.ID] = account.ID
+		}
+	}
+
+	// Filter out positive transactions and transfers (keep only expenses, net of linked refunds)
 	var expenses []Transaction
 	positiveTxnCount := 0
+	transferTxnCount := 0
+	refundedTxnCount := 0
 	for _, tx := range allTransactions {
-		if tx.Amount < 0 {
-			expenses = append(expenses, tx)
+		if tx.IsTransfer {
+			transferTxnCount++
+		} else if tx.Amount < 0 {
+			if net, ok := netExpense(tx); ok {
+				expenses = append(expenses, net)
+			} else {
+				refundedTxnCount++
+			}
 		} else {
 			positiveTxnCount++
 		}
@@ -368,9 +789,18 @@ func run(config RunConfig) error {
 	log.Debug().
 		Int("filtered_transaction_count", len(allTransactions)).
 		Int("positive_txns_ignored", positiveTxnCount).
-		Msg("Filtered out positive transactions (e.g., income, payments)")
+		Int("transfer_txns_ignored", transferTxnCount).
+		Int("refunded_txns_ignored", refundedTxnCount).
+		Msg("Filtered out positive transactions (e.g., income, payments) and transfers")
+
+	// Seed categories from rules and provider-assigned categories, then (with LLM_CATEGORIZE) let the
+	// LLM classify the rest, so exclusion rules can match them
+	applyProviderCategories(allTransactions, filterConfig)
+	if settings.LLMCategorize {
+		categorizeWithLLM(settings, allTransactions, filterConfig, config.DryRun)
+	}
 
-	// Apply merchant/description filtering if configured
+	// Apply exclusion rules (description, merchant, category or note) if configured
 	var filterResult FilterResult
 	allTransactions, filterResult = filterTransactions(allTransactions, filterConfig)
 
@@ -378,9 +808,48 @@ func run(config RunConfig) error {
 		return fmt.Errorf("no transactions found")
 	}
 
+	summaryAccounts := excludeAccounts(accounts, filterConfig, AccountScopeSummary)
+	included := make(map[string]bool, len(summaryAccounts))
+	for _, account := range summaryAccounts {
+		included[account.ID] = true
+	}
+
+	// Flag subscriptions that got more expensive or started during the last month of the period,
+	// against SUBSCRIPTION_HISTORY_MONTHS of earlier expenses so monthly ones have earlier charges
+	subscriptionHistory := allTransactions
+	historyStart := billingStart
+	if settings.SubscriptionMonths > 0 {
+		historyStart = billingStart.AddDate(0, -settings.SubscriptionMonths, 0)
+		earlier := lookbackExpenses(settings, lookback, included, filterConfig, func(Account) (time.Time, time.Time) {
+			return historyStart, billingStart.Add(-time.Nanosecond)
+		})
+		subscriptionHistory = append(earlier, allTransactions...)
+	}
+	subscriptionChanges := detectSubscriptionChanges(subscriptionHistory, historyStart, billingEnd.AddDate(0, -1, 0))
+
+	// Compare with the same span of the previous period, each account's own billing cycle if it
+	// has one
+	var comparison *PeriodComparison
+	if previousStart, previousEnd, ok := previousPeriod(dateRangeType, billingStart, billingEnd); ok {
+		previous := lookbackExpenses(settings, lookback, included, filterConfig, func(account Account) (time.Time, time.Time) {
+			start, end, err := accountPeriod(account, dateRangeType, billingStart, billingEnd, filterConfig)
+			if err != nil {
+				return previousStart, previousEnd
+			}
+			accountPreviousStart, accountPreviousEnd, _ := previousPeriod(dateRangeType, start, end)
+			return accountPreviousStart, accountPreviousEnd
+		})
+		periodComparison := comparePeriods(allTransactions, previous, previousStart, previousEnd)
+		comparison = &periodComparison
+	}
+
 	// Process transactions with AI
 	log.Info().Msg("🤖 Analyzing transactions with AI...")
-	prompt := generateAnalysisPrompt(accounts, allTransactions, billingStart, billingEnd, dateRangeType, config.BillingDay, &filterResult)
+	prompt, promptFigures := generateAnalysisPrompt(accounts, allTransactions, billingStart, billingEnd, dateRangeType, config.BillingDay, &filterResult, []string{
+		formatPeriodComparison(comparison),
+		formatIncomeSummary(incomeSummary),
+		formatInterestSummary(interestSummaries),
+	})
 	log.Debug().Str("prompt", prompt).Msg("Generated analysis prompt")
 
 	// Determine if this is complex analysis requiring reasoning
@@ -389,28 +858,149 @@ func run(config RunConfig) error {
 		dateRangeType == DateRangeTypeCurrentYear ||
 		dateRangeType == DateRangeTypeLastYear
 
-	// Get LLM response with retry
-	analysis, err := retryWithBackoff(
-		func() (string, error) {
-			return getLLMResponse(settings, prompt, isComplexAnalysis)
-		},
-		config.MaxRetries,
-		config.RetryDelay,
-		"LLM request",
-	)
+	// Reuse the stored summary if the prompt for this period (and the models) hasn't changed
+	periodKey := fmt.Sprintf("%s:%s", dateRangeType, billingStart.Format("2006-01-02"))
+	if config.Profile != "" {
+		// Profiles summarize different accounts, so they don't share cached summaries
+		periodKey = config.Profile + "/" + periodKey
+	}
+	if locale := Locale(strings.ToLower(string(settings.Locale))); locale != LocaleEnglish {
+		// Summaries are written in the locale's language, so switching LOCALE regenerates them
+		periodKey += "@" + string(locale)
+	}
+	promptHash := hashPrompt(prompt, llmSettings(settings, LLMTaskSummary).OpenRouterModel)
+	cache, err := newCacheStore(settings)
 	if err != nil {
-		return fmt.Errorf("error getting LLM response: %w", err)
+		return fmt.Errorf("error opening summary cache: %w", err)
 	}
 
-	log.Debug().Str("analysis", analysis).Msg("Received AI analysis")
+	analysis, cached := cache.Lookup(periodKey, promptHash)
+	if cached && config.ForceLLM {
+		log.Debug().Str("period", periodKey).Msg("Ignoring cached summary (--force-llm set)")
+		cached = false
+	}
 
-	log.Info().Msg("✨ AI Summary:")
-	log.Info().Msg(analysis)
+	streamed := false
+	if cached {
+		log.Info().
+			Str("period", periodKey).
+			Str("hash", promptHash).
+			Msg("♻️ Prompt unchanged since last analysis, reusing cached summary (use --force-llm to regenerate)")
+	} else {
+		// Stream the summary to the terminal as it is generated, if enabled
+		var streamOutput io.Writer
+		if config.Stream {
+			log.Info().Msg("✨ AI Summary:")
+			streamOutput = os.Stdout
+			streamed = true
+		}
 
-	// Send notifications
-	if !config.DisableNotifications {
+		// Get LLM response with retry
+		analysis, err = retryWithBackoff(
+			func() (string, error) {
+				result, err := getLLMResponse(llmSettings(settings, LLMTaskSummary), prompt, isComplexAnalysis, streamOutput)
+				// Retrying into the terminal would print the partial summary twice, so the retries
+				// run without streaming and the complete summary is printed below instead
+				if errors.Is(err, errStreamInterrupted) {
+					fmt.Fprintln(streamOutput)
+					streamOutput = nil
+					streamed = false
+				}
+				return result, err
+			},
+			config.MaxRetries,
+			config.RetryDelay,
+			"LLM request",
+		)
+		if err != nil {
+			return fmt.Errorf("error getting LLM response: %w", err)
+		}
+
+		log.Debug().Str("analysis", analysis).Msg("Received AI analysis")
+
+		// Make sure the figures in the summary are the ones given in the prompt; a replaced
+		// summary differs from what was streamed, so it is printed again below
+		analytics := computeAnalytics(allTransactions)
+		figures := summaryFigures(promptFigures, comparison, incomeSummary, interestSummaries)
+		if verified, replaced := verifySummary(settings, prompt, analysis, isComplexAnalysis, figures, analytics, billingStart, billingEnd); replaced {
+			analysis = verified
+			streamed = false
+		}
+
+		if err := cache.Store(periodKey, promptHash, analysis); err != nil {
+			log.Warn().Err(err).Msg("Failed to cache AI summary")
+		}
+	}
+
+	if !streamed {
+		log.Info().Msg("✨ AI Summary:")
+		log.Info().Msg(analysis)
+	}
+
+	// Append subscription changes, savings goal progress and accounts that failed to sync to the summary sent out
+	for _, section := range []string{formatSubscriptionChanges(subscriptionChanges), formatGoalsSummary(goalProgress), formatSyncIssues(syncIssues)} {
+		if section != "" {
+			analysis = strings.TrimRight(analysis, "\n") + "\n\n" + section
+		}
+	}
+
+	var notificationTransactions []Transaction
+	for _, tx := range allTransactions {
+		if notificationAccountIDs[transactionAccounts[tx.ID]] {
+			notificationTransactions = append(notificationTransactions, tx)
+		}
+	}
+
+	// Attach the transactions of the accounts in the summary to the email
+	var attachments []EmailAttachment
+	if settings.EmailAttachCSV {
+		var notificationAccounts []Account
+		for _, account := range accounts {
+			if notificationAccountIDs[account.ID] {
+				notificationAccounts = append(notificationAccounts, account)
+			}
+		}
+		attachment, err := transactionsCSVAttachment(billingStart, billingEnd, notificationAccounts)
+		if err != nil {
+			log.Warn().Err(err).Msg("Failed to build the transactions CSV, sending the email without it")
+		} else {
+			attachments = append(attachments, attachment)
+		}
+	}
+
+	// Keep a copy of the summary and transactions independent of the notification channels
+	if settings.ReportArchive != "" && !config.DryRun {
+		if err := archiveReport(settings, billingStart, billingEnd, analysis, accounts); err != nil {
+			log.Error().Err(err).Msg("Failed to archive report")
+		}
+	}
+
+	if !config.DryRun {
+		emitEvent(settings, WebhookEventSummaryGenerated, webhookSummary{
+			Start:   billingStart.Format("2006-01-02"),
+			End:     billingEnd.Format("2006-01-02"),
+			Profile: config.Profile,
+			Summary: analysis,
+		})
+	}
+
+	// Send notifications, titled with the summary's billing period and computed total
+	summaryPeriod := &SummaryPeriod{Start: billingStart, End: billingEnd, Spend: computeAnalytics(allTransactions).Total}
+	if config.DryRun && !config.DisableNotifications {
+		log.Info().Msg("🧪 Dry run: rendering notifications without sending")
+		if err := renderNotification(settings, analysis, notificationTransactions, summaryPeriod, "info", config.Notifications); err != nil {
+			return fmt.Errorf("error rendering notifications: %w", err)
+		}
+	} else if !config.DisableNotifications {
 		log.Debug().Strs("notification_channels", config.Notifications).Msg("Sending notifications")
-		successfulChannels, err := sendNotification(settings, analysis, allTransactions, "info", config.Notifications)
+		successfulChannels, err := sendNotification(settings, analysis, notificationTransactions, summaryPeriod, "info", config.Notifications, attachments...)
+		if errors.Is(err, errNotificationDeferred) {
+			// Recorded as a partial run so `history` shows the summary still has to be delivered;
+			// running the period again resends it from the response cache
+			log.Warn().Msg("Every notification channel is over its rate limit, the summary was not sent")
+			syncIssues = append(syncIssues, "summary not sent: every notification channel is over its rate limit, run the period again to deliver it")
+			return nil
+		}
 		if err != nil {
 			return fmt.Errorf("error sending notifications: %w", err)
 		}
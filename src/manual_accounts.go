@@ -0,0 +1,249 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// manualAccountsFile is the name of the JSON file holding manual accounts inside the cache directory
+const manualAccountsFile = "manual_accounts.json"
+
+// holdingsRevalueInterval is how old holding prices may get before networth fetches them again
+const holdingsRevalueInterval = 24 * time.Hour
+
+// AssetKind classifies a manual account
+type AssetKind string
+
+// Available asset kinds
+const (
+	AssetKindProperty   AssetKind = "property"   // House, land
+	AssetKindVehicle    AssetKind = "vehicle"    // Car, boat
+	AssetKindCash       AssetKind = "cash"       // Cash on hand
+	AssetKindCrypto     AssetKind = "crypto"     // Crypto wallet
+	AssetKindInvestment AssetKind = "investment" // Brokerage account not available through SimpleFin
+	AssetKindLiability  AssetKind = "liability"  // Loan or mortgage (entered as a negative balance)
+	AssetKindOther      AssetKind = "other"
+)
+
+// validate returns an error for unknown asset kinds
+func (k AssetKind) validate() error {
+	switch k {
+	case AssetKindProperty, AssetKindVehicle, AssetKindCash, AssetKindCrypto, AssetKindInvestment, AssetKindLiability, AssetKindOther:
+		return nil
+	}
+	return fmt.Errorf("invalid asset kind %q (supported: property, vehicle, cash, crypto, investment, liability, other)", k)
+}
+
+// Holding is a quantity of a priced asset (a coin or a ticker) held in a manual account
+type Holding struct {
+	Symbol   string      `json:"symbol"` // CoinGecko coin ID or Yahoo Finance ticker
+	Quantity float64     `json:"quantity"`
+	Source   PriceSource `json:"source"`
+	Price    Balance     `json:"price,omitempty"` // Last fetched price
+	PricedAt time.Time   `json:"priced_at,omitempty"`
+}
+
+// ManualAccount is an account whose balance is entered by hand, covering assets that aren't
+// available through SimpleFin. Accounts with holdings are valued from their prices instead.
+type ManualAccount struct {
+	Name      string    `json:"name"`
+	Kind      AssetKind `json:"kind"`
+	Balance   Balance   `json:"balance"`
+	UpdatedAt time.Time `json:"updated_at"` // When the balance was last entered or revalued
+	Holdings  []Holding `json:"holdings,omitempty"`
+}
+
+// needsRevalue reports whether any of the account's holdings was priced too long ago
+func (m ManualAccount) needsRevalue(now time.Time) bool {
+	for _, holding := range m.Holdings {
+		if now.Sub(holding.PricedAt) > holdingsRevalueInterval {
+			return true
+		}
+	}
+	return false
+}
+
+// manualAccountID returns the ID of a manual account, derived from its name
+func manualAccountID(name string) string {
+	return "manual:" + strings.Join(strings.Fields(strings.ToLower(name)), "-")
+}
+
+// ManualAccounts holds the manual accounts keyed by ID
+type ManualAccounts struct {
+	path     string
+	Accounts map[string]ManualAccount `json:"accounts"`
+}
+
+// loadManualAccounts loads the manual accounts from the cache directory, returning nil if no
+// cache directory is configured or the file can't be read
+func loadManualAccounts(cacheDir string) *ManualAccounts {
+	if cacheDir == "" {
+		return nil
+	}
+
+	manual := &ManualAccounts{
+		path:     filepath.Join(cacheDir, manualAccountsFile),
+		Accounts: make(map[string]ManualAccount),
+	}
+
+	data, err := os.ReadFile(manual.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return manual
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("manual_accounts_path", manual.path).Msg("Failed to read manual accounts, continuing without them")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, manual); err != nil {
+		log.Warn().Err(err).Str("manual_accounts_path", manual.path).Msg("Failed to parse manual accounts, continuing without them")
+		return nil
+	}
+	if manual.Accounts == nil {
+		manual.Accounts = make(map[string]ManualAccount)
+	}
+
+	return manual
+}
+
+// Set records the balance of a manual account, creating it if needed. The kind of an existing
+// account is kept unless a new one is given.
+func (m *ManualAccounts) Set(name string, kind AssetKind, balance Balance, now time.Time) (ManualAccount, error) {
+	id := manualAccountID(name)
+	if id == manualAccountID("") {
+		return ManualAccount{}, fmt.Errorf("account name is required")
+	}
+
+	account, ok := m.Accounts[id]
+	if !ok {
+		account = ManualAccount{Name: strings.TrimSpace(name), Kind: AssetKindOther}
+	}
+	if kind != "" {
+		if err := kind.validate(); err != nil {
+			return ManualAccount{}, err
+		}
+		account.Kind = kind
+	}
+	account.Balance = balance
+	account.UpdatedAt = now
+	m.Accounts[id] = account
+	return account, nil
+}
+
+// SetHolding sets the quantity of a symbol held in a manual account, creating the account as an
+// investment (or crypto for CoinGecko) if needed. A zero quantity removes the holding. The
+// account's balance is updated on the next revaluation.
+func (m *ManualAccounts) SetHolding(name, symbol string, quantity float64, source PriceSource) error {
+	if err := source.validate(); err != nil {
+		return err
+	}
+	id := manualAccountID(name)
+	if id == manualAccountID("") {
+		return fmt.Errorf("account name is required")
+	}
+
+	account, ok := m.Accounts[id]
+	if !ok {
+		account = ManualAccount{Name: strings.TrimSpace(name), Kind: AssetKindInvestment}
+		if source == PriceSourceCoinGecko {
+			account.Kind = AssetKindCrypto
+		}
+	}
+
+	var holdings []Holding
+	for _, holding := range account.Holdings {
+		if !strings.EqualFold(holding.Symbol, symbol) || holding.Source != source {
+			holdings = append(holdings, holding)
+		}
+	}
+	if quantity != 0 {
+		holdings = append(holdings, Holding{Symbol: symbol, Quantity: quantity, Source: source})
+	}
+	account.Holdings = holdings
+	m.Accounts[id] = account
+	return nil
+}
+
+// Revalue fetches the price of every holding in accounts with holdings (only those priced more
+// than a day ago unless force is set) and sets their balances to the holdings' value. Holdings
+// whose price can't be fetched keep their last price. It returns the number of accounts
+// revalued and the price errors.
+func (m *ManualAccounts) Revalue(client *http.Client, now time.Time, force bool) (int, []error) {
+	if m == nil {
+		return 0, nil
+	}
+
+	revalued := 0
+	var errs []error
+	for id, account := range m.Accounts {
+		if len(account.Holdings) == 0 || (!force && !account.needsRevalue(now)) {
+			continue
+		}
+
+		var value Money
+		for i, holding := range account.Holdings {
+			price, err := fetchPrice(client, holding.Source, holding.Symbol)
+			if err != nil {
+				errs = append(errs, err)
+			} else {
+				account.Holdings[i].Price = price
+				account.Holdings[i].PricedAt = now
+			}
+			value += toMoney(Balance(holding.Quantity * float64(account.Holdings[i].Price)))
+		}
+
+		account.Balance = Balance(value.Float64())
+		account.UpdatedAt = now
+		m.Accounts[id] = account
+		revalued++
+		log.Info().Str("account", account.Name).Str("value", value.String()).Msg("📈 Revalued manual account holdings")
+	}
+	return revalued, errs
+}
+
+// List returns the manual accounts sorted by name
+func (m *ManualAccounts) List() []ManualAccount {
+	if m == nil {
+		return nil
+	}
+
+	accounts := make([]ManualAccount, 0, len(m.Accounts))
+	for _, account := range m.Accounts {
+		accounts = append(accounts, account)
+	}
+	sort.Slice(accounts, func(i, j int) bool {
+		return strings.ToLower(accounts[i].Name) < strings.ToLower(accounts[j].Name)
+	})
+	return accounts
+}
+
+// Save writes the manual accounts to disk
+func (m *ManualAccounts) Save() error {
+	if m == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(m, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling manual accounts: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(m.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(m.path, data); err != nil {
+		return fmt.Errorf("error writing manual accounts: %w", err)
+	}
+
+	return nil
+}
@@ -0,0 +1,105 @@
+package main
+
+import (
+	"regexp"
+	"strings"
+	"unicode"
+
+	"github.com/rs/zerolog/log"
+)
+
+// MerchantRule maps raw transaction descriptions to a clean merchant name and optional logo
+type MerchantRule struct {
+	Pattern   string    `yaml:"pattern"`
+	MatchType MatchType `yaml:"match_type"`
+	Name      string    `yaml:"name"`
+	Logo      string    `yaml:"logo"` // Logo image URL shown next to the merchant in emails
+}
+
+var (
+	// paymentProcessorPrefix matches point-of-sale and wallet prefixes such as "SQ *", "TST* " or "PAYPAL *"
+	paymentProcessorPrefix = regexp.MustCompile(`(?i)^(?:SQ|TST|SP|PP|PAYPAL|PY|IC|DD|LS|CKE|GOOGLE|APL|APPLE PAY|AMZN MKTP US|AMZN MKTP CA)\s*\*\s*`)
+	// storeNumberToken matches tokens that start the noisy tail of a description (store numbers, phone numbers, references)
+	storeNumberToken = regexp.MustCompile(`^#|\d{3,}|^\d+[-/]\d+`)
+)
+
+// normalizeMerchantName cleans a raw description using the built-in heuristics: payment processor
+// prefixes are removed, everything from the first store/reference number on is dropped and the
+// result is title-cased, e.g. "SQ *BLUE BOTTLE 4459 OAK" becomes "Blue Bottle".
+func normalizeMerchantName(description string) string {
+	name := paymentProcessorPrefix.ReplaceAllString(strings.TrimSpace(description), "")
+
+	tokens := strings.Fields(name)
+	for i, token := range tokens {
+		if i > 0 && storeNumberToken.MatchString(token) {
+			tokens = tokens[:i]
+			break
+		}
+	}
+	if len(tokens) == 0 {
+		return strings.TrimSpace(description)
+	}
+
+	return titleCase(strings.Join(tokens, " "))
+}
+
+// titleCase capitalizes the first letter of each word and lowercases the rest, leaving
+// mixed-case words (e.g. "McDonald's", "iTunes") untouched
+func titleCase(s string) string {
+	words := strings.Fields(s)
+	for i, word := range words {
+		if word != strings.ToUpper(word) {
+			continue
+		}
+		runes := []rune(strings.ToLower(word))
+		runes[0] = unicode.ToUpper(runes[0])
+		words[i] = string(runes)
+	}
+	return strings.Join(words, " ")
+}
+
+// normalizeMerchants sets the merchant name (and logo) of every transaction. Configured
+// merchant_rules take precedence over the built-in heuristics.
+func normalizeMerchants(accounts []Account, filterConfig *FilterConfig) {
+	var rules []MerchantRule
+	if filterConfig != nil {
+		rules = filterConfig.MerchantRules
+	}
+
+	for i := range accounts {
+		for j := range accounts[i].Transactions {
+			tx := &accounts[i].Transactions[j]
+
+			matched := false
+			for _, rule := range rules {
+				if matchesRule(tx.Description, FilterRule{Pattern: rule.Pattern, MatchType: rule.MatchType}) {
+					tx.Merchant = rule.Name
+					tx.MerchantLogo = rule.Logo
+					matched = true
+					break
+				}
+			}
+			if !matched {
+				tx.Merchant = normalizeMerchantName(tx.Description)
+			}
+
+			log.Debug().
+				Str("description", tx.Description).
+				Str("merchant", tx.Merchant).
+				Bool("rule", matched).
+				Msg("Normalized merchant")
+		}
+	}
+}
+
+// merchantName returns the user-edited description if set, else the transaction's normalized
+// merchant name, falling back to the raw description
+func merchantName(tx Transaction) string {
+	if tx.UserDescription != "" {
+		return tx.UserDescription
+	}
+	if tx.Merchant != "" {
+		return tx.Merchant
+	}
+	return tx.Description
+}
@@ -32,9 +32,9 @@ func (b *Balance) UnmarshalJSON(data []byte) error {
 	return nil
 }
 
-// String returns a string representation of the balance
+// String returns a string representation of the balance rounded to the currency's minor unit
 func (b Balance) String() string {
-	return fmt.Sprintf("%.2f", float64(b))
+	return toMoney(b).String()
 }
 
 // NotificationType defines the type of notification
@@ -42,9 +42,10 @@ type NotificationType string
 
 // Available notification types
 const (
-	NotificationTypeSMS   NotificationType = "sms"
-	NotificationTypeEmail NotificationType = "email"
-	NotificationTypeNtfy  NotificationType = "ntfy"
+	NotificationTypeSMS    NotificationType = "sms"
+	NotificationTypeEmail  NotificationType = "email"
+	NotificationTypeNtfy   NotificationType = "ntfy"
+	NotificationTypeGotify NotificationType = "gotify"
 )
 
 // DateRangeType defines the type of date range for analysis
@@ -58,9 +59,41 @@ const (
 	DateRangeTypeLast3Months         DateRangeType = "last_3_months"
 	DateRangeTypeCurrentYear         DateRangeType = "current_year"
 	DateRangeTypeLastYear            DateRangeType = "last_year"
+	DateRangeTypeYesterday           DateRangeType = "yesterday"
+	DateRangeTypeLast7Days           DateRangeType = "last_7_days"
 	DateRangeTypeCustom              DateRangeType = "custom"
 )
 
+// SummaryCadence defines how often summaries are sent, which selects the default date range
+// and the length of the summary
+type SummaryCadence string
+
+// Available summary cadences
+const (
+	SummaryCadenceDaily   SummaryCadence = "daily"
+	SummaryCadenceWeekly  SummaryCadence = "weekly"
+	SummaryCadenceMonthly SummaryCadence = "monthly"
+)
+
+// DateRange returns the date range analyzed by default for the cadence
+func (c SummaryCadence) DateRange() (DateRangeType, error) {
+	switch c {
+	case SummaryCadenceDaily:
+		return DateRangeTypeYesterday, nil
+	case SummaryCadenceWeekly:
+		return DateRangeTypeLast7Days, nil
+	case SummaryCadenceMonthly:
+		return DateRangeTypeCurrentAndLastMonth, nil
+	default:
+		return "", fmt.Errorf("invalid summary cadence %q (supported: daily, weekly, monthly)", c)
+	}
+}
+
+// isRecap reports whether the date range is a short daily or weekly recap
+func (d DateRangeType) isRecap() bool {
+	return d == DateRangeTypeYesterday || d == DateRangeTypeLast7Days
+}
+
 // Organization represents a financial institution or organization
 type Organization struct {
 	SfinURL string  `json:"sfin-url"`
@@ -68,30 +101,46 @@ type Organization struct {
 	Name    *string `json:"name,omitempty"`
 	URL     *string `json:"url,omitempty"`
 	ID      *string `json:"id,omitempty"`
+	LogoURL string  `json:"logo-url,omitempty"` // Derived from the domain after fetching (not part of the SimpleFin API)
 }
 
 // Transaction represents a financial transaction
 type Transaction struct {
-	ID           string                  `json:"id"`
-	Description  string                  `json:"description"`
-	Amount       Balance                 `json:"amount"`
-	Posted       int64                   `json:"posted"`
-	TransactedAt *int64                  `json:"transacted_at,omitempty"`
-	Pending      *bool                   `json:"pending,omitempty"`
-	Extra        *map[string]interface{} `json:"extra,omitempty"`
+	ID              string                  `json:"id"`
+	Description     string                  `json:"description"`
+	Amount          Balance                 `json:"amount"`
+	Posted          int64                   `json:"posted"`
+	TransactedAt    *int64                  `json:"transacted_at,omitempty"`
+	Pending         *bool                   `json:"pending,omitempty"`
+	Extra           *map[string]interface{} `json:"extra,omitempty"`
+	Category        string                  `json:"-"` // Local category mapped from provider data (not part of the SimpleFin API)
+	UserCategory    string                  `json:"-"` // Category set with `transactions recategorize`, preferred over rules and mappings
+	LearnedCategory string                  `json:"-"` // Category learned from the user's corrections for this merchant
+	CategorySource  CategorySource          `json:"-"` // How Category was assigned (manual, rule, learned, provider or llm)
+	Confidence      float64                 `json:"-"` // Confidence of the category assignment, from 0 to 1
+	Merchant        string                  `json:"-"` // Normalized merchant name derived from the description
+	MerchantLogo    string                  `json:"-"` // Merchant logo URL from merchant_rules
+	Note            string                  `json:"-"` // User note from `transactions annotate`
+	UserDescription string                  `json:"-"` // User-edited description, preferred over Merchant in reports
+	IsTransfer      bool                    `json:"-"` // Side of a transfer between the user's own accounts, excluded from spending
+	RefundOf        string                  `json:"-"` // ID of the purchase this refund is linked to
+	RefundedAmount  Balance                 `json:"-"` // Total of the refunds linked to this purchase
 }
 
 // Account represents a financial account
 type Account struct {
-	ID               string        `json:"id"`
-	Name             string        `json:"name"`
-	Balance          Balance       `json:"balance"`
-	BalanceDate      int64         `json:"balance-date"`
-	Org              Organization  `json:"org"`
-	Transactions     []Transaction `json:"transactions,omitempty"`
-	Currency         *string       `json:"currency,omitempty"`
-	AvailableBalance *Balance      `json:"available-balance,omitempty"`
-	Holdings         []interface{} `json:"holdings,omitempty"`
+	ID               string                  `json:"id"`
+	Name             string                  `json:"name"`
+	Balance          Balance                 `json:"balance"`
+	BalanceDate      int64                   `json:"balance-date"`
+	Org              Organization            `json:"org"`
+	Transactions     []Transaction           `json:"transactions,omitempty"`
+	Currency         *string                 `json:"currency,omitempty"`
+	AvailableBalance *Balance                `json:"available-balance,omitempty"`
+	Holdings         []interface{}           `json:"holdings,omitempty"`
+	Extra            *map[string]interface{} `json:"extra,omitempty"`
+	Nickname         string                  `json:"-"` // Display name from the filter config (not part of the SimpleFin API)
+	CreditLimit      Money                   `json:"-"` // Credit limit from the filter config or provider data (0 if unknown)
 }
 
 // AccountsResponse represents the response from the SimpleFin API
@@ -109,17 +158,76 @@ const (
 	MatchTypeSubstring MatchType = "substring" // Contains anywhere (case-insensitive)
 	MatchTypePrefix    MatchType = "prefix"    // Starts with (case-insensitive)
 	MatchTypeSuffix    MatchType = "suffix"    // Ends with (case-insensitive)
+	MatchTypeExact     MatchType = "exact"     // Whole value (case-insensitive)
+)
+
+// FilterField selects the transaction field a filter rule is matched against
+type FilterField string
+
+// Available filter fields
+const (
+	FilterFieldDescription FilterField = "description" // Raw bank description (default)
+	FilterFieldMerchant    FilterField = "merchant"    // Normalized merchant name or edited description
+	FilterFieldCategory    FilterField = "category"    // Category mapped from provider data
+	FilterFieldNote        FilterField = "note"        // User note, e.g. a tag such as "#fixed"
 )
 
 // FilterRule represents a single transaction filter rule
 type FilterRule struct {
-	Pattern   string    `yaml:"pattern"`
-	MatchType MatchType `yaml:"match_type"`
+	Pattern   string      `yaml:"pattern"`
+	MatchType MatchType   `yaml:"match_type"`
+	Field     FilterField `yaml:"field"` // Field to match (default: description)
 }
 
 // FilterConfig represents the complete filter configuration
 type FilterConfig struct {
-	ExcludedTransactions []FilterRule `yaml:"excluded_transactions"`
+	ExcludedTransactions []FilterRule               `yaml:"excluded_transactions"`
+	CategoryMappings     map[string]string          `yaml:"category_mappings"` // Provider category -> local category
+	CategoryPreset       string                     `yaml:"category_preset"`   // Built-in provider category mappings (plaid)
+	CategoryParents      map[string]string          `yaml:"category_parents"`  // Category -> parent category, for roll-up in reports
+	CategoryRules        []CategoryRule             `yaml:"category_rules"`    // Transaction patterns -> categories, applied to every period
+	AccountAPY           map[string]float64         `yaml:"account_apy"`       // Account name or ID -> APY in percent
+	Accounts             map[string]AccountSettings `yaml:"accounts"`          // Account name or ID -> per-account settings
+	MerchantRules        []MerchantRule             `yaml:"merchant_rules"`    // Description patterns -> clean merchant names
+	Goals                []Goal                     `yaml:"goals"`             // Savings goals tracked against account balances
+	TaxRules             []TaxRule                  `yaml:"tax_rules"`         // Transaction patterns -> tax lines for `tax-report`
+	Summaries            []SummaryProfile           `yaml:"summaries"`         // Named summary configurations run with `--profile`
+}
+
+// AccountScope identifies a stage of processing an account can be excluded from
+type AccountScope string
+
+// Available account scopes
+const (
+	AccountScopeSync          AccountScope = "sync"          // Dropped right after fetching
+	AccountScopeSummary       AccountScope = "summary"       // Balance listed, transactions excluded from analysis
+	AccountScopeNotifications AccountScope = "notifications" // Transactions excluded from notification transaction lists
+)
+
+// AccountSettings holds per-account overrides from the filter configuration
+type AccountSettings struct {
+	BillingDay       int            `yaml:"billing_day"`        // Statement cycle start day for this account (1-28, 0 uses the global day)
+	Enabled          *bool          `yaml:"enabled"`            // Set to false to exclude the account from every scope
+	ExcludeFrom      []AccountScope `yaml:"exclude_from"`       // Scopes the account is excluded from
+	StaleAfterDays   int            `yaml:"stale_after_days"`   // Days without a sync before the account is reported as stale (0 uses the global threshold)
+	YNABAccountID    string         `yaml:"ynab_account_id"`    // YNAB account that `ynab push` mirrors this account into
+	FireflyAccountID string         `yaml:"firefly_account_id"` // Firefly III asset account that `firefly sync` mirrors this account into
+	Nickname         string         `yaml:"nickname"`           // Name shown in summaries and notifications instead of the bank's account name
+	DisplayOrder     int            `yaml:"display_order"`      // Position in account listings (lower first, 0 keeps the bank's order after ordered accounts)
+	CreditLimit      float64        `yaml:"credit_limit"`       // Credit limit for utilization tracking (overrides the provider's)
+}
+
+// IsExcludedFrom reports whether the account is excluded from the given scope
+func (s AccountSettings) IsExcludedFrom(scope AccountScope) bool {
+	if s.Enabled != nil && !*s.Enabled {
+		return true
+	}
+	for _, excluded := range s.ExcludeFrom {
+		if excluded == scope {
+			return true
+		}
+	}
+	return false
 }
 
 // FilterResult tracks the results of transaction filtering
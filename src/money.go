@@ -0,0 +1,183 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+)
+
+// RoundingMode defines how monetary amounts are rounded to a currency's minor unit
+type RoundingMode string
+
+// Available rounding modes
+const (
+	RoundingModeHalfUp   RoundingMode = "half_up"   // Round half away from zero (default)
+	RoundingModeHalfEven RoundingMode = "half_even" // Round half to the nearest even digit (banker's rounding)
+	RoundingModeDown     RoundingMode = "down"      // Truncate toward zero
+)
+
+// currencyMinorUnits lists ISO 4217 currencies whose minor unit differs from the default of 2 decimals
+var currencyMinorUnits = map[string]int{
+	"BIF": 0,
+	"CLP": 0,
+	"ISK": 0,
+	"JPY": 0,
+	"KRW": 0,
+	"PYG": 0,
+	"VND": 0,
+	"BHD": 3,
+	"JOD": 3,
+	"KWD": 3,
+	"OMR": 3,
+	"TND": 3,
+}
+
+// currencySymbols lists the symbols amounts are written with; other currencies are written with
+// their ISO 4217 code
+var currencySymbols = map[string]string{
+	"AUD": "A$",
+	"BRL": "R$",
+	"CAD": "CA$",
+	"CNY": "¥",
+	"EUR": "€",
+	"GBP": "£",
+	"INR": "₹",
+	"JPY": "¥",
+	"KRW": "₩",
+	"MXN": "MX$",
+	"USD": "$",
+}
+
+// moneyConfig holds the report currency and rounding strategy used by every aggregation
+var moneyConfig = struct {
+	Currency string
+	Rounding RoundingMode
+}{
+	Currency: "USD",
+	Rounding: RoundingModeHalfUp,
+}
+
+// configureMoney sets the report currency and rounding strategy
+func configureMoney(currency string, rounding RoundingMode) error {
+	switch rounding {
+	case RoundingModeHalfUp, RoundingModeHalfEven, RoundingModeDown:
+	default:
+		return fmt.Errorf("invalid rounding mode: %s", rounding)
+	}
+
+	moneyConfig.Currency = strings.ToUpper(currency)
+	moneyConfig.Rounding = rounding
+	return nil
+}
+
+// minorUnits returns the number of decimal places used by the given currency
+func minorUnits(currency string) int {
+	if digits, ok := currencyMinorUnits[strings.ToUpper(currency)]; ok {
+		return digits
+	}
+	return 2
+}
+
+// currencySymbol returns the symbol of the report currency, or its ISO 4217 code if it has none
+func currencySymbol() string {
+	if symbol, ok := currencySymbols[moneyConfig.Currency]; ok {
+		return symbol
+	}
+	return moneyConfig.Currency
+}
+
+// Money is an exact monetary amount expressed in the report currency's minor units (e.g. cents).
+// All totals are computed by summing Money values so they always equal the sum of the
+// line items as displayed.
+type Money int64
+
+// toMoney rounds a balance to the report currency's minor unit using the configured rounding mode
+func toMoney(b Balance) Money {
+	digits := minorUnits(moneyConfig.Currency)
+
+	// Work on the shortest decimal representation to avoid binary floating point artifacts
+	// (e.g. 0.285 * 100 = 28.499999999999996)
+	s := strconv.FormatFloat(math.Abs(float64(b)), 'f', -1, 64)
+	intPart, fracPart, _ := strings.Cut(s, ".")
+	for len(fracPart) < digits+1 {
+		fracPart += "0"
+	}
+
+	units, err := strconv.ParseInt(intPart+fracPart[:digits], 10, 64)
+	if err != nil {
+		// Out of int64 range; fall back to float math
+		return Money(math.Round(float64(b) * math.Pow10(digits)))
+	}
+
+	if shouldRoundUp(units, fracPart[digits:], moneyConfig.Rounding) {
+		units++
+	}
+
+	if b < 0 {
+		units = -units
+	}
+	return Money(units)
+}
+
+// shouldRoundUp decides whether the magnitude should be incremented given the discarded digits
+func shouldRoundUp(units int64, discarded string, rounding RoundingMode) bool {
+	switch rounding {
+	case RoundingModeDown:
+		return false
+	case RoundingModeHalfEven:
+		if discarded[0] != '5' {
+			return discarded[0] > '5'
+		}
+		if strings.TrimRight(discarded[1:], "0") != "" {
+			return true
+		}
+		return units%2 == 1
+	default:
+		return discarded[0] >= '5'
+	}
+}
+
+// sumMoney sums transaction amounts after rounding each one to the minor unit
+func sumMoney(transactions []Transaction) Money {
+	var total Money
+	for _, txn := range transactions {
+		total += toMoney(txn.Amount)
+	}
+	return total
+}
+
+// Float64 returns the amount in major units (e.g. dollars)
+func (m Money) Float64() float64 {
+	return float64(m) / math.Pow10(minorUnits(moneyConfig.Currency))
+}
+
+// String returns the amount in major units with exactly the currency's number of decimals
+func (m Money) String() string {
+	digits := minorUnits(moneyConfig.Currency)
+
+	sign := ""
+	value := int64(m)
+	if value < 0 {
+		sign = "-"
+		value = -value
+	}
+
+	if digits == 0 {
+		return fmt.Sprintf("%s%d", sign, value)
+	}
+
+	scale := int64(math.Pow10(digits))
+	return fmt.Sprintf("%s%d.%0*d", sign, value/scale, digits, value%scale)
+}
+
+// Format returns the amount with the report currency's symbol in English format, e.g. "$1,234.56"
+// or "€12.50", for prompts and English messages (see LocaleStrings.formatAmount)
+func (m Money) Format() string {
+	return localeStrings[LocaleEnglish].formatAmount(m)
+}
+
+// MarshalJSON encodes the amount as a JSON number in major units with exact decimals
+func (m Money) MarshalJSON() ([]byte, error) {
+	return []byte(m.String()), nil
+}
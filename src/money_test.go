@@ -0,0 +1,137 @@
+package main
+
+import (
+	"strconv"
+	"testing"
+)
+
+// withMoneyConfig sets the report currency and rounding mode for the duration of a test
+func withMoneyConfig(t *testing.T, currency string, rounding RoundingMode) {
+	t.Helper()
+	previous := moneyConfig
+	t.Cleanup(func() { moneyConfig = previous })
+	if err := configureMoney(currency, rounding); err != nil {
+		t.Fatalf("configureMoney(%q, %q): %v", currency, rounding, err)
+	}
+}
+
+func TestToMoneyRounding(t *testing.T) {
+	tests := []struct {
+		currency string
+		rounding RoundingMode
+		amount   Balance
+		want     Money
+	}{
+		// Half-way cases that float math gets wrong (0.285 * 100 = 28.499999999999996)
+		{"USD", RoundingModeHalfUp, 0.285, 29},
+		{"USD", RoundingModeHalfUp, -0.285, -29},
+		{"USD", RoundingModeHalfUp, 1.005, 101},
+		{"USD", RoundingModeHalfUp, 0.284, 28},
+		{"USD", RoundingModeHalfEven, 0.125, 12},
+		{"USD", RoundingModeHalfEven, 0.135, 14},
+		{"USD", RoundingModeHalfEven, 0.1251, 13},
+		{"USD", RoundingModeDown, 0.289, 28},
+		{"USD", RoundingModeDown, -0.289, -28},
+		{"USD", RoundingModeHalfUp, 12, 1200},
+
+		// Zero-decimal currency
+		{"JPY", RoundingModeHalfUp, 1234.5, 1235},
+		{"JPY", RoundingModeHalfUp, 1234.4, 1234},
+		{"JPY", RoundingModeHalfEven, 1234.5, 1234},
+		{"JPY", RoundingModeHalfUp, -99.5, -100},
+
+		// Three-decimal currency
+		{"KWD", RoundingModeHalfUp, 1.2345, 1235},
+		{"KWD", RoundingModeHalfUp, 1.2344, 1234},
+		{"KWD", RoundingModeHalfUp, 0.0005, 1},
+		{"KWD", RoundingModeHalfEven, 1.2345, 1234},
+		{"KWD", RoundingModeDown, 1.2349, 1234},
+	}
+
+	for _, tt := range tests {
+		withMoneyConfig(t, tt.currency, tt.rounding)
+		if got := toMoney(tt.amount); got != tt.want {
+			t.Errorf("toMoney(%v) in %s with %s = %d, want %d", tt.amount, tt.currency, tt.rounding, got, tt.want)
+		}
+	}
+}
+
+func TestMoneyString(t *testing.T) {
+	tests := []struct {
+		currency string
+		amount   Money
+		want     string
+	}{
+		{"USD", 29, "0.29"},
+		{"USD", -29, "-0.29"},
+		{"USD", 123456, "1234.56"},
+		{"JPY", 1235, "1235"},
+		{"JPY", -1235, "-1235"},
+		{"KWD", 1235, "1.235"},
+		{"KWD", 5, "0.005"},
+	}
+
+	for _, tt := range tests {
+		withMoneyConfig(t, tt.currency, RoundingModeHalfUp)
+		if got := tt.amount.String(); got != tt.want {
+			t.Errorf("Money(%d).String() in %s = %q, want %q", tt.amount, tt.currency, got, tt.want)
+		}
+	}
+}
+
+// TestReportTotalsMatchLineItems checks that the totals shown in a report are exactly the sum of
+// the line items as displayed, for amounts whose rounding would drift if summed as floats
+func TestReportTotalsMatchLineItems(t *testing.T) {
+	for _, currency := range []string{"USD", "JPY", "KWD"} {
+		withMoneyConfig(t, currency, RoundingModeHalfUp)
+
+		transactions := []Transaction{
+			{ID: "1", Description: "Coffee", Amount: -0.285, Category: "Dining > Coffee"},
+			{ID: "2", Description: "Coffee", Amount: -0.285, Category: "Dining > Coffee"},
+			{ID: "3", Description: "Lunch", Amount: -10.125, Category: "Dining > Restaurants"},
+			{ID: "4", Description: "Groceries", Amount: -1.005, Category: "Groceries"},
+			{ID: "5", Description: "Bus", Amount: -2.5, Category: "Transportation"},
+			{ID: "6", Description: "Market", Amount: -3.3335},
+			{ID: "7", Description: "Groceries", Amount: -1234.5675, Category: "Groceries"},
+		}
+
+		// Render each line item as displayed, then sum the rendered lines
+		var rendered []Transaction
+		for _, txn := range transactions {
+			line := toMoney(txn.Amount).String()
+			value, err := strconv.ParseFloat(line, 64)
+			if err != nil {
+				t.Fatalf("%s: parsing rendered line %q: %v", currency, line, err)
+			}
+			rendered = append(rendered, Transaction{Amount: Balance(value)})
+		}
+
+		total := sumMoney(transactions)
+		if got := sumMoney(rendered); got != total {
+			t.Errorf("%s: sum of rendered lines = %s, rendered total = %s", currency, got, total)
+		}
+
+		analytics := computeAnalytics(transactions)
+		if analytics.Total != -total {
+			t.Errorf("%s: analytics total = %s, want %s", currency, analytics.Total, -total)
+		}
+
+		categorized := analytics.Uncategorized.Total
+		for _, category := range analytics.Categories {
+			categorized += category.Total
+			if len(category.Children) == 0 {
+				continue
+			}
+			var children Money
+			for _, child := range category.Children {
+				children += child.Total
+			}
+			if children != category.Total {
+				t.Errorf("%s: %s subcategories sum to %s, category total is %s", currency, category.Name, children, category.Total)
+			}
+		}
+		if categorized != analytics.Total {
+			t.Errorf("%s: categories sum to %s, total is %s", currency, categorized, analytics.Total)
+		}
+	}
+}
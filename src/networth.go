@@ -0,0 +1,113 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// netWorthHistoryFile is the name of the JSON file holding daily net worth snapshots inside the cache directory
+const netWorthHistoryFile = "net_worth_history.json"
+
+// NetWorthSnapshot is the net worth on a given day, split between synced and manual accounts
+type NetWorthSnapshot struct {
+	Date   string `json:"date"`   // YYYY-MM-DD in the configured timezone
+	Synced Money  `json:"synced"` // Accounts fetched from SimpleFin
+	Manual Money  `json:"manual"` // Manual accounts (property, vehicles, cash, crypto...)
+}
+
+// Total returns the net worth across synced and manual accounts
+func (s NetWorthSnapshot) Total() Money {
+	return s.Synced + s.Manual
+}
+
+// computeNetWorth sums the balances of the synced and manual accounts. Credit card balances
+// are negative, so they count as liabilities.
+func computeNetWorth(synced []Account, manual []ManualAccount, now time.Time) NetWorthSnapshot {
+	snapshot := NetWorthSnapshot{Date: now.In(periodLocation).Format("2006-01-02")}
+	for _, account := range synced {
+		snapshot.Synced += toMoney(account.Balance)
+	}
+	for _, account := range manual {
+		snapshot.Manual += toMoney(account.Balance)
+	}
+	return snapshot
+}
+
+// NetWorthHistory holds one net worth snapshot per day
+type NetWorthHistory struct {
+	path      string
+	Snapshots []NetWorthSnapshot `json:"snapshots"`
+}
+
+// loadNetWorthHistory loads the net worth history from the cache directory, returning nil if no
+// cache directory is configured or the file can't be read
+func loadNetWorthHistory(cacheDir string) *NetWorthHistory {
+	if cacheDir == "" {
+		return nil
+	}
+
+	history := &NetWorthHistory{path: filepath.Join(cacheDir, netWorthHistoryFile)}
+
+	data, err := os.ReadFile(history.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return history
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("net_worth_history_path", history.path).Msg("Failed to read net worth history, continuing without it")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, history); err != nil {
+		log.Warn().Err(err).Str("net_worth_history_path", history.path).Msg("Failed to parse net worth history, continuing without it")
+		return nil
+	}
+
+	return history
+}
+
+// Record adds the snapshot to the history, replacing an earlier snapshot of the same day
+func (h *NetWorthHistory) Record(snapshot NetWorthSnapshot) {
+	if h == nil {
+		return
+	}
+
+	for i := range h.Snapshots {
+		if h.Snapshots[i].Date == snapshot.Date {
+			h.Snapshots[i] = snapshot
+			return
+		}
+	}
+	h.Snapshots = append(h.Snapshots, snapshot)
+	sort.Slice(h.Snapshots, func(i, j int) bool {
+		return h.Snapshots[i].Date < h.Snapshots[j].Date
+	})
+}
+
+// Save writes the net worth history to disk
+func (h *NetWorthHistory) Save() error {
+	if h == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(h, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling net worth history: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(h.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(h.path, data); err != nil {
+		return fmt.Errorf("error writing net worth history: %w", err)
+	}
+
+	return nil
+}
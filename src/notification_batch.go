@@ -0,0 +1,137 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// pendingWarningsFile is the name of the JSON file holding batched warnings inside the cache directory
+const pendingWarningsFile = "pending_warnings.json"
+
+// PendingWarning is a warning waiting to be sent with the rest of its batch
+type PendingWarning struct {
+	Message  string    `json:"message"`
+	Channels []string  `json:"channels"`
+	QueuedAt time.Time `json:"queued_at"`
+}
+
+// loadPendingWarnings reads the batched warnings from the cache directory
+func loadPendingWarnings(cacheDir string) ([]PendingWarning, error) {
+	data, err := os.ReadFile(filepath.Join(cacheDir, pendingWarningsFile))
+	if errors.Is(err, os.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("error reading pending warnings: %w", err)
+	}
+
+	var pending []PendingWarning
+	if err := json.Unmarshal(data, &pending); err != nil {
+		return nil, fmt.Errorf("error parsing pending warnings: %w", err)
+	}
+	return pending, nil
+}
+
+// savePendingWarnings writes the batched warnings to the cache directory, removing the file once
+// the batch is empty
+func savePendingWarnings(cacheDir string, pending []PendingWarning) error {
+	path := filepath.Join(cacheDir, pendingWarningsFile)
+	if len(pending) == 0 {
+		if err := os.Remove(path); err != nil && !errors.Is(err, os.ErrNotExist) {
+			return fmt.Errorf("error removing pending warnings: %w", err)
+		}
+		return nil
+	}
+
+	data, err := json.MarshalIndent(pending, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling pending warnings: %w", err)
+	}
+	if err := os.MkdirAll(cacheDir, 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+	if err := writeFileAtomic(path, data); err != nil {
+		return fmt.Errorf("error writing pending warnings: %w", err)
+	}
+	return nil
+}
+
+// queueWarning adds a warning to the pending batch
+func queueWarning(settings *Settings, message string, channels []string, now time.Time) error {
+	if settings.CacheDir == "" {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	pending, err := loadPendingWarnings(settings.CacheDir)
+	if err != nil {
+		return err
+	}
+	pending = append(pending, PendingWarning{Message: message, Channels: channels, QueuedAt: now.UTC()})
+	return savePendingWarnings(settings.CacheDir, pending)
+}
+
+// formatWarningBatch combines warnings into a single message
+func formatWarningBatch(messages []string) string {
+	if len(messages) == 1 {
+		return messages[0]
+	}
+	return fmt.Sprintf("⚠️ **%d alerts**\n\n%s", len(messages), strings.Join(messages, "\n\n---\n\n"))
+}
+
+// flushWarnings sends the pending warnings as one message per channel once the oldest has waited
+// for the batching window. Channels that fail or are held back keep their warnings for the next
+// flush.
+func flushWarnings(settings *Settings, window time.Duration, now time.Time) error {
+	if settings.CacheDir == "" {
+		return nil
+	}
+
+	pending, err := loadPendingWarnings(settings.CacheDir)
+	if err != nil || len(pending) == 0 {
+		return err
+	}
+	if oldest := pending[0].QueuedAt; now.Sub(oldest) < window {
+		log.Debug().Int("pending_warnings", len(pending)).Time("oldest", oldest).Msg("Batching window still open, holding warnings")
+		return nil
+	}
+
+	// Group the messages per channel, keeping their order
+	var channels []string
+	messages := make(map[string][]string)
+	for _, warning := range pending {
+		for _, channel := range warning.Channels {
+			if _, ok := messages[channel]; !ok {
+				channels = append(channels, channel)
+			}
+			messages[channel] = append(messages[channel], warning.Message)
+		}
+	}
+
+	var remaining []PendingWarning
+	var errs []error
+	for _, channel := range channels {
+		_, err := sendNotification(settings, formatWarningBatch(messages[channel]), nil, nil, "warning", []string{channel})
+		if err == nil {
+			log.Info().Str("channel", channel).Int("warnings", len(messages[channel])).Msg("📦 Sent batched warnings")
+			continue
+		}
+		if !errors.Is(err, errNotificationDeferred) {
+			errs = append(errs, fmt.Errorf("error sending batched warnings via %s: %w", channel, err))
+		}
+		for _, message := range messages[channel] {
+			remaining = append(remaining, PendingWarning{Message: message, Channels: []string{channel}, QueuedAt: pending[0].QueuedAt})
+		}
+	}
+
+	if err := savePendingWarnings(settings.CacheDir, remaining); err != nil {
+		errs = append(errs, err)
+	}
+	return errors.Join(errs...)
+}
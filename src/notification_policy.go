@@ -0,0 +1,214 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// notificationLogFile is the name of the JSON file recording sent notifications inside the cache directory
+const notificationLogFile = "notification_log.json"
+
+// errNotificationDeferred is returned by sendNotification when quiet hours or rate limits held
+// back every channel, so callers can report the event again on a later run
+var errNotificationDeferred = errors.New("notification deferred by quiet hours or rate limits")
+
+// QuietHours is a daily window, in the configured time zone, during which warnings are held back
+type QuietHours struct {
+	Start int // Minutes after midnight
+	End   int // Minutes after midnight; before Start for windows spanning midnight
+}
+
+// parseQuietHours parses a "HH:MM-HH:MM" window (e.g. "22:00-07:00")
+func parseQuietHours(value string) (*QuietHours, error) {
+	startText, endText, ok := strings.Cut(value, "-")
+	if !ok {
+		return nil, fmt.Errorf("invalid quiet hours %q (expected HH:MM-HH:MM)", value)
+	}
+
+	var minutes [2]int
+	for i, text := range []string{startText, endText} {
+		t, err := time.Parse("15:04", strings.TrimSpace(text))
+		if err != nil {
+			return nil, fmt.Errorf("invalid quiet hours %q (expected HH:MM-HH:MM)", value)
+		}
+		minutes[i] = t.Hour()*60 + t.Minute()
+	}
+	return &QuietHours{Start: minutes[0], End: minutes[1]}, nil
+}
+
+// Contains reports whether t falls within the quiet hours
+func (q *QuietHours) Contains(t time.Time) bool {
+	if q == nil || q.Start == q.End {
+		return false
+	}
+	t = t.In(periodLocation)
+	minute := t.Hour()*60 + t.Minute()
+	if q.Start < q.End {
+		return minute >= q.Start && minute < q.End
+	}
+	return minute >= q.Start || minute < q.End
+}
+
+// RateLimit caps how many notifications a channel sends for an event type (the notification
+// topic: info or warning) within a period. An empty channel or event matches all of them.
+type RateLimit struct {
+	Channel string
+	Event   string
+	Max     int
+	Per     time.Duration
+}
+
+// matches reports whether the limit applies to the channel and event
+func (r RateLimit) matches(channel, event string) bool {
+	return (r.Channel == "" || r.Channel == channel) && (r.Event == "" || r.Event == event)
+}
+
+// parseRateLimits parses comma-separated "[channel][:event]=max/period" rules, e.g.
+// "ntfy:warning=5/1h,email=10/24h,warning=20/24h"
+func parseRateLimits(value string) ([]RateLimit, error) {
+	var limits []RateLimit
+	for _, rule := range strings.Split(value, ",") {
+		rule = strings.TrimSpace(rule)
+		if rule == "" {
+			continue
+		}
+
+		scope, budget, hasBudget := strings.Cut(rule, "=")
+		maxText, perText, hasPeriod := strings.Cut(budget, "/")
+		if !hasBudget || !hasPeriod {
+			return nil, fmt.Errorf("invalid rate limit %q (expected [channel][:event]=max/period)", rule)
+		}
+
+		var limit RateLimit
+		if channel, event, hasEvent := strings.Cut(scope, ":"); hasEvent {
+			limit.Channel, limit.Event = channel, event
+		} else if scope == "info" || scope == "warning" {
+			limit.Event = scope
+		} else {
+			limit.Channel = scope
+		}
+
+		var err error
+		if limit.Max, err = strconv.Atoi(maxText); err != nil || limit.Max < 0 {
+			return nil, fmt.Errorf("invalid rate limit %q: max must be a non-negative number", rule)
+		}
+		if limit.Per, err = time.ParseDuration(perText); err != nil || limit.Per <= 0 {
+			return nil, fmt.Errorf("invalid rate limit %q: period must be a duration such as 1h", rule)
+		}
+		limits = append(limits, limit)
+	}
+	return limits, nil
+}
+
+// SentNotification records a notification delivered through a channel
+type SentNotification struct {
+	Channel string    `json:"channel"`
+	Event   string    `json:"event"`
+	SentAt  time.Time `json:"sent_at"`
+}
+
+// NotificationLog holds the notifications sent within the longest rate limit period
+type NotificationLog struct {
+	path string
+	Sent []SentNotification `json:"sent"`
+}
+
+// loadNotificationLog loads the notification log from the cache directory, returning nil if no
+// cache directory is configured or the file can't be read
+func loadNotificationLog(cacheDir string) *NotificationLog {
+	if cacheDir == "" {
+		return nil
+	}
+
+	notificationLog := &NotificationLog{path: filepath.Join(cacheDir, notificationLogFile)}
+
+	data, err := os.ReadFile(notificationLog.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return notificationLog
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("notification_log_path", notificationLog.path).Msg("Failed to read notification log, rate limits won't apply")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, notificationLog); err != nil {
+		log.Warn().Err(err).Str("notification_log_path", notificationLog.path).Msg("Failed to parse notification log, starting fresh")
+		notificationLog.Sent = nil
+	}
+
+	return notificationLog
+}
+
+// Allowed reports whether sending through the channel stays within every matching rate limit
+func (l *NotificationLog) Allowed(limits []RateLimit, channel, event string, now time.Time) bool {
+	if l == nil {
+		return true
+	}
+	for _, limit := range limits {
+		if !limit.matches(channel, event) {
+			continue
+		}
+		count := 0
+		for _, sent := range l.Sent {
+			if limit.matches(sent.Channel, sent.Event) && now.Sub(sent.SentAt) < limit.Per {
+				count++
+			}
+		}
+		if count >= limit.Max {
+			return false
+		}
+	}
+	return true
+}
+
+// Record adds a sent notification and drops those older than the longest rate limit period
+func (l *NotificationLog) Record(limits []RateLimit, channel, event string, now time.Time) {
+	if l == nil {
+		return
+	}
+
+	var longest time.Duration
+	for _, limit := range limits {
+		if limit.Per > longest {
+			longest = limit.Per
+		}
+	}
+
+	kept := l.Sent[:0]
+	for _, sent := range l.Sent {
+		if now.Sub(sent.SentAt) < longest {
+			kept = append(kept, sent)
+		}
+	}
+	l.Sent = append(kept, SentNotification{Channel: channel, Event: event, SentAt: now})
+}
+
+// Save writes the notification log to disk
+func (l *NotificationLog) Save() error {
+	if l == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(l, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling notification log: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(l.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(l.path, data); err != nil {
+		return fmt.Errorf("error writing notification log: %w", err)
+	}
+
+	return nil
+}
@@ -2,12 +2,16 @@ package main
 
 import (
 	"bytes"
+	"encoding/base64"
+	"encoding/json"
 	"fmt"
 	"html/template"
 	"io"
+	"mime"
+	"mime/multipart"
 	"net/http"
-	"net/smtp"
-	"net/url"
+	"net/textproto"
+	"os"
 	"strings"
 	"time"
 
@@ -20,12 +24,8 @@ type NtfyMessage struct {
 	Priority string `json:"priority,omitempty"`
 }
 
-// sendNtfyNotification sends a notification to the ntfy.sh service with the specified topic
-func sendNtfyNotification(settings *Settings, message string, notificationTopic string) error {
-	if settings.NtfyTopic == nil || *settings.NtfyTopic == "" {
-		return nil
-	}
-
+// resolveNtfyTopic returns the ntfy topic for the notification type, appending the warning suffix for warnings
+func resolveNtfyTopic(settings *Settings, notificationTopic string) string {
 	topic := *settings.NtfyTopic
 	log.Debug().
 		Str("received_topic_type", notificationTopic).
@@ -40,8 +40,27 @@ func sendNtfyNotification(settings *Settings, message string, notificationTopic
 		log.Debug().Str("final_topic", topic).Msg("Using base topic for ntfy notification")
 	}
 
-	// Strip markdown formatting from the message
-	plainMessage := stripMarkdown(message)
+	return topic
+}
+
+// sendNtfyNotification sends a notification to the ntfy.sh service with the specified topic
+func sendNtfyNotification(settings *Settings, message string, transactions []Transaction, period *SummaryPeriod, notificationTopic string) error {
+	if settings.NtfyTopic == nil || *settings.NtfyTopic == "" {
+		return nil
+	}
+
+	topic := resolveNtfyTopic(settings, notificationTopic)
+
+	// Strip markdown formatting from the message, or render the custom template
+	plainMessage, err := renderNtfyMessage(settings.TemplatesDir, message, topic, notificationTopic)
+	if err != nil {
+		return err
+	}
+
+	title, err := notificationTitle(settings, transactions, period, notificationTopic)
+	if err != nil {
+		return err
+	}
 
 	url := fmt.Sprintf("%s/%s", settings.NtfyServer, topic)
 	req, err := http.NewRequest(http.MethodPost, url, bytes.NewBuffer([]byte(plainMessage)))
@@ -50,14 +69,12 @@ func sendNtfyNotification(settings *Settings, message string, notificationTopic
 	}
 
 	req.Header.Set("Content-Type", "text/plain")
-	req.Header.Set("Title", "💰 Finance Tracker")
+	req.Header.Set("Title", title)
 
 	// Update request body with plain text message
 	req.Body = io.NopCloser(bytes.NewBuffer([]byte(plainMessage)))
 
-	client := &http.Client{
-		Timeout: 10 * time.Second,
-	}
+	client := newHTTPClient(10 * time.Second)
 
 	resp, err := client.Do(req)
 	if err != nil {
@@ -73,8 +90,65 @@ func sendNtfyNotification(settings *Settings, message string, notificationTopic
 	return nil
 }
 
-// generateEmailHTML generates a beautiful HTML email with the transaction list
-func generateEmailHTML(message string, transactions []Transaction) (string, error) {
+// EmailAttachment is a file attached to the summary email
+type EmailAttachment struct {
+	Name        string
+	ContentType string
+	Data        []byte
+}
+
+// buildEmailBody returns the Content-Type and body of an email with the HTML content; with
+// attachments, the body is multipart/mixed with each attachment base64 encoded
+func buildEmailBody(htmlContent string, attachments []EmailAttachment) (string, []byte, error) {
+	if len(attachments) == 0 {
+		return "text/html; charset=UTF-8", []byte(htmlContent), nil
+	}
+
+	var body bytes.Buffer
+	writer := multipart.NewWriter(&body)
+	part, err := writer.CreatePart(textproto.MIMEHeader{"Content-Type": {"text/html; charset=UTF-8"}})
+	if err != nil {
+		return "", nil, fmt.Errorf("error creating HTML part: %w", err)
+	}
+	if _, err := io.WriteString(part, htmlContent); err != nil {
+		return "", nil, fmt.Errorf("error writing HTML part: %w", err)
+	}
+
+	for _, attachment := range attachments {
+		part, err := writer.CreatePart(textproto.MIMEHeader{
+			"Content-Type":              {attachment.ContentType},
+			"Content-Transfer-Encoding": {"base64"},
+			"Content-Disposition":       {mime.FormatMediaType("attachment", map[string]string{"filename": attachment.Name})},
+		})
+		if err != nil {
+			return "", nil, fmt.Errorf("error creating attachment %s: %w", attachment.Name, err)
+		}
+		// Base64 lines are wrapped at 76 characters as required by RFC 2045
+		encoded := base64.StdEncoding.EncodeToString(attachment.Data)
+		for len(encoded) > 76 {
+			fmt.Fprintf(part, "%s\r\n", encoded[:76])
+			encoded = encoded[76:]
+		}
+		fmt.Fprintf(part, "%s\r\n", encoded)
+	}
+
+	if err := writer.Close(); err != nil {
+		return "", nil, fmt.Errorf("error finishing email body: %w", err)
+	}
+	return "multipart/mixed; boundary=" + writer.Boundary(), body.Bytes(), nil
+}
+
+// emailData is the data available to the email template, including custom ones
+type emailData struct {
+	Message      template.HTML // Summary rendered as HTML
+	Markdown     string        // Summary as markdown
+	Transactions []Transaction
+	Locale       LocaleStrings // Labels and formats of the configured LOCALE
+}
+
+// generateEmailHTML generates a beautiful HTML email with the transaction list. A custom
+// email.html in templatesDir replaces the built-in template.
+func generateEmailHTML(templatesDir string, message string, transactions []Transaction) (string, error) {
 	// Convert markdown message to HTML
 	messageHTML := convertMarkdownToHTML(message)
 
@@ -162,7 +236,7 @@ func generateEmailHTML(message string, transactions []Transaction) (string, erro
     <div class="container">
         <div class="header">
             <img src="https://raw.githubusercontent.com/arsfeld/finance-tracker/refs/heads/main/logo.jpg" class="logo" alt="Finance Tracker Logo">
-            <div class="title">Transaction Summary</div>
+            <div class="title">{{.Locale.EmailTitle}}</div>
         </div>
         
         <div class="content">
@@ -170,14 +244,14 @@ func generateEmailHTML(message string, transactions []Transaction) (string, erro
             
             <table class="transactions">
                 <tr>
-                    <th>Description</th>
-                    <th>Amount</th>
-                    <th>Date</th>
+                    <th>{{.Locale.Merchant}}</th>
+                    <th>{{.Locale.Amount}}</th>
+                    <th>{{.Locale.Date}}</th>
                 </tr>
                 {{range .Transactions}}
                 <tr>
-                    <td>{{.Description}}</td>
-                    <td>{{.Amount}}</td>
+                    <td>{{if .MerchantLogo}}<img src="{{.MerchantLogo}}" alt="" width="16" height="16"> {{end}}{{merchant .}}</td>
+                    <td>{{amount .Amount}}</td>
                     <td>{{formatDate .TransactedAt .Posted}}</td>
                 </tr>
                 {{end}}
@@ -185,27 +259,33 @@ func generateEmailHTML(message string, transactions []Transaction) (string, erro
         </div>
         
         <div class="footer">
-            This is an automated message. Please do not reply to this email.
+            {{.Locale.EmailFooter}}
         </div>
     </div>
 </body>
 </html>`
 
-	type emailData struct {
-		Message      template.HTML
-		Transactions []Transaction
-	}
-
 	funcMap := template.FuncMap{
+		"merchant": merchantName,
+		"amount": func(amount Balance) string {
+			return currentLocale.formatAmount(toMoney(amount))
+		},
 		"formatDate": func(transactedAt *int64, posted int64) string {
 			if transactedAt != nil {
-				return time.Unix(*transactedAt, 0).Format("2006-01-02 15:04")
+				return time.Unix(*transactedAt, 0).In(periodLocation).Format(currentLocale.DateTimeLayout)
 			}
-			return time.Unix(posted, 0).Format("2006-01-02 15:04")
+			return time.Unix(posted, 0).In(periodLocation).Format(currentLocale.DateTimeLayout)
 		},
 	}
 
-	tmpl, err := template.New("email").Funcs(funcMap).Parse(emailTemplate)
+	templateText := emailTemplate
+	if custom, ok, err := readTemplateOverride(templatesDir, emailTemplateFile); err != nil {
+		return "", err
+	} else if ok {
+		templateText = custom
+	}
+
+	tmpl, err := template.New("email").Funcs(funcMap).Parse(templateText)
 	if err != nil {
 		return "", fmt.Errorf("error parsing template: %w", err)
 	}
@@ -213,7 +293,9 @@ func generateEmailHTML(message string, transactions []Transaction) (string, erro
 	var buf bytes.Buffer
 	if err := tmpl.Execute(&buf, emailData{
 		Message:      template.HTML(messageHTML),
+		Markdown:     message,
 		Transactions: transactions,
+		Locale:       currentLocale,
 	}); err != nil {
 		return "", fmt.Errorf("error executing template: %w", err)
 	}
@@ -226,7 +308,7 @@ func generateEmailHTML(message string, transactions []Transaction) (string, erro
 }
 
 // sendEmailNotification sends an email notification using SMTP
-func sendEmailNotification(settings *Settings, message string, transactions []Transaction) error {
+func sendEmailNotification(settings *Settings, message string, transactions []Transaction, period *SummaryPeriod, notificationTopic string, attachments []EmailAttachment) error {
 	log.Debug().Msg("Starting email notification process")
 
 	if settings.MailerURL == nil || *settings.MailerURL == "" ||
@@ -239,54 +321,39 @@ func sendEmailNotification(settings *Settings, message string, transactions []Tr
 	log.Debug().
 		Str("from", *settings.MailerFrom).
 		Str("to", *settings.MailerTo).
-		Str("url", *settings.MailerURL).
+		Stringer("url", settings.MailerURL).
 		Int("transaction_count", len(transactions)).
 		Msg("Email notification settings validated")
 
 	// Generate HTML content
-	htmlContent, err := generateEmailHTML(message, transactions)
+	htmlContent, err := generateEmailHTML(settings.TemplatesDir, message, transactions)
 	if err != nil {
 		log.Error().Err(err).Msg("Failed to generate HTML content")
 		return fmt.Errorf("error generating HTML: %w", err)
 	}
 	log.Debug().Int("html_length", len(htmlContent)).Msg("HTML content generated successfully")
 
-	// Parse SMTP server from URL
-	mailURL, err := url.Parse(*settings.MailerURL)
+	subject, err := emailSubject(settings, transactions, period, notificationTopic)
 	if err != nil {
-		log.Error().Err(err).Msg("Failed to parse SMTP URL")
-		return fmt.Errorf("error parsing SMTP URL: %w", err)
-	}
-
-	smtpHost := mailURL.Hostname()
-	smtpPort := mailURL.Port()
-	if smtpPort == "" {
-		smtpPort = "587" // Default to TLS port
+		return err
 	}
 
-	// Extract username and password from URL if present
-	username := ""
-	password := ""
-	if mailURL.User != nil {
-		username = mailURL.User.Username()
-		if pass, ok := mailURL.User.Password(); ok {
-			password = pass
-		}
-	}
-
-	log.Debug().
-		Str("smtp_host", smtpHost).
-		Str("smtp_port", smtpPort).
-		Str("username", username).
-		Msg("SMTP server details parsed")
-
-	// Prepare email headers
+	// Prepare email headers; Bcc recipients only get the envelope
+	to, cc, bcc := emailRecipients(settings)
 	headers := make(map[string]string)
 	headers["From"] = *settings.MailerFrom
-	headers["To"] = *settings.MailerTo
-	headers["Subject"] = "Finance Tracker - Transaction Summary"
+	headers["To"] = strings.Join(to, ", ")
+	if len(cc) > 0 {
+		headers["Cc"] = strings.Join(cc, ", ")
+	}
+	headers["Subject"] = mime.QEncoding.Encode("utf-8", subject)
 	headers["MIME-Version"] = "1.0"
-	headers["Content-Type"] = "text/html; charset=UTF-8"
+
+	contentType, body, err := buildEmailBody(htmlContent, attachments)
+	if err != nil {
+		return err
+	}
+	headers["Content-Type"] = contentType
 
 	// Build email message
 	var messageBuilder strings.Builder
@@ -294,21 +361,15 @@ func sendEmailNotification(settings *Settings, message string, transactions []Tr
 		messageBuilder.WriteString(fmt.Sprintf("%s: %s\r\n", key, value))
 	}
 	messageBuilder.WriteString("\r\n")
-	messageBuilder.WriteString(htmlContent)
+	messageBuilder.Write(body)
 
-	log.Debug().Int("message_size", messageBuilder.Len()).Msg("Email message built")
+	log.Debug().Int("message_size", messageBuilder.Len()).Int("attachment_count", len(attachments)).Msg("Email message built")
 
 	// Send email using SMTP
-	auth := smtp.PlainAuth("", username, password, smtpHost)
-	log.Debug().Msg("Attempting to send email via SMTP")
-
-	err = smtp.SendMail(
-		fmt.Sprintf("%s:%s", smtpHost, smtpPort),
-		auth,
-		*settings.MailerFrom,
-		[]string{*settings.MailerTo},
-		[]byte(messageBuilder.String()),
-	)
+	recipients := append(append(append([]string{}, to...), cc...), bcc...)
+	log.Debug().Int("recipient_count", len(recipients)).Msg("Attempting to send email via SMTP")
+
+	err = sendSMTPMessage(settings, recipients, []byte(messageBuilder.String()))
 	if err != nil {
 		log.Error().Err(err).Msg("Failed to send email via SMTP")
 		return fmt.Errorf("error sending email: %w", err)
@@ -318,28 +379,189 @@ func sendEmailNotification(settings *Settings, message string, transactions []Tr
 	return nil
 }
 
-// sendNotification sends a notification through the specified notification channels
-func sendNotification(settings *Settings, message string, allTransactions []Transaction, notificationTopic string, notificationTypes []string) ([]string, error) {
+// sendNotification sends a notification through the specified notification channels. Warnings
+// are held back during quiet hours, and channels over a rate limit are skipped; if that leaves no
+// channel, errNotificationDeferred is returned. Attachments are only sent by email.
+func sendNotification(settings *Settings, message string, allTransactions []Transaction, period *SummaryPeriod, notificationTopic string, notificationTypes []string, attachments ...EmailAttachment) ([]string, error) {
 	var successfulChannels []string
 
+	now := time.Now()
+	if notificationTopic == "warning" && settings.QuietHours.Contains(now) {
+		log.Info().Str("topic", notificationTopic).Msg("🌙 Quiet hours, holding back the notification")
+		return nil, errNotificationDeferred
+	}
+
+	// While a run batches its warnings, they are queued and sent together by flushWarnings
+	if notificationTopic == "warning" && settings.BatchWarnings {
+		queueErr := queueWarning(settings, message, notificationTypes, now)
+		if queueErr == nil {
+			log.Debug().Msg("Queued warning for the batch")
+			return nil, nil
+		}
+		log.Warn().Err(queueErr).Msg("Failed to queue warning, sending it now")
+	}
+
+	var notificationLog *NotificationLog
+	if len(settings.RateLimits) > 0 {
+		notificationLog = loadNotificationLog(settings.CacheDir)
+		defer func() {
+			if notificationLog != nil {
+				if err := notificationLog.Save(); err != nil {
+					log.Warn().Err(err).Msg("Failed to save notification log")
+				}
+			}
+		}()
+	}
+
+	skipped := 0
 	for _, nt := range notificationTypes {
+		if !notificationLog.Allowed(settings.RateLimits, nt, notificationTopic, now) {
+			log.Info().Str("channel", nt).Str("topic", notificationTopic).Msg("🚦 Rate limit reached, skipping the channel")
+			skipped++
+			continue
+		}
+
+		_, finish := startSpan("notification", map[string]interface{}{
+			"channel": nt,
+			"topic":   notificationTopic,
+		})
+
 		switch NotificationType(nt) {
 		case NotificationTypeNtfy:
-			if err := sendNtfyNotification(settings, message, notificationTopic); err != nil {
+			err := sendNtfyNotification(settings, message, allTransactions, period, notificationTopic)
+			finish(err)
+			if err != nil {
 				return nil, fmt.Errorf("error sending ntfy notification: %w", err)
 			}
+			notificationLog.Record(settings.RateLimits, nt, notificationTopic, now)
 			if settings.NtfyTopic != nil && *settings.NtfyTopic != "" {
 				successfulChannels = append(successfulChannels, fmt.Sprintf("Ntfy: %s", *settings.NtfyTopic))
 			}
+		case NotificationTypeGotify:
+			err := sendGotifyNotification(settings, message, allTransactions, period, notificationTopic)
+			finish(err)
+			if err != nil {
+				return nil, fmt.Errorf("error sending gotify notification: %w", err)
+			}
+			notificationLog.Record(settings.RateLimits, nt, notificationTopic, now)
+			if gotifyConfigured(settings) {
+				successfulChannels = append(successfulChannels, fmt.Sprintf("Gotify: %s", settings.GotifyURL))
+			}
 		case NotificationTypeEmail:
-			if err := sendEmailNotification(settings, message, allTransactions); err != nil {
+			err := sendEmailNotification(settings, message, allTransactions, period, notificationTopic, attachments)
+			finish(err)
+			if err != nil {
 				return nil, fmt.Errorf("error sending email notification: %w", err)
 			}
+			notificationLog.Record(settings.RateLimits, nt, notificationTopic, now)
 			if settings.MailerTo != nil && *settings.MailerTo != "" {
 				successfulChannels = append(successfulChannels, fmt.Sprintf("Email: %s", *settings.MailerTo))
 			}
+		default:
+			finish(nil)
 		}
 	}
 
+	if skipped > 0 && skipped == len(notificationTypes) {
+		return nil, errNotificationDeferred
+	}
 	return successfulChannels, nil
 }
+
+// sendTestNotification sends a canned test message through a single channel, failing if the
+// channel is not configured
+func sendTestNotification(settings *Settings, channel NotificationType, notificationTopic string) error {
+	message := fmt.Sprintf("✅ **Test notification**\n\nThis is a test message from %s. If you can read this, the %s channel is configured correctly.", GetVersion(), channel)
+
+	switch channel {
+	case NotificationTypeNtfy:
+		if settings.NtfyTopic == nil || *settings.NtfyTopic == "" {
+			return fmt.Errorf("ntfy is not configured (set NTFY_TOPIC)")
+		}
+		return sendNtfyNotification(settings, message, nil, nil, notificationTopic)
+	case NotificationTypeGotify:
+		if !gotifyConfigured(settings) {
+			return fmt.Errorf("gotify is not configured (set GOTIFY_URL and GOTIFY_TOKEN)")
+		}
+		return sendGotifyNotification(settings, message, nil, nil, notificationTopic)
+	case NotificationTypeEmail:
+		if settings.MailerURL == nil || *settings.MailerURL == "" ||
+			settings.MailerFrom == nil || *settings.MailerFrom == "" ||
+			settings.MailerTo == nil || *settings.MailerTo == "" {
+			return fmt.Errorf("email is not configured (set MAILER_URL, MAILER_FROM and MAILER_TO)")
+		}
+		return sendEmailNotification(settings, message, nil, nil, notificationTopic, nil)
+	default:
+		return fmt.Errorf("unsupported notification channel: %s", channel)
+	}
+}
+
+// renderNotification composes every notification for the given channels without sending anything.
+// Ntfy and Gotify payloads are printed to stdout and email HTML is written to a temporary file.
+func renderNotification(settings *Settings, message string, allTransactions []Transaction, period *SummaryPeriod, notificationTopic string, notificationTypes []string) error {
+	for _, nt := range notificationTypes {
+		switch NotificationType(nt) {
+		case NotificationTypeNtfy:
+			if settings.NtfyTopic == nil || *settings.NtfyTopic == "" {
+				log.Info().Msg("🧪 [dry-run] Ntfy not configured, skipping")
+				continue
+			}
+			topic := resolveNtfyTopic(settings, notificationTopic)
+			plainMessage, err := renderNtfyMessage(settings.TemplatesDir, message, topic, notificationTopic)
+			if err != nil {
+				return err
+			}
+			title, err := notificationTitle(settings, allTransactions, period, notificationTopic)
+			if err != nil {
+				return err
+			}
+			fmt.Printf("----- ntfy: POST %s/%s -----\nTitle: %s\n\n%s\n\n", settings.NtfyServer, topic, title, plainMessage)
+			log.Info().Str("topic", topic).Msg("🧪 [dry-run] Rendered ntfy notification")
+		case NotificationTypeGotify:
+			if !gotifyConfigured(settings) {
+				log.Info().Msg("🧪 [dry-run] Gotify not configured, skipping")
+				continue
+			}
+			gotifyMessage, err := newGotifyMessage(settings, message, allTransactions, period, notificationTopic)
+			if err != nil {
+				return err
+			}
+			payload, err := json.MarshalIndent(gotifyMessage, "", "  ")
+			if err != nil {
+				return fmt.Errorf("error encoding gotify message: %w", err)
+			}
+			fmt.Printf("----- gotify: POST %s/message -----\n%s\n\n", strings.TrimSuffix(settings.GotifyURL, "/"), payload)
+			log.Info().Int("priority", gotifyPriority(settings, notificationTopic)).Msg("🧪 [dry-run] Rendered gotify notification")
+		case NotificationTypeEmail:
+			htmlContent, err := generateEmailHTML(settings.TemplatesDir, message, allTransactions)
+			if err != nil {
+				return fmt.Errorf("error generating HTML: %w", err)
+			}
+			subject, err := emailSubject(settings, allTransactions, period, notificationTopic)
+			if err != nil {
+				return err
+			}
+
+			file, err := os.CreateTemp("", "finance_tracker_email_*.html")
+			if err != nil {
+				return fmt.Errorf("error creating email preview file: %w", err)
+			}
+			if _, err := file.WriteString(htmlContent); err != nil {
+				file.Close()
+				return fmt.Errorf("error writing email preview file: %w", err)
+			}
+			if err := file.Close(); err != nil {
+				return fmt.Errorf("error closing email preview file: %w", err)
+			}
+
+			log.Info().
+				Str("from", getStringValue(settings.MailerFrom)).
+				Str("to", getStringValue(settings.MailerTo)).
+				Str("subject", subject).
+				Str("path", file.Name()).
+				Msg("🧪 [dry-run] Rendered email notification")
+		}
+	}
+
+	return nil
+}
@@ -0,0 +1,271 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// organizationsFile is the name of the JSON file holding organization renames and merges inside the cache directory
+const organizationsFile = "organizations.json"
+
+// OrganizationOverrides renames organizations and merges duplicates, such as a bank that got a
+// new organization ID after reconnecting
+type OrganizationOverrides struct {
+	path   string
+	Names  map[string]string `json:"names"`  // Organization ID -> display name
+	Merged map[string]string `json:"merged"` // Organization ID -> ID of the organization it was merged into
+}
+
+// loadOrganizationOverrides loads the organization overrides from the cache directory, returning
+// nil if no cache directory is configured or the file can't be read
+func loadOrganizationOverrides(cacheDir string) *OrganizationOverrides {
+	if cacheDir == "" {
+		return nil
+	}
+
+	overrides := &OrganizationOverrides{
+		path:   filepath.Join(cacheDir, organizationsFile),
+		Names:  make(map[string]string),
+		Merged: make(map[string]string),
+	}
+
+	data, err := os.ReadFile(overrides.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return overrides
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("organizations_path", overrides.path).Msg("Failed to read organization overrides, continuing without them")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, overrides); err != nil {
+		log.Warn().Err(err).Str("organizations_path", overrides.path).Msg("Failed to parse organization overrides, continuing without them")
+		return nil
+	}
+	if overrides.Names == nil {
+		overrides.Names = make(map[string]string)
+	}
+	if overrides.Merged == nil {
+		overrides.Merged = make(map[string]string)
+	}
+
+	return overrides
+}
+
+// Resolve follows merges from an organization ID to the organization it ends up in
+func (o *OrganizationOverrides) Resolve(id string) string {
+	if o == nil {
+		return id
+	}
+	for seen := 0; seen <= len(o.Merged); seen++ {
+		into, ok := o.Merged[id]
+		if !ok {
+			break
+		}
+		id = into
+	}
+	return id
+}
+
+// Merge merges an organization into another. Merging an organization into one that was merged
+// into it is rejected.
+func (o *OrganizationOverrides) Merge(from, into string) error {
+	if from == into || o.Resolve(into) == from {
+		return fmt.Errorf("cannot merge organization %q into itself", from)
+	}
+	o.Merged[from] = into
+	return nil
+}
+
+// Save writes the organization overrides to disk
+func (o *OrganizationOverrides) Save() error {
+	if o == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(o, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling organization overrides: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(o.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(o.path, data); err != nil {
+		return fmt.Errorf("error writing organization overrides: %w", err)
+	}
+
+	return nil
+}
+
+// applyOrganizationOverrides moves accounts of merged organizations to the organization they were
+// merged into, taking its name and domain from another of its accounts, and applies renames
+func applyOrganizationOverrides(accounts []Account, overrides *OrganizationOverrides) {
+	if overrides == nil || (len(overrides.Names) == 0 && len(overrides.Merged) == 0) {
+		return
+	}
+
+	orgs := make(map[string]Organization)
+	for _, account := range accounts {
+		if _, merged := overrides.Merged[organizationID(account.Org)]; !merged {
+			orgs[organizationID(account.Org)] = account.Org
+		}
+	}
+
+	for i := range accounts {
+		id := overrides.Resolve(organizationID(accounts[i].Org))
+		if id != organizationID(accounts[i].Org) {
+			org, ok := orgs[id]
+			if !ok {
+				org = Organization{SfinURL: accounts[i].Org.SfinURL, Name: accounts[i].Org.Name}
+			}
+			org.ID = &id
+			accounts[i].Org = org
+		}
+		if name, ok := overrides.Names[id]; ok {
+			accounts[i].Org.Name = &name
+		}
+	}
+}
+
+// OrganizationAccount is an account listed under its organization
+type OrganizationAccount struct {
+	ID       string    `json:"id"`
+	Name     string    `json:"name"`
+	LastSync time.Time `json:"last_sync"`
+}
+
+// OrganizationStatus is an institution's connection status: its accounts, when it last synced
+// and the bridge errors that mention it
+type OrganizationStatus struct {
+	ID       string                `json:"id"`
+	Name     string                `json:"name"`
+	Domain   string                `json:"domain,omitempty"`
+	Accounts []OrganizationAccount `json:"accounts"`
+	LastSync time.Time             `json:"last_sync"` // Most recent balance date of its accounts
+	Errors   []string              `json:"errors,omitempty"`
+}
+
+// OrganizationReport lists every organization, plus the bridge errors that couldn't be matched
+// to one
+type OrganizationReport struct {
+	Organizations []OrganizationStatus `json:"organizations"`
+	Unmatched     []string             `json:"unmatched_errors,omitempty"`
+}
+
+// organizationID returns the organization's ID, falling back to its domain and SimpleFin URL
+// for bridges that don't provide one
+func organizationID(org Organization) string {
+	if id := getStringValue(org.ID); id != "" {
+		return id
+	}
+	if domain := getStringValue(org.Domain); domain != "" {
+		return domain
+	}
+	return org.SfinURL
+}
+
+// organizationName returns the organization's name, falling back to its domain
+func organizationName(org Organization) string {
+	if name := getStringValue(org.Name); name != "" {
+		return name
+	}
+	if domain := getStringValue(org.Domain); domain != "" {
+		return domain
+	}
+	return "Unknown"
+}
+
+// buildOrganizationReport groups accounts by organization. Bridge errors are attached to each
+// organization whose name or domain they mention, since SimpleFin reports them as plain messages.
+func buildOrganizationReport(accounts []Account, apiErrors []string) OrganizationReport {
+	byID := make(map[string]*OrganizationStatus)
+	var order []string
+	for _, account := range accounts {
+		id := organizationID(account.Org)
+		status, ok := byID[id]
+		if !ok {
+			status = &OrganizationStatus{ID: id, Name: organizationName(account.Org), Domain: getStringValue(account.Org.Domain)}
+			byID[id] = status
+			order = append(order, id)
+		}
+
+		lastSync := time.Unix(account.BalanceDate, 0).UTC()
+		status.Accounts = append(status.Accounts, OrganizationAccount{ID: account.ID, Name: accountDisplayName(account), LastSync: lastSync})
+		if lastSync.After(status.LastSync) {
+			status.LastSync = lastSync
+		}
+	}
+
+	var report OrganizationReport
+	for _, apiErr := range apiErrors {
+		lower := strings.ToLower(apiErr)
+		matched := false
+		for _, id := range order {
+			status := byID[id]
+			if strings.Contains(lower, strings.ToLower(status.Name)) || (status.Domain != "" && strings.Contains(lower, strings.ToLower(status.Domain))) {
+				status.Errors = append(status.Errors, apiErr)
+				matched = true
+			}
+		}
+		if !matched {
+			report.Unmatched = append(report.Unmatched, apiErr)
+		}
+	}
+
+	for _, id := range order {
+		report.Organizations = append(report.Organizations, *byID[id])
+	}
+	sort.SliceStable(report.Organizations, func(i, j int) bool {
+		return strings.ToLower(report.Organizations[i].Name) < strings.ToLower(report.Organizations[j].Name)
+	})
+	return report
+}
+
+// writeOrganizationReportJSON writes the organization report as indented JSON
+func writeOrganizationReportJSON(w io.Writer, report OrganizationReport) error {
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(report)
+}
+
+// writeOrganizationReportText writes the organization report as a table followed by the
+// unmatched bridge errors
+func writeOrganizationReportText(w io.Writer, report OrganizationReport) error {
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(tw, "ORGANIZATION\tID\tACCOUNTS\tLAST SYNCED\tERRORS")
+	for _, org := range report.Organizations {
+		var names []string
+		for _, account := range org.Accounts {
+			names = append(names, account.Name)
+		}
+		fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s\n",
+			org.Name,
+			org.ID,
+			strings.Join(names, ", "),
+			org.LastSync.Local().Format("2006-01-02 15:04"),
+			strings.Join(org.Errors, "; "))
+	}
+	if err := tw.Flush(); err != nil {
+		return err
+	}
+
+	if len(report.Unmatched) > 0 {
+		fmt.Fprintln(w, "\nOther bridge errors:")
+		for _, apiErr := range report.Unmatched {
+			fmt.Fprintf(w, "- %s\n", apiErr)
+		}
+	}
+	return nil
+}
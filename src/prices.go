@@ -0,0 +1,115 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+)
+
+// PriceSource selects where a holding's price is fetched from
+type PriceSource string
+
+// Available price sources
+const (
+	PriceSourceCoinGecko PriceSource = "coingecko" // Crypto, by CoinGecko coin ID (e.g. bitcoin)
+	PriceSourceYahoo     PriceSource = "yahoo"     // Stocks and funds, by Yahoo Finance ticker (e.g. VTI)
+)
+
+// Price source endpoints, variables so they can point at a proxy or mirror
+var (
+	coinGeckoPriceURL = "https://api.coingecko.com/api/v3/simple/price"
+	yahooChartURL     = "https://query1.finance.yahoo.com/v8/finance/chart/"
+)
+
+// validate returns an error for unknown price sources
+func (s PriceSource) validate() error {
+	switch s {
+	case PriceSourceCoinGecko, PriceSourceYahoo:
+		return nil
+	}
+	return fmt.Errorf("invalid price source %q (supported: coingecko, yahoo)", s)
+}
+
+// fetchPrice returns the current price of a symbol. CoinGecko prices are quoted in the report
+// currency; Yahoo prices are in the listing's currency, which must match the report currency.
+func fetchPrice(client *http.Client, source PriceSource, symbol string) (Balance, error) {
+	switch source {
+	case PriceSourceCoinGecko:
+		return fetchCoinGeckoPrice(client, symbol)
+	case PriceSourceYahoo:
+		return fetchYahooPrice(client, symbol)
+	}
+	return 0, source.validate()
+}
+
+// fetchCoinGeckoPrice returns the price of a coin from the CoinGecko simple price API
+func fetchCoinGeckoPrice(client *http.Client, coin string) (Balance, error) {
+	currency := strings.ToLower(moneyConfig.Currency)
+	query := url.Values{"ids": {coin}, "vs_currencies": {currency}}
+
+	var prices map[string]map[string]float64
+	if err := getPriceJSON(client, coinGeckoPriceURL+"?"+query.Encode(), &prices); err != nil {
+		return 0, fmt.Errorf("error fetching CoinGecko price for %s: %w", coin, err)
+	}
+
+	price, ok := prices[coin][currency]
+	if !ok {
+		return 0, fmt.Errorf("CoinGecko has no %s price for %s", moneyConfig.Currency, coin)
+	}
+	return Balance(price), nil
+}
+
+// fetchYahooPrice returns the latest market price of a ticker from the Yahoo Finance chart API
+func fetchYahooPrice(client *http.Client, ticker string) (Balance, error) {
+	var chart struct {
+		Chart struct {
+			Result []struct {
+				Meta struct {
+					Currency           string  `json:"currency"`
+					RegularMarketPrice float64 `json:"regularMarketPrice"`
+				} `json:"meta"`
+			} `json:"result"`
+		} `json:"chart"`
+	}
+	if err := getPriceJSON(client, yahooChartURL+url.PathEscape(ticker)+"?range=1d&interval=1d", &chart); err != nil {
+		return 0, fmt.Errorf("error fetching Yahoo Finance price for %s: %w", ticker, err)
+	}
+
+	if len(chart.Chart.Result) == 0 {
+		return 0, fmt.Errorf("Yahoo Finance has no price for %s", ticker)
+	}
+	meta := chart.Chart.Result[0].Meta
+	if meta.Currency != "" && !strings.EqualFold(meta.Currency, moneyConfig.Currency) {
+		return 0, fmt.Errorf("%s is quoted in %s, not the report currency %s", ticker, meta.Currency, moneyConfig.Currency)
+	}
+	return Balance(meta.RegularMarketPrice), nil
+}
+
+// getPriceJSON fetches a price API URL and decodes the JSON response into target
+func getPriceJSON(client *http.Client, priceURL string, target interface{}) error {
+	req, err := http.NewRequest(http.MethodGet, priceURL, nil)
+	if err != nil {
+		return fmt.Errorf("error creating request: %w", err)
+	}
+	req.Header.Set("Accept", "application/json")
+	req.Header.Set("User-Agent", "finance_tracker/"+GetVersion())
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return fmt.Errorf("error making request: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return fmt.Errorf("request failed with status %d: %s", resp.StatusCode, string(body))
+	}
+
+	if err := json.NewDecoder(resp.Body).Decode(target); err != nil {
+		return fmt.Errorf("error decoding response: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,216 @@
+package main
+
+import (
+	"bytes"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// receiptMatchDays is how far a transaction's date may be from the receipt's to match it; card
+// transactions often post a few days after the purchase
+const receiptMatchDays = 4
+
+// receiptPrompt asks the vision model for the receipt's fields as JSON
+const receiptPrompt = `Extract the data from this receipt. Reply with only a JSON object, no other text:
+{"merchant": "store name", "date": "YYYY-MM-DD", "total": 12.34, "items": [{"description": "item", "amount": 1.23}]}
+Use the total actually paid (after tax, tips and discounts). Leave "date" empty if it isn't readable.`
+
+// ReceiptItem is a line item read from a receipt
+type ReceiptItem struct {
+	Description string  `json:"description"`
+	Amount      float64 `json:"amount"`
+}
+
+// Receipt is the data extracted from a receipt image
+type Receipt struct {
+	Merchant string        `json:"merchant"`
+	Date     string        `json:"date"`
+	Total    float64       `json:"total"`
+	Items    []ReceiptItem `json:"items"`
+}
+
+// visionContent is a part of a multimodal message: text or an image URL (data URLs included)
+type visionContent struct {
+	Type     string          `json:"type"`
+	Text     string          `json:"text,omitempty"`
+	ImageURL *visionImageURL `json:"image_url,omitempty"`
+}
+
+type visionImageURL struct {
+	URL string `json:"url"`
+}
+
+// visionMessage is a chat message with multimodal content
+type visionMessage struct {
+	Role    string          `json:"role"`
+	Content []visionContent `json:"content"`
+}
+
+// visionRequest is an OpenRouter chat completion request with image input
+type visionRequest struct {
+	Model       string          `json:"model"`
+	Models      []string        `json:"models"`
+	Messages    []visionMessage `json:"messages"`
+	Temperature float64         `json:"temperature"`
+}
+
+// extractReceipt sends the receipt image to the OPENROUTER_VISION_MODEL (or LOCAL_LLM_MODEL when
+// receipts run locally) and parses the merchant, date, total and line items from its reply
+func extractReceipt(settings *Settings, imagePath string) (Receipt, error) {
+	settings = llmSettings(settings, LLMTaskReceipt)
+	if settings.VisionModel == "" {
+		return Receipt{}, fmt.Errorf("receipt extraction needs a vision model (set OPENROUTER_VISION_MODEL)")
+	}
+
+	image, err := os.ReadFile(imagePath)
+	if err != nil {
+		return Receipt{}, fmt.Errorf("error reading %s: %w", imagePath, err)
+	}
+	contentType := http.DetectContentType(image)
+	if !strings.HasPrefix(contentType, "image/") {
+		return Receipt{}, fmt.Errorf("%s is not an image (detected %s)", imagePath, contentType)
+	}
+
+	models := strings.Split(settings.VisionModel, ",")
+	reqBody := visionRequest{
+		Model:       models[0], // Servers without model fallbacks (e.g. Ollama) only read this
+		Models:      models,
+		Temperature: 0,
+		Messages: []visionMessage{{
+			Role: "user",
+			Content: []visionContent{
+				{Type: "text", Text: receiptPrompt},
+				{Type: "image_url", ImageURL: &visionImageURL{URL: "data:" + contentType + ";base64," + base64.StdEncoding.EncodeToString(image)}},
+			},
+		}},
+	}
+	jsonData, err := json.Marshal(reqBody)
+	if err != nil {
+		return Receipt{}, fmt.Errorf("error marshaling request: %w", err)
+	}
+
+	req, err := http.NewRequest(http.MethodPost, settings.OpenRouterURL, bytes.NewReader(jsonData))
+	if err != nil {
+		return Receipt{}, fmt.Errorf("error creating request: %w", err)
+	}
+	if settings.OpenRouterAPIKey != "" {
+		req.Header.Set("Authorization", fmt.Sprintf("Bearer %s", settings.OpenRouterAPIKey.Reveal()))
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := newHTTPClient(120 * time.Second).Do(req)
+	if err != nil {
+		return Receipt{}, fmt.Errorf("error making request: %w", err)
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return Receipt{}, fmt.Errorf("error reading response body: %w", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		return Receipt{}, fmt.Errorf("API request failed with status %d: %s", resp.StatusCode, string(body))
+	}
+
+	var openRouterResp OpenRouterResponse
+	if err := json.Unmarshal(body, &openRouterResp); err != nil {
+		return Receipt{}, fmt.Errorf("error decoding response: %w", err)
+	}
+	if openRouterResp.Error != nil {
+		return Receipt{}, fmt.Errorf("OpenRouter API error: %s (code: %d)", openRouterResp.Error.Message, openRouterResp.Error.Code)
+	}
+	if len(openRouterResp.Choices) == 0 {
+		return Receipt{}, fmt.Errorf("no response from OpenRouter")
+	}
+	log.Debug().Str("model", openRouterResp.Model).Msg("Receipt extracted")
+
+	return parseReceipt(openRouterResp.Choices[0].Message.Content)
+}
+
+// parseReceipt parses the JSON object in the model's reply, ignoring any text or code fences
+// around it
+func parseReceipt(content string) (Receipt, error) {
+	start := strings.Index(content, "{")
+	end := strings.LastIndex(content, "}")
+	if start < 0 || end < start {
+		return Receipt{}, fmt.Errorf("no receipt data in the model's reply: %q", content)
+	}
+
+	var receipt Receipt
+	if err := json.Unmarshal([]byte(content[start:end+1]), &receipt); err != nil {
+		return Receipt{}, fmt.Errorf("error parsing receipt data: %w", err)
+	}
+	if receipt.Total <= 0 {
+		return Receipt{}, fmt.Errorf("no total found on the receipt")
+	}
+	return receipt, nil
+}
+
+// matchReceipt finds the expense for the receipt: same amount (to the cent) and the closest date
+// within receiptMatchDays, preferring transactions whose merchant matches. Without a readable
+// date every transaction of the period is considered.
+func matchReceipt(accounts []Account, receipt Receipt) (Transaction, bool) {
+	date, dateErr := time.ParseInLocation("2006-01-02", receipt.Date, periodLocation)
+	total := toMoney(Balance(receipt.Total))
+	merchant := strings.ToLower(receipt.Merchant)
+
+	var best Transaction
+	bestScore := -1.0
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if -toMoney(tx.Amount) != total {
+				continue
+			}
+
+			score := 0.0
+			if dateErr == nil {
+				days := transactionTime(tx).Sub(date).Hours() / 24
+				if days < -1 || days > receiptMatchDays {
+					continue
+				}
+				if days < 0 {
+					days = -days
+				}
+				score = receiptMatchDays - days
+			}
+			name := strings.ToLower(merchantName(tx))
+			if merchant != "" && name != "" && (strings.Contains(name, merchant) || strings.Contains(merchant, name)) {
+				score += receiptMatchDays + 1
+			}
+
+			if score > bestScore {
+				best, bestScore = tx, score
+			}
+		}
+	}
+	return best, bestScore >= 0
+}
+
+// saveReceiptData stores the extracted receipt as JSON next to the attached image
+func saveReceiptData(attachment Attachment, receipt Receipt) error {
+	data, err := json.MarshalIndent(receipt, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error encoding receipt data: %w", err)
+	}
+	path := filepath.Join(filepath.Dir(attachment.Path), strings.TrimSuffix(attachment.Name, filepath.Ext(attachment.Name))+".receipt.json")
+	return writeFileAtomic(path, data)
+}
+
+// formatReceipt renders the extracted receipt for the terminal
+func formatReceipt(receipt Receipt) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "Merchant: %s\nDate:     %s\nTotal:    %s\n", receipt.Merchant, receipt.Date, currentLocale.formatAmount(toMoney(Balance(receipt.Total))))
+	for _, item := range receipt.Items {
+		fmt.Fprintf(&b, "  - %s: %s\n", item.Description, currentLocale.formatAmount(toMoney(Balance(item.Amount))))
+	}
+	return b.String()
+}
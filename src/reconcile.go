@@ -0,0 +1,279 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// reconciliationFile is the name of the JSON file holding balance checkpoints and reconciliation
+// results inside the cache directory
+const reconciliationFile = "reconciliation.json"
+
+// reconciliationWindow is how far back transactions are fetched to reconcile a checkpoint. Older
+// checkpoints are replaced without being checked.
+const reconciliationWindow = 90 * 24 * time.Hour
+
+// maxReconciliationResults is the number of reconciliation gaps kept, oldest dropped first
+const maxReconciliationResults = 500
+
+// BalanceCheckpoint is an account balance as reported at a point in time, the opening balance
+// for the next reconciliation
+type BalanceCheckpoint struct {
+	Balance     Balance `json:"balance"`
+	BalanceDate int64   `json:"balance_date"`
+}
+
+// ReconciliationGap records a reported balance that doesn't match the opening balance plus the
+// transactions posted since, which usually means transactions were missed
+type ReconciliationGap struct {
+	AccountID string    `json:"account_id"`
+	Account   string    `json:"account"`
+	From      time.Time `json:"from"` // Opening balance date
+	To        time.Time `json:"to"`   // Reported balance date
+	Expected  Money     `json:"expected"`
+	Reported  Money     `json:"reported"`
+	CheckedAt time.Time `json:"checked_at"`
+}
+
+// Gap returns the amount unaccounted for by the fetched transactions
+func (g ReconciliationGap) Gap() Money {
+	return g.Reported - g.Expected
+}
+
+// Reconciliation holds the last balance checkpoint of each account and the gaps found
+type Reconciliation struct {
+	path        string
+	Checkpoints map[string]BalanceCheckpoint `json:"checkpoints"` // Account ID -> last reported balance
+	Gaps        []ReconciliationGap          `json:"gaps"`
+	Pending     []ReconciliationGap          `json:"pending,omitempty"` // Gaps not notified yet, sent again by the next run
+}
+
+// loadReconciliation loads the reconciliation state from the cache directory, returning nil if
+// no cache directory is configured or the file can't be read
+func loadReconciliation(cacheDir string) *Reconciliation {
+	if cacheDir == "" {
+		return nil
+	}
+
+	reconciliation := &Reconciliation{
+		path:        filepath.Join(cacheDir, reconciliationFile),
+		Checkpoints: make(map[string]BalanceCheckpoint),
+	}
+
+	data, err := os.ReadFile(reconciliation.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return reconciliation
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("reconciliation_path", reconciliation.path).Msg("Failed to read reconciliation state, continuing without it")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, reconciliation); err != nil {
+		log.Warn().Err(err).Str("reconciliation_path", reconciliation.path).Msg("Failed to parse reconciliation state, starting fresh")
+		reconciliation.Gaps = nil
+	}
+	if reconciliation.Checkpoints == nil {
+		reconciliation.Checkpoints = make(map[string]BalanceCheckpoint)
+	}
+
+	return reconciliation
+}
+
+// FetchStart returns the start of the transaction window needed to reconcile every checkpoint,
+// limited to the reconciliation window
+func (r *Reconciliation) FetchStart(now time.Time) time.Time {
+	start := now.Add(-24 * time.Hour)
+	for _, checkpoint := range r.Checkpoints {
+		if date := time.Unix(checkpoint.BalanceDate, 0); date.Before(start) {
+			start = date
+		}
+	}
+	if limit := now.Add(-reconciliationWindow); start.Before(limit) {
+		start = limit
+	}
+	return start
+}
+
+// Reconcile compares each account's reported balance with its last checkpoint plus the
+// transactions posted since, records the gaps, and moves the checkpoints to the reported
+// balances. Pending transactions are ignored since banks don't agree on whether the balance
+// includes them. It returns the new gaps and the number of accounts checked.
+func (r *Reconciliation) Reconcile(accounts []Account, now time.Time) ([]ReconciliationGap, int) {
+	var gaps []ReconciliationGap
+	checked := 0
+	for _, account := range accounts {
+		checkpoint, ok := r.Checkpoints[account.ID]
+		if ok && account.BalanceDate <= checkpoint.BalanceDate {
+			continue
+		}
+		r.Checkpoints[account.ID] = BalanceCheckpoint{Balance: account.Balance, BalanceDate: account.BalanceDate}
+		if !ok || now.Sub(time.Unix(checkpoint.BalanceDate, 0)) > reconciliationWindow {
+			continue
+		}
+
+		expected := toMoney(checkpoint.Balance)
+		for _, tx := range account.Transactions {
+			if getTransactionStatus(tx) == "pending" {
+				continue
+			}
+			if tx.Posted > checkpoint.BalanceDate && tx.Posted <= account.BalanceDate {
+				expected += toMoney(tx.Amount)
+			}
+		}
+		checked++
+
+		reported := toMoney(account.Balance)
+		if reported == expected {
+			continue
+		}
+
+		gap := ReconciliationGap{
+			AccountID: account.ID,
+			Account:   accountDisplayName(account),
+			From:      time.Unix(checkpoint.BalanceDate, 0).UTC(),
+			To:        time.Unix(account.BalanceDate, 0).UTC(),
+			Expected:  expected,
+			Reported:  reported,
+			CheckedAt: now,
+		}
+		log.Warn().
+			Str("account_name", account.Name).
+			Str("expected", expected.String()).
+			Str("reported", reported.String()).
+			Msg("🧮 Account balance doesn't reconcile with its transactions")
+		gaps = append(gaps, gap)
+	}
+
+	r.Gaps = append(r.Gaps, gaps...)
+	if len(r.Gaps) > maxReconciliationResults {
+		r.Gaps = r.Gaps[len(r.Gaps)-maxReconciliationResults:]
+	}
+	r.Pending = append(r.Pending, gaps...)
+	if len(r.Pending) > maxReconciliationResults {
+		r.Pending = r.Pending[len(r.Pending)-maxReconciliationResults:]
+	}
+
+	return gaps, checked
+}
+
+// Unfetched returns the IDs of the accounts with a checkpoint that weren't fetched, e.g. accounts
+// with a zero balance, which getTransactionsForWindow leaves out
+func (r *Reconciliation) Unfetched(accounts []Account) []string {
+	fetched := make(map[string]bool, len(accounts))
+	for _, account := range accounts {
+		fetched[account.ID] = true
+	}
+
+	var unfetched []string
+	for id := range r.Checkpoints {
+		if !fetched[id] {
+			unfetched = append(unfetched, id)
+		}
+	}
+	return unfetched
+}
+
+// Forward settles the checkpoints of unfetched accounts against the account list: an account
+// with a zero balance has its checkpoint moved to that balance without being reconciled, and one
+// SimpleFin no longer lists has its checkpoint dropped. Otherwise an account paid down to zero
+// would be compared with its old checkpoint once its balance moves again. Other accounts (e.g.
+// paused ones) keep their checkpoints.
+func (r *Reconciliation) Forward(unfetched []string, listed []Account) {
+	byID := make(map[string]Account, len(listed))
+	for _, account := range listed {
+		byID[account.ID] = account
+	}
+
+	for _, id := range unfetched {
+		account, ok := byID[id]
+		if !ok {
+			log.Info().Str("account_id", id).Msg("Account is no longer listed, dropping its balance checkpoint")
+			delete(r.Checkpoints, id)
+			continue
+		}
+		checkpoint := r.Checkpoints[id]
+		if account.Balance != 0 || account.BalanceDate <= checkpoint.BalanceDate {
+			continue
+		}
+		log.Info().Str("account_name", account.Name).Msg("Account has a zero balance and wasn't fetched, moving its checkpoint without reconciling")
+		r.Checkpoints[id] = BalanceCheckpoint{Balance: account.Balance, BalanceDate: account.BalanceDate}
+	}
+}
+
+// Save writes the reconciliation state to disk
+func (r *Reconciliation) Save() error {
+	if r == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(r, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling reconciliation state: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(r.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(r.path, data); err != nil {
+		return fmt.Errorf("error writing reconciliation state: %w", err)
+	}
+
+	return nil
+}
+
+// formatReconciliationGap formats a gap as a bullet for notifications
+func formatReconciliationGap(gap ReconciliationGap) string {
+	return fmt.Sprintf("- %s: reported %s, expected %s from transactions since %s (gap: %s)",
+		gap.Account, gap.Reported.Format(), gap.Expected.Format(), gap.From.In(periodLocation).Format("2006-01-02"), gap.Gap().Format())
+}
+
+// notifyReconciliationGaps warns through the warning channel about the pending gaps, the balances
+// that don't reconcile and haven't been reported yet. The checkpoints have already moved past
+// them, so gaps held back by quiet hours or rate limits, or whose warning failed, stay pending
+// and are sent again by the next run.
+func notifyReconciliationGaps(settings *Settings, config RunConfig, r *Reconciliation, accounts []Account, filterConfig *FilterConfig) error {
+	excluded := make(map[string]bool)
+	for _, account := range accounts {
+		if lookupAccountSettings(account, filterConfig).IsExcludedFrom(AccountScopeNotifications) {
+			excluded[account.ID] = true
+		}
+	}
+
+	var lines []string
+	for _, gap := range r.Pending {
+		if !excluded[gap.AccountID] {
+			lines = append(lines, formatReconciliationGap(gap))
+		}
+	}
+	if len(lines) == 0 {
+		r.Pending = nil
+		return nil
+	}
+	if config.DisableNotifications {
+		return nil
+	}
+
+	message := fmt.Sprintf("🧮 **Balances don't reconcile**\n\nThe reported balance of these accounts doesn't match their previous balance plus the transactions since. Some transactions may be missing:\n%s", strings.Join(lines, "\n"))
+	if config.DryRun {
+		return renderNotification(settings, message, nil, nil, "warning", config.Notifications)
+	}
+	_, err := sendNotification(settings, message, nil, nil, "warning", config.Notifications)
+	if errors.Is(err, errNotificationDeferred) {
+		log.Info().Int("gaps", len(lines)).Msg("Reconciliation warning held back, it will be sent by the next run")
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("error sending reconciliation warning: %w", err)
+	}
+	r.Pending = nil
+	return nil
+}
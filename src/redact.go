@@ -0,0 +1,71 @@
+package main
+
+import (
+	"regexp"
+	"strings"
+)
+
+var (
+	// cardNumberPattern matches 12-19 digit card numbers, optionally grouped with spaces or dashes
+	cardNumberPattern = regexp.MustCompile(`\b\d(?:[ -]?\d){11,18}\b`)
+	// accountNumberPattern matches shorter numbers introduced by an account/card keyword, e.g. "ACCT #12345678"
+	accountNumberPattern = regexp.MustCompile(`(?i)\b((?:acct|account|card|a/c)\s*(?:no\.?|number)?\s*[:#]?\s*)(\d[\d -]{3,}\d)`)
+)
+
+// maskDigits replaces every digit but the last four with '*', dropping separators
+func maskDigits(number string) string {
+	digits := strings.Map(func(r rune) rune {
+		if r >= '0' && r <= '9' {
+			return r
+		}
+		return -1
+	}, number)
+
+	if len(digits) <= 4 {
+		return strings.Repeat("*", len(digits))
+	}
+	return "****" + digits[len(digits)-4:]
+}
+
+// redactAccountNumbers masks anything that looks like a card or account number, keeping the last four digits
+func redactAccountNumbers(s string) string {
+	s = cardNumberPattern.ReplaceAllStringFunc(s, maskDigits)
+	return accountNumberPattern.ReplaceAllStringFunc(s, func(match string) string {
+		parts := accountNumberPattern.FindStringSubmatch(match)
+		return parts[1] + maskDigits(parts[2])
+	})
+}
+
+// redactValue masks account numbers in strings nested anywhere in a decoded JSON value
+func redactValue(value interface{}) interface{} {
+	switch v := value.(type) {
+	case string:
+		return redactAccountNumbers(v)
+	case map[string]interface{}:
+		for key, item := range v {
+			v[key] = redactValue(item)
+		}
+		return v
+	case []interface{}:
+		for i, item := range v {
+			v[i] = redactValue(item)
+		}
+		return v
+	default:
+		return value
+	}
+}
+
+// redactAccounts masks account numbers in transaction descriptions and extra data in place, so
+// every output built from the accounts (logs, prompts, notifications, exports) is redacted
+func redactAccounts(accounts []Account) {
+	for i := range accounts {
+		for j := range accounts[i].Transactions {
+			tx := &accounts[i].Transactions[j]
+			tx.Description = redactAccountNumbers(tx.Description)
+			if tx.Extra != nil {
+				redactValue(*tx.Extra)
+			}
+		}
+	}
+}
@@ -0,0 +1,123 @@
+package main
+
+import (
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// refundMatchWindowDays is how long after a purchase a refund from the same merchant is linked to it automatically
+const refundMatchWindowDays = 90
+
+// RefundLink links a refund (keyed by its transaction ID) to the purchase it returns. The refund
+// amount is kept so the purchase can be netted even when the refund is outside the fetched period.
+type RefundLink struct {
+	PurchaseID string    `json:"purchase_id"`
+	Amount     Balance   `json:"amount"`
+	Automatic  bool      `json:"automatic,omitempty"` // Detected by merchant and amount rather than linked by the user
+	LinkedAt   time.Time `json:"linked_at"`
+}
+
+// detectRefunds links credits on an account to the most recent earlier purchase from the same
+// merchant on that account, within refundMatchWindowDays, whose amount not yet refunded covers the
+// refund. Refunds that are already linked are skipped; new links are added to notes and returned.
+func detectRefunds(accounts []Account, notes *TransactionNotes) int {
+	if notes == nil {
+		return 0
+	}
+
+	// What each purchase already has refunded, so several refunds never add up to more than it cost
+	refunded := make(map[string]Money, len(notes.Refunds))
+	for _, link := range notes.Refunds {
+		refunded[link.PurchaseID] += toMoney(link.Amount)
+	}
+
+	detected := 0
+	for _, account := range accounts {
+		for _, refund := range account.Transactions {
+			if refund.Amount <= 0 || refund.IsTransfer {
+				continue
+			}
+			if _, ok := notes.Refunds[refund.ID]; ok {
+				continue
+			}
+
+			refundTime := transactionTime(refund)
+			var purchase *Transaction
+			for i := range account.Transactions {
+				candidate := &account.Transactions[i]
+				if candidate.Amount >= 0 || candidate.IsTransfer || toMoney(-candidate.Amount)-refunded[candidate.ID] < toMoney(refund.Amount) {
+					continue
+				}
+				if !strings.EqualFold(merchantName(*candidate), merchantName(refund)) {
+					continue
+				}
+				candidateTime := transactionTime(*candidate)
+				if candidateTime.After(refundTime) || refundTime.Sub(candidateTime) > refundMatchWindowDays*24*time.Hour {
+					continue
+				}
+				if purchase == nil || candidateTime.After(transactionTime(*purchase)) {
+					purchase = candidate
+				}
+			}
+			if purchase == nil {
+				continue
+			}
+
+			notes.Refunds[refund.ID] = RefundLink{
+				PurchaseID: purchase.ID,
+				Amount:     refund.Amount,
+				Automatic:  true,
+				LinkedAt:   time.Now().UTC(),
+			}
+			refunded[purchase.ID] += toMoney(refund.Amount)
+			detected++
+
+			log.Debug().
+				Str("refund_id", refund.ID).
+				Str("purchase_id", purchase.ID).
+				Str("merchant", merchantName(refund)).
+				Str("amount", toMoney(refund.Amount).String()).
+				Msg("Linked refund to purchase")
+		}
+	}
+	return detected
+}
+
+// applyRefundLinks marks linked refunds and adds their amounts to the purchases they return
+func applyRefundLinks(accounts []Account, notes *TransactionNotes) {
+	if notes == nil || len(notes.Refunds) == 0 {
+		return
+	}
+
+	refunded := make(map[string]Balance)
+	for _, link := range notes.Refunds {
+		refunded[link.PurchaseID] += link.Amount
+	}
+
+	for i := range accounts {
+		for j := range accounts[i].Transactions {
+			tx := &accounts[i].Transactions[j]
+			if link, ok := notes.Refunds[tx.ID]; ok {
+				tx.RefundOf = link.PurchaseID
+			}
+			if amount, ok := refunded[tx.ID]; ok {
+				tx.RefundedAmount = amount
+			}
+		}
+	}
+}
+
+// netExpense returns the expense with linked refunds subtracted, and false if it was fully refunded
+func netExpense(tx Transaction) (Transaction, bool) {
+	if tx.RefundedAmount == 0 {
+		return tx, true
+	}
+	net := toMoney(tx.Amount) + toMoney(tx.RefundedAmount)
+	if net >= 0 {
+		return tx, false
+	}
+	tx.Amount = Balance(net.Float64())
+	return tx, true
+}
@@ -0,0 +1,142 @@
+package main
+
+import (
+	"bytes"
+	"encoding/csv"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// writeTransactionsCSV writes every transaction of the accounts as CSV, oldest first
+func writeTransactionsCSV(w io.Writer, accounts []Account) error {
+	writer := csv.NewWriter(w)
+	if err := writer.Write([]string{"Date", "Account", "Description", "Merchant", "Category", "Amount", "Status", "Note", "ID"}); err != nil {
+		return fmt.Errorf("error writing CSV header: %w", err)
+	}
+
+	type accountTransaction struct {
+		account string
+		tx      Transaction
+	}
+	var rows []accountTransaction
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			rows = append(rows, accountTransaction{account: accountDisplayName(account), tx: tx})
+		}
+	}
+	sort.SliceStable(rows, func(i, j int) bool {
+		return transactionTime(rows[i].tx).Before(transactionTime(rows[j].tx))
+	})
+
+	for _, row := range rows {
+		record := []string{
+			transactionTime(row.tx).Format("2006-01-02"),
+			row.account,
+			row.tx.Description,
+			merchantName(row.tx),
+			row.tx.Category,
+			toMoney(row.tx.Amount).String(),
+			getTransactionStatus(row.tx),
+			row.tx.Note,
+			row.tx.ID,
+		}
+		if err := writer.Write(record); err != nil {
+			return fmt.Errorf("error writing CSV record: %w", err)
+		}
+	}
+
+	writer.Flush()
+	return writer.Error()
+}
+
+// transactionsCSVAttachment returns the period's transactions as a CSV email attachment
+func transactionsCSVAttachment(start, end time.Time, accounts []Account) (EmailAttachment, error) {
+	var csvData bytes.Buffer
+	if err := writeTransactionsCSV(&csvData, accounts); err != nil {
+		return EmailAttachment{}, err
+	}
+	return EmailAttachment{
+		Name:        fmt.Sprintf("transactions-%s_%s.csv", start.Format("2006-01-02"), end.Format("2006-01-02")),
+		ContentType: "text/csv; charset=UTF-8",
+		Data:        csvData.Bytes(),
+	}, nil
+}
+
+// archiveReport stores the summary (markdown) and the period's transactions (CSV) in the report
+// archive: a local directory, or a WebDAV collection when the destination is an http(s) URL
+func archiveReport(settings *Settings, start, end time.Time, analysis string, accounts []Account) error {
+	var csvData bytes.Buffer
+	if err := writeTransactionsCSV(&csvData, accounts); err != nil {
+		return err
+	}
+
+	period := fmt.Sprintf("%s_%s", start.Format("2006-01-02"), end.Format("2006-01-02"))
+	files := map[string][]byte{
+		"summary-" + period + ".md":       []byte(analysis + "\n"),
+		"transactions-" + period + ".csv": csvData.Bytes(),
+	}
+
+	destination := settings.ReportArchive.Reveal()
+	for name, data := range files {
+		var err error
+		if strings.HasPrefix(destination, "http://") || strings.HasPrefix(destination, "https://") {
+			err = uploadWebDAV(settings.ReportArchive, name, data)
+		} else {
+			err = writeArchiveFile(destination, name, data)
+		}
+		if err != nil {
+			return fmt.Errorf("error archiving %s: %w", name, err)
+		}
+	}
+
+	log.Info().Stringer("destination", settings.ReportArchive).Str("period", period).Msg("🗄️ Archived summary and transactions")
+	return nil
+}
+
+// writeArchiveFile writes a report file into a local archive directory
+func writeArchiveFile(dir string, name string, data []byte) error {
+	if err := os.MkdirAll(dir, 0o700); err != nil {
+		return fmt.Errorf("error creating archive directory: %w", err)
+	}
+	return writeFileAtomic(filepath.Join(dir, name), data)
+}
+
+// uploadWebDAV uploads a report file to a WebDAV collection with a PUT request. Credentials are
+// taken from the URL's user info.
+func uploadWebDAV(collection SecretURL, name string, data []byte) error {
+	base, err := collection.Parse()
+	if err != nil {
+		return fmt.Errorf("error parsing REPORT_ARCHIVE: %w", err)
+	}
+
+	target := base.JoinPath(name)
+	target.User = nil
+	req, err := http.NewRequest(http.MethodPut, target.String(), bytes.NewReader(data))
+	if err != nil {
+		return fmt.Errorf("error creating request: %w", err)
+	}
+	if base.User != nil {
+		password, _ := base.User.Password()
+		req.SetBasicAuth(base.User.Username(), password)
+	}
+
+	resp, err := newHTTPClient(60 * time.Second).Do(req)
+	if err != nil {
+		return fmt.Errorf("error uploading to WebDAV: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		body, _ := io.ReadAll(resp.Body)
+		return fmt.Errorf("WebDAV upload failed with status %d: %s", resp.StatusCode, string(body))
+	}
+	return nil
+}
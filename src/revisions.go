@@ -0,0 +1,203 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// transactionRevisionsFile is the name of the JSON file holding transaction revisions inside the cache directory
+const transactionRevisionsFile = "transaction_revisions.json"
+
+// transactionSnapshotRetention is how long a transaction's last seen version is kept after it stops being fetched
+const transactionSnapshotRetention = 400 * 24 * time.Hour
+
+// maxTransactionRevisions is the number of revisions kept, oldest dropped first
+const maxTransactionRevisions = 1000
+
+// TransactionSnapshot is the last seen version of a transaction's bank-provided fields
+type TransactionSnapshot struct {
+	Description string    `json:"description"`
+	Amount      Balance   `json:"amount"`
+	Posted      int64     `json:"posted"`
+	SeenAt      time.Time `json:"seen_at"`
+}
+
+// TransactionRevision records a change the bank made to a transaction after it was first seen
+type TransactionRevision struct {
+	TransactionID string    `json:"transaction_id"`
+	Account       string    `json:"account"`
+	Field         string    `json:"field"` // description, amount or posted
+	Old           string    `json:"old"`
+	New           string    `json:"new"`
+	DetectedAt    time.Time `json:"detected_at"`
+}
+
+// TransactionRevisions holds the last seen version of each transaction and the changes detected
+type TransactionRevisions struct {
+	path        string
+	Snapshots   map[string]TransactionSnapshot `json:"snapshots"` // Transaction ID -> last seen version
+	Revisions   []TransactionRevision          `json:"revisions"`
+	FetchedFrom time.Time                      `json:"fetched_from"` // Start of the earliest window a run recorded
+}
+
+// loadTransactionRevisions loads the transaction revisions from the cache directory, returning nil
+// if no cache directory is configured or the file can't be read
+func loadTransactionRevisions(cacheDir string) *TransactionRevisions {
+	if cacheDir == "" {
+		return nil
+	}
+
+	revisions := &TransactionRevisions{
+		path:      filepath.Join(cacheDir, transactionRevisionsFile),
+		Snapshots: make(map[string]TransactionSnapshot),
+	}
+
+	data, err := os.ReadFile(revisions.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return revisions
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("revisions_path", revisions.path).Msg("Failed to read transaction revisions, continuing without them")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, revisions); err != nil {
+		log.Warn().Err(err).Str("revisions_path", revisions.path).Msg("Failed to parse transaction revisions, starting fresh")
+		revisions.Revisions = nil
+	}
+	if revisions.Snapshots == nil {
+		revisions.Snapshots = make(map[string]TransactionSnapshot)
+	}
+
+	return revisions
+}
+
+// Unseen returns the IDs of the fetched transactions that are new since the previous run: without
+// a snapshot, and pending or posted since the start of the windows earlier runs fetched. Older
+// transactions brought in by a wider window (e.g. a last_3_months run after daily ones) were
+// never fetched rather than new. Before the first snapshot is taken nothing is reported, so the
+// first run doesn't treat the whole fetch window as new.
+func (r *TransactionRevisions) Unseen(accounts []Account) map[string]bool {
+	if r == nil || len(r.Snapshots) == 0 {
+		return nil
+	}
+
+	// Files written before the window was recorded start from their earliest posted transaction
+	fetchedFrom := r.FetchedFrom.Unix()
+	if r.FetchedFrom.IsZero() {
+		fetchedFrom = 0
+		for _, snapshot := range r.Snapshots {
+			if snapshot.Posted != 0 && (fetchedFrom == 0 || snapshot.Posted < fetchedFrom) {
+				fetchedFrom = snapshot.Posted
+			}
+		}
+	}
+
+	unseen := make(map[string]bool)
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if _, seen := r.Snapshots[tx.ID]; seen {
+				continue
+			}
+			if getTransactionStatus(tx) == "pending" || tx.Posted >= fetchedFrom {
+				unseen[tx.ID] = true
+			}
+		}
+	}
+	return unseen
+}
+
+// Record compares the transactions fetched from fetchStart with their last seen versions, records
+// a revision for each changed description, amount or posted date, and updates the snapshots. It
+// returns the number of revisions recorded.
+func (r *TransactionRevisions) Record(accounts []Account, fetchStart, now time.Time) int {
+	if r == nil {
+		return 0
+	}
+
+	if r.FetchedFrom.IsZero() || fetchStart.Before(r.FetchedFrom) {
+		r.FetchedFrom = fetchStart.UTC()
+	}
+
+	recorded := 0
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			current := TransactionSnapshot{Description: tx.Description, Amount: tx.Amount, Posted: tx.Posted, SeenAt: now}
+			previous, seen := r.Snapshots[tx.ID]
+			r.Snapshots[tx.ID] = current
+			if !seen {
+				continue
+			}
+
+			revision := TransactionRevision{TransactionID: tx.ID, Account: accountDisplayName(account), DetectedAt: now}
+			var changes []TransactionRevision
+			if previous.Description != current.Description {
+				revision.Field, revision.Old, revision.New = "description", previous.Description, current.Description
+				changes = append(changes, revision)
+			}
+			if toMoney(previous.Amount) != toMoney(current.Amount) {
+				revision.Field, revision.Old, revision.New = "amount", toMoney(previous.Amount).String(), toMoney(current.Amount).String()
+				changes = append(changes, revision)
+			}
+			if previous.Posted != current.Posted && previous.Posted != 0 && current.Posted != 0 {
+				revision.Field = "posted"
+				revision.Old = time.Unix(previous.Posted, 0).In(periodLocation).Format("2006-01-02")
+				revision.New = time.Unix(current.Posted, 0).In(periodLocation).Format("2006-01-02")
+				if revision.Old != revision.New {
+					changes = append(changes, revision)
+				}
+			}
+
+			for _, change := range changes {
+				log.Info().
+					Str("transaction_id", change.TransactionID).
+					Str("field", change.Field).
+					Str("old", change.Old).
+					Str("new", change.New).
+					Msg("✏️ Bank revised a transaction")
+			}
+			r.Revisions = append(r.Revisions, changes...)
+			recorded += len(changes)
+		}
+	}
+
+	// Forget transactions that haven't been fetched for a long time and cap the revision log
+	for id, snapshot := range r.Snapshots {
+		if now.Sub(snapshot.SeenAt) > transactionSnapshotRetention {
+			delete(r.Snapshots, id)
+		}
+	}
+	if len(r.Revisions) > maxTransactionRevisions {
+		r.Revisions = r.Revisions[len(r.Revisions)-maxTransactionRevisions:]
+	}
+
+	return recorded
+}
+
+// Save writes the transaction revisions to disk
+func (r *TransactionRevisions) Save() error {
+	if r == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(r, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling transaction revisions: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(r.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(r.path, data); err != nil {
+		return fmt.Errorf("error writing transaction revisions: %w", err)
+	}
+
+	return nil
+}
@@ -0,0 +1,109 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// runHistoryFile is the name of the JSON file holding the run history inside the cache directory
+const runHistoryFile = "runs.json"
+
+// maxRunHistory is the number of most recent runs kept in the history
+const maxRunHistory = 100
+
+// RunRecord describes a single finished run of a command
+type RunRecord struct {
+	Command    string    `json:"command"`
+	StartedAt  time.Time `json:"started_at"`
+	DurationMS int64     `json:"duration_ms"`
+	Status     string    `json:"status"` // "success", "partial" (some accounts failed to sync) or "failed"
+	Error      string    `json:"error,omitempty"`
+	SyncIssues []string  `json:"sync_issues,omitempty"` // Per-account errors reported by SimpleFin
+}
+
+// loadRunHistory reads the run history from the cache directory, oldest first
+func loadRunHistory(cacheDir string) ([]RunRecord, error) {
+	data, err := os.ReadFile(filepath.Join(cacheDir, runHistoryFile))
+	if errors.Is(err, os.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("error reading run history: %w", err)
+	}
+
+	var records []RunRecord
+	if err := json.Unmarshal(data, &records); err != nil {
+		return nil, fmt.Errorf("error parsing run history: %w", err)
+	}
+	return records, nil
+}
+
+// recordRun appends a run to the history, keeping only the most recent runs. Failures to
+// record are logged and never affect the run itself.
+func recordRun(cacheDir string, command string, startedAt time.Time, runErr error, syncIssues []string) {
+	if cacheDir == "" {
+		return
+	}
+
+	record := RunRecord{
+		Command:    command,
+		StartedAt:  startedAt.UTC(),
+		DurationMS: time.Since(startedAt).Milliseconds(),
+		Status:     "success",
+		SyncIssues: syncIssues,
+	}
+	if runErr != nil {
+		record.Status = "failed"
+		record.Error = runErr.Error()
+	} else if len(syncIssues) > 0 {
+		record.Status = "partial"
+	}
+
+	if err := os.MkdirAll(cacheDir, 0o755); err != nil {
+		log.Warn().Err(err).Msg("Failed to create cache directory for run history")
+		return
+	}
+
+	// Hold the lock across read and write so overlapping runs don't drop each other's records
+	path := filepath.Join(cacheDir, runHistoryFile)
+	unlock, err := lockFile(path)
+	if err != nil {
+		log.Warn().Err(err).Msg("Failed to lock run history")
+		return
+	}
+	defer unlock()
+
+	records, err := loadRunHistory(cacheDir)
+	if err != nil {
+		log.Warn().Err(err).Msg("Failed to load run history, starting fresh")
+		records = nil
+	}
+
+	records = append(records, record)
+	if len(records) > maxRunHistory {
+		records = records[len(records)-maxRunHistory:]
+	}
+
+	data, err := json.MarshalIndent(records, "", "  ")
+	if err != nil {
+		log.Warn().Err(err).Msg("Failed to encode run history")
+		return
+	}
+
+	if err := writeFileAtomic(path, data); err != nil {
+		log.Warn().Err(err).Msg("Failed to write run history")
+		return
+	}
+
+	log.Debug().
+		Str("command", command).
+		Str("status", record.Status).
+		Int64("duration_ms", record.DurationMS).
+		Msg("Recorded run in history")
+}
@@ -0,0 +1,135 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"syscall"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// runLockFile is the name of the lock file held by a running summary inside the cache directory.
+// Every profile shares it, since they all update the same state files.
+const runLockFile = "run.lock"
+
+// runLockMaxAge is the age after which a run lock is considered abandoned even if its process
+// still exists (e.g. the PID was reused)
+const runLockMaxAge = 2 * time.Hour
+
+// RunLock describes the run holding the run lock
+type RunLock struct {
+	PID       int       `json:"pid"`
+	Command   string    `json:"command"`
+	Profile   string    `json:"profile,omitempty"`
+	StartedAt time.Time `json:"started_at"`
+}
+
+// stale reports whether the lock was left behind by a run that is no longer running
+func (l RunLock) stale(now time.Time) bool {
+	if now.Sub(l.StartedAt) > runLockMaxAge {
+		return true
+	}
+	process, err := os.FindProcess(l.PID)
+	if err != nil {
+		return true
+	}
+	err = process.Signal(syscall.Signal(0))
+	return err != nil && !errors.Is(err, os.ErrPermission)
+}
+
+// readRunLock returns the run holding the run lock, if the lock exists and can be parsed
+func readRunLock(cacheDir string) (RunLock, bool) {
+	var lock RunLock
+	if cacheDir == "" {
+		return lock, false
+	}
+	data, err := os.ReadFile(filepath.Join(cacheDir, runLockFile))
+	if err != nil {
+		return lock, false
+	}
+	if err := json.Unmarshal(data, &lock); err != nil {
+		return lock, false
+	}
+	return lock, true
+}
+
+// acquireRunLock makes sure only one run of the command is in progress, for any profile, since
+// overlapping runs (e.g. cron and a manual run) would send duplicate notifications and race on
+// the state files. Unlike lockFile it doesn't wait: if another live run holds the lock, an error
+// is returned unless force is set. Locks left by runs that died are broken; a lock that can't be
+// parsed may belong to a run that has just created it, so it is only broken once it is older than
+// runLockMaxAge. The returned function releases the lock if this run still holds it.
+func acquireRunLock(cacheDir, command, profile string, force bool) (func(), error) {
+	if cacheDir == "" {
+		return func() {}, nil
+	}
+	if err := os.MkdirAll(cacheDir, 0o755); err != nil {
+		return nil, fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	lockPath := filepath.Join(cacheDir, runLockFile)
+	pid := os.Getpid()
+	data, err := json.Marshal(RunLock{PID: pid, Command: command, Profile: profile, StartedAt: time.Now().UTC()})
+	if err != nil {
+		return nil, fmt.Errorf("error marshaling run lock: %w", err)
+	}
+
+	for attempt := 0; attempt < 2; attempt++ {
+		f, err := os.OpenFile(lockPath, os.O_CREATE|os.O_EXCL|os.O_WRONLY, 0o600)
+		if err == nil {
+			_, writeErr := f.Write(data)
+			f.Close()
+			if writeErr != nil {
+				os.Remove(lockPath)
+				return nil, fmt.Errorf("error writing run lock: %w", writeErr)
+			}
+			return func() {
+				// A run started with --force may have taken the lock over; leave its lock alone
+				if holder, ok := readRunLock(cacheDir); !ok || holder.PID != pid {
+					log.Warn().Str("lock_path", lockPath).Msg("Run lock was taken over by another run, leaving it")
+					return
+				}
+				if err := os.Remove(lockPath); err != nil && !errors.Is(err, os.ErrNotExist) {
+					log.Warn().Err(err).Str("lock_path", lockPath).Msg("Failed to release run lock")
+				}
+			}, nil
+		}
+		if !errors.Is(err, os.ErrExist) {
+			return nil, fmt.Errorf("error creating run lock: %w", err)
+		}
+
+		holder, ok := readRunLock(cacheDir)
+		if _, err := os.Stat(lockPath); errors.Is(err, os.ErrNotExist) {
+			continue // Released in the meantime
+		}
+		switch {
+		case force:
+			log.Warn().Int("pid", holder.PID).Msg("Overriding the run lock held by another run (--force)")
+		case ok && holder.stale(time.Now()):
+			log.Warn().Int("pid", holder.PID).Msg("Removing stale run lock")
+		case !ok && unreadableLockStale(lockPath):
+			log.Warn().Str("lock_path", lockPath).Msg("Removing stale unreadable run lock")
+		case !ok:
+			return nil, fmt.Errorf("another %s run is in progress (run lock %s is being written); use --force to run anyway",
+				command, lockPath)
+		default:
+			return nil, fmt.Errorf("another %s run (pid %d, started %s) is in progress; use --force to run anyway",
+				holder.Command, holder.PID, holder.StartedAt.Local().Format("2006-01-02 15:04:05"))
+		}
+		if err := os.Remove(lockPath); err != nil && !errors.Is(err, os.ErrNotExist) {
+			return nil, fmt.Errorf("error removing run lock: %w", err)
+		}
+	}
+	return nil, fmt.Errorf("could not acquire run lock %s", lockPath)
+}
+
+// unreadableLockStale reports whether a run lock that can't be parsed was last written more than
+// runLockMaxAge ago, so it can't be the lock of a run still writing it
+func unreadableLockStale(lockPath string) bool {
+	info, err := os.Stat(lockPath)
+	return err == nil && time.Since(info.ModTime()) > runLockMaxAge
+}
@@ -0,0 +1,81 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"net/url"
+)
+
+// redactedPlaceholder replaces secret values in logs, errors and JSON output
+const redactedPlaceholder = "[REDACTED]"
+
+// Secret holds a credential such as an API key. Formatting, logging or marshaling it prints a
+// placeholder; use Reveal where the actual value is needed (e.g. request headers).
+type Secret string
+
+// Reveal returns the secret value
+func (s Secret) Reveal() string {
+	return string(s)
+}
+
+// String masks the secret so it never ends up in logs or error messages
+func (s Secret) String() string {
+	if s == "" {
+		return ""
+	}
+	return redactedPlaceholder
+}
+
+// GoString masks the secret for %#v formatting
+func (s Secret) GoString() string {
+	return s.String()
+}
+
+// MarshalJSON masks the secret in JSON output, including zerolog's Interface fields
+func (s Secret) MarshalJSON() ([]byte, error) {
+	return json.Marshal(s.String())
+}
+
+// SecretURL holds a URL that may embed credentials (e.g. basic auth in a SimpleFin access URL).
+// Formatting, logging or marshaling it masks the password.
+type SecretURL string
+
+// Reveal returns the full URL including credentials
+func (u SecretURL) Reveal() string {
+	return string(u)
+}
+
+// String returns the URL with its password masked
+func (u SecretURL) String() string {
+	parsed, err := url.Parse(string(u))
+	if err != nil {
+		if u == "" {
+			return ""
+		}
+		return redactedPlaceholder
+	}
+	return parsed.Redacted()
+}
+
+// GoString masks the URL's password for %#v formatting
+func (u SecretURL) GoString() string {
+	return u.String()
+}
+
+// MarshalJSON masks the URL's password in JSON output
+func (u SecretURL) MarshalJSON() ([]byte, error) {
+	return json.Marshal(u.String())
+}
+
+// Parse parses the URL. Parse errors don't include the URL itself, since it may hold credentials.
+func (u SecretURL) Parse() (*url.URL, error) {
+	parsed, err := url.Parse(string(u))
+	if err != nil {
+		var urlErr *url.Error
+		if errors.As(err, &urlErr) {
+			return nil, urlErr.Err
+		}
+		return nil, errors.New("invalid URL")
+	}
+	return parsed, nil
+}
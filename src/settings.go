@@ -1,7 +1,13 @@
 package main
 
 import (
+	"fmt"
 	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
 
 	"github.com/joho/godotenv"
 	"github.com/rs/zerolog/log"
@@ -10,37 +16,118 @@ import (
 
 // Settings holds the application configuration
 type Settings struct {
-	SimplefinBridgeURL string
-	OpenRouterURL      string
-	OpenRouterAPIKey   string
-	OpenRouterModel    string
-	NtfyServer         string
-	MailerURL          *string
-	MailerFrom         *string
-	MailerTo           *string
-	NtfyTopic          *string
-	NtfyWarningSuffix  string  // Suffix appended to NtfyTopic for warning notifications (default: "-warning")
-	FilterConfigPath   *string // Path to YAML file with transaction filter rules (optional)
+	SimplefinBridgeURL   SecretURL
+	OpenRouterURL        string
+	OpenRouterAPIKey     Secret
+	OpenRouterModel      string
+	VisionModel          string   // OpenRouter model(s) with image input, used by `transactions receipt` (optional)
+	LLMCategorize        bool     // Categorize uncategorized expenses with the LLM before the summary (default: false)
+	CategorizeBatchSize  int      // Transactions per LLM categorization request (default: 50)
+	CategorizeWorkers    int      // Concurrent LLM categorization requests (default: 3)
+	LocalLLMURL          string   // OpenAI-compatible chat completions endpoint of a local model server such as Ollama
+	LocalLLMModel        string   // Local model used for the tasks in LocalLLMTasks
+	LocalLLMTasks        []string // LLM tasks run against the local model instead of OpenRouter (optional)
+	NtfyServer           string
+	MailerURL            *SecretURL
+	MailerFrom           *string
+	MailerTo             *string        // Comma-separated recipient addresses
+	MailerCC             []string       // Cc addresses of summary emails (optional)
+	MailerBCC            []string       // Bcc addresses of summary emails (optional)
+	MailerTLSMode        SMTPTLSMode    // SMTP encryption: auto, starttls, tls or none (default: "auto")
+	MailerCAFile         string         // PEM file with extra CAs trusted for the SMTP server, e.g. a self-signed one (optional)
+	MailerSkipVerify     bool           // Skip SMTP certificate verification entirely (default: false)
+	MailerTimeout        time.Duration  // Limit for connecting and for the whole SMTP session (default: 30s)
+	EmailAttachCSV       bool           // Attach the period's transactions as CSV to summary emails (default: false)
+	EmailSubject         string         // Go text/template for email subjects (default: the locale's subject)
+	NotificationTitle    string         // Go text/template for ntfy and Gotify titles (default: "💰 Finance Tracker")
+	HouseholdName        string         // Household name available to subject and title templates as .Household (optional)
+	NtfyTopic            *string
+	NtfyWarningSuffix    string         // Suffix appended to NtfyTopic for warning notifications (default: "-warning")
+	GotifyURL            string         // Gotify server base URL (optional)
+	GotifyToken          Secret         // Gotify application token (optional)
+	GotifyPriority       int            // Gotify priority of summaries and other regular notifications (default: 5)
+	GotifyWarnPriority   int            // Gotify priority of warning notifications (default: 8)
+	QuietHours           *QuietHours    // Daily window in TIMEZONE when warnings are held back (optional)
+	RateLimits           []RateLimit    // Maximum notifications per channel and event type within a period (optional)
+	BatchWindow          *time.Duration // Warnings of a run are sent as one message per channel, once the oldest waited this long (optional)
+	BatchWarnings        bool           // Queue warnings for flushWarnings; set by the run collecting them, not the environment
+	FilterConfigPath     *string        // Path to YAML file with transaction filter rules (optional)
+	Currency             string         // ISO 4217 report currency used for minor-unit rounding (default: "USD")
+	RoundingMode         RoundingMode   // Rounding strategy for monetary aggregates (default: "half_up")
+	CacheDir             string         // Directory for cached LLM summaries (empty disables caching)
+	CacheBackend         string         // Summary cache backend: "json" or "none" (default: "json")
+	TemplatesDir         string         // Directory with custom notification templates (email.html, ntfy.txt) (optional)
+	AttachmentsDir       string         // Directory for transaction attachments (default: CACHE_DIR/attachments)
+	ReportArchive        SecretURL      // Local directory or WebDAV URL where each summary and transaction CSV is archived (optional)
+	BillingCycleDay      int            // Default billing cycle start day when --billing-day isn't set (default: 15)
+	SummaryCadence       SummaryCadence // Default summary cadence when --cadence isn't set (default: "monthly")
+	Timezone             string         // IANA time zone for billing periods and transaction days (default: "Local", the system time zone)
+	Locale               Locale         // Language and formats of summaries and notifications: en, fr, pt or es (default: "en")
+	StaleAccountDays     int            // Days without a sync before an account is reported as stale (default: 2, 0 disables)
+	SubscriptionMonths   int            // Months of expenses before the period checked for subscription changes, widening the SimpleFin fetch (default: 0)
+	InterestYearToDate   bool           // Add interest earned since January 1 to the summary, widening the SimpleFin fetch (default: false)
+	UtilizationThreshold float64        // Credit utilization percentage that triggers a warning (default: 30, 0 disables)
+	SimplefinMaxDays     int            // Longest period fetched from SimpleFin in one request; longer periods are split (default: 90)
+	BankLogoService      string         // Favicon service URL the bank's domain is appended to for logos; empty uses the bank's own /favicon.ico (optional)
+	RedactAccountNumbers bool           // Mask card/account numbers in transaction data before it is logged or rendered (default: true)
+	LogFormat            string         // Log output format, console or json; --log-format takes precedence (default: "console")
+	YNABToken            Secret         // YNAB personal access token for `ynab push` (optional)
+	YNABBudgetID         string         // YNAB budget to push transactions into (optional)
+	FireflyURL           string         // Firefly III base URL for `firefly sync` (optional)
+	FireflyToken         Secret         // Firefly III personal access token (optional)
+	WebhookURLs          []SecretURL    // URLs POSTed a JSON event on new transactions, balance changes and summaries (optional)
+	WebhookEvents        []string       // Events sent to the webhooks (default: all)
+	WebhookSecret        Secret         // Key for the X-Finance-Tracker-Signature HMAC-SHA256 header (optional)
+	EventBusURL          SecretURL      // Redis or NATS URL the webhook events are also published to (optional)
+	EventBus             *EventBus      // Connection the run's events are published on; opened by the first event, not the environment
 }
 
-// NewSettings creates a new Settings instance from environment variables
-func NewSettings(env_file string) (*Settings, error) {
+// NewSettings creates a new Settings instance from environment variables, the .env file and the
+// config file, in that order of precedence. An empty configFile uses the default location.
+func NewSettings(env_file string, configFile string) (*Settings, error) {
 	// Try to load .env file, but don't error if it doesn't exist
 	if err := godotenv.Load(env_file); err != nil {
 		log.Info().Str("env_file", env_file).Str("error", err.Error()).Msg("No .env file found, using environment variables")
 	}
 
+	// Fill in anything not set in the environment from the config file
+	explicitConfig := configFile != ""
+	if !explicitConfig {
+		configFile = defaultConfigFile()
+	}
+	if err := applyConfigFile(configFile, explicitConfig); err != nil {
+		return nil, err
+	}
+
 	settings := &Settings{
-		SimplefinBridgeURL: os.Getenv("SIMPLEFIN_BRIDGE_URL"),
-		OpenRouterURL:      os.Getenv("OPENROUTER_URL"),
-		OpenRouterAPIKey:   os.Getenv("OPENROUTER_API_KEY"),
-		OpenRouterModel:    os.Getenv("OPENROUTER_MODEL"),
-		NtfyServer:         "https://ntfy.sh",
-		NtfyWarningSuffix:  "-warning", // Default suffix for warning notifications
+		SimplefinBridgeURL:   SecretURL(os.Getenv("SIMPLEFIN_BRIDGE_URL")),
+		OpenRouterURL:        os.Getenv("OPENROUTER_URL"),
+		OpenRouterAPIKey:     Secret(os.Getenv("OPENROUTER_API_KEY")),
+		OpenRouterModel:      os.Getenv("OPENROUTER_MODEL"),
+		VisionModel:          os.Getenv("OPENROUTER_VISION_MODEL"),
+		CategorizeBatchSize:  50,
+		CategorizeWorkers:    3,
+		NtfyServer:           "https://ntfy.sh",
+		MailerTLSMode:        SMTPTLSModeAuto,
+		MailerTimeout:        30 * time.Second,
+		NtfyWarningSuffix:    "-warning", // Default suffix for warning notifications
+		GotifyPriority:       5,
+		GotifyWarnPriority:   8,
+		Currency:             "USD",
+		RoundingMode:         RoundingModeHalfUp,
+		BillingCycleDay:      15,
+		SummaryCadence:       SummaryCadenceMonthly,
+		Timezone:             "Local",
+		Locale:               LocaleEnglish,
+		StaleAccountDays:     2,
+		UtilizationThreshold: 30,
+		SimplefinMaxDays:     90,
+		CacheBackend:         CacheBackendJSON,
+		RedactAccountNumbers: true,
 	}
 
 	// Optional fields
-	if mailerURL := os.Getenv("MAILER_URL"); mailerURL != "" {
+	if mailerURL := SecretURL(os.Getenv("MAILER_URL")); mailerURL != "" {
 		settings.MailerURL = &mailerURL
 	}
 	if mailerFrom := os.Getenv("MAILER_FROM"); mailerFrom != "" {
@@ -49,6 +136,85 @@ func NewSettings(env_file string) (*Settings, error) {
 	if mailerTo := os.Getenv("MAILER_TO"); mailerTo != "" {
 		settings.MailerTo = &mailerTo
 	}
+	settings.MailerCC = parseAddressList(os.Getenv("MAILER_CC"))
+	settings.MailerBCC = parseAddressList(os.Getenv("MAILER_BCC"))
+	// SMTP transport (optional)
+	if tlsMode := os.Getenv("MAILER_TLS_MODE"); tlsMode != "" {
+		mode, err := parseSMTPTLSMode(tlsMode)
+		if err != nil {
+			return nil, fmt.Errorf("invalid MAILER_TLS_MODE: %w", err)
+		}
+		settings.MailerTLSMode = mode
+	}
+	settings.MailerCAFile = os.Getenv("MAILER_TLS_CA_FILE")
+	if skipVerify := os.Getenv("MAILER_TLS_SKIP_VERIFY"); skipVerify != "" {
+		enabled, err := strconv.ParseBool(skipVerify)
+		if err != nil {
+			return nil, fmt.Errorf("invalid MAILER_TLS_SKIP_VERIFY %q: %w", skipVerify, err)
+		}
+		settings.MailerSkipVerify = enabled
+	}
+	// Local model for privacy-sensitive tasks (optional)
+	settings.LocalLLMModel = os.Getenv("LOCAL_LLM_MODEL")
+	settings.LocalLLMURL = os.Getenv("LOCAL_LLM_URL")
+	if settings.LocalLLMURL == "" {
+		settings.LocalLLMURL = defaultLocalLLMURL
+	}
+	if tasks := os.Getenv("LOCAL_LLM_TASKS"); tasks != "" {
+		parsed, err := parseLLMTasks(tasks)
+		if err != nil {
+			return nil, fmt.Errorf("invalid LOCAL_LLM_TASKS: %w", err)
+		}
+		if settings.LocalLLMModel == "" {
+			return nil, fmt.Errorf("LOCAL_LLM_TASKS is set but LOCAL_LLM_MODEL is missing")
+		}
+		settings.LocalLLMTasks = parsed
+	}
+	// LLM categorization (optional)
+	if categorize := os.Getenv("LLM_CATEGORIZE"); categorize != "" {
+		enabled, err := strconv.ParseBool(categorize)
+		if err != nil {
+			return nil, fmt.Errorf("invalid LLM_CATEGORIZE %q: %w", categorize, err)
+		}
+		settings.LLMCategorize = enabled
+	}
+	if batchSize := os.Getenv("LLM_CATEGORIZE_BATCH_SIZE"); batchSize != "" {
+		value, err := strconv.Atoi(batchSize)
+		if err != nil || value < 1 {
+			return nil, fmt.Errorf("invalid LLM_CATEGORIZE_BATCH_SIZE %q: must be a positive number", batchSize)
+		}
+		settings.CategorizeBatchSize = value
+	}
+	if concurrency := os.Getenv("LLM_CATEGORIZE_CONCURRENCY"); concurrency != "" {
+		value, err := strconv.Atoi(concurrency)
+		if err != nil || value < 1 {
+			return nil, fmt.Errorf("invalid LLM_CATEGORIZE_CONCURRENCY %q: must be a positive number", concurrency)
+		}
+		settings.CategorizeWorkers = value
+	}
+	if attachCSV := os.Getenv("EMAIL_ATTACH_CSV"); attachCSV != "" {
+		enabled, err := strconv.ParseBool(attachCSV)
+		if err != nil {
+			return nil, fmt.Errorf("invalid EMAIL_ATTACH_CSV %q: %w", attachCSV, err)
+		}
+		settings.EmailAttachCSV = enabled
+	}
+	// Subject and title templates (optional)
+	settings.EmailSubject = os.Getenv("EMAIL_SUBJECT")
+	settings.NotificationTitle = os.Getenv("NOTIFICATION_TITLE")
+	settings.HouseholdName = os.Getenv("HOUSEHOLD_NAME")
+	for name, text := range map[string]string{"EMAIL_SUBJECT": settings.EmailSubject, "NOTIFICATION_TITLE": settings.NotificationTitle} {
+		if _, err := parseTitleTemplate(name, text); err != nil {
+			return nil, fmt.Errorf("invalid %s: %w", name, err)
+		}
+	}
+	if timeout := os.Getenv("MAILER_TIMEOUT"); timeout != "" {
+		duration, err := time.ParseDuration(timeout)
+		if err != nil || duration <= 0 {
+			return nil, fmt.Errorf("invalid MAILER_TIMEOUT %q: must be a positive duration such as 30s", timeout)
+		}
+		settings.MailerTimeout = duration
+	}
 	if ntfyTopic := os.Getenv("NTFY_TOPIC"); ntfyTopic != "" {
 		settings.NtfyTopic = &ntfyTopic
 	}
@@ -56,6 +222,184 @@ func NewSettings(env_file string) (*Settings, error) {
 	if ntfyWarningSuffix := os.Getenv("NTFY_WARNING_SUFFIX"); ntfyWarningSuffix != "" {
 		settings.NtfyWarningSuffix = ntfyWarningSuffix
 	}
+	// Gotify notifications (optional)
+	settings.GotifyURL = os.Getenv("GOTIFY_URL")
+	settings.GotifyToken = Secret(os.Getenv("GOTIFY_TOKEN"))
+	if priority := os.Getenv("GOTIFY_PRIORITY"); priority != "" {
+		value, err := strconv.Atoi(priority)
+		if err != nil || value < 0 || value > 10 {
+			return nil, fmt.Errorf("invalid GOTIFY_PRIORITY %q: must be a number from 0 to 10", priority)
+		}
+		settings.GotifyPriority = value
+	}
+	if priority := os.Getenv("GOTIFY_WARNING_PRIORITY"); priority != "" {
+		value, err := strconv.Atoi(priority)
+		if err != nil || value < 0 || value > 10 {
+			return nil, fmt.Errorf("invalid GOTIFY_WARNING_PRIORITY %q: must be a number from 0 to 10", priority)
+		}
+		settings.GotifyWarnPriority = value
+	}
+	// Quiet hours for warnings (optional)
+	if quietHours := os.Getenv("QUIET_HOURS"); quietHours != "" {
+		parsed, err := parseQuietHours(quietHours)
+		if err != nil {
+			return nil, fmt.Errorf("invalid QUIET_HOURS: %w", err)
+		}
+		settings.QuietHours = parsed
+	}
+	// Notification rate limits (optional)
+	if rateLimits := os.Getenv("NOTIFICATION_RATE_LIMITS"); rateLimits != "" {
+		parsed, err := parseRateLimits(rateLimits)
+		if err != nil {
+			return nil, fmt.Errorf("invalid NOTIFICATION_RATE_LIMITS: %w", err)
+		}
+		settings.RateLimits = parsed
+	}
+	// Warning batching (optional)
+	if batchWindow := os.Getenv("NOTIFICATION_BATCH_WINDOW"); batchWindow != "" {
+		window, err := time.ParseDuration(batchWindow)
+		if err != nil || window < 0 {
+			return nil, fmt.Errorf("invalid NOTIFICATION_BATCH_WINDOW %q: must be a duration such as 0s or 30m", batchWindow)
+		}
+		settings.BatchWindow = &window
+	}
+	// Money handling (optional)
+	if currency := os.Getenv("CURRENCY"); currency != "" {
+		settings.Currency = currency
+	}
+	if roundingMode := os.Getenv("ROUNDING_MODE"); roundingMode != "" {
+		settings.RoundingMode = RoundingMode(roundingMode)
+	}
+	// Default billing cycle day (optional)
+	if billingCycleDay := os.Getenv("BILLING_CYCLE_DAY"); billingCycleDay != "" {
+		day, err := strconv.Atoi(billingCycleDay)
+		if err != nil {
+			return nil, fmt.Errorf("invalid BILLING_CYCLE_DAY %q: %w", billingCycleDay, err)
+		}
+		settings.BillingCycleDay = day
+	}
+	// Summary cadence (optional)
+	if cadence := os.Getenv("SUMMARY_CADENCE"); cadence != "" {
+		settings.SummaryCadence = SummaryCadence(cadence)
+		if _, err := settings.SummaryCadence.DateRange(); err != nil {
+			return nil, err
+		}
+	}
+	// Time zone for period calculations (optional)
+	if timezone := os.Getenv("TIMEZONE"); timezone != "" {
+		settings.Timezone = timezone
+	}
+	// Locale for summaries and notifications (optional)
+	if locale := os.Getenv("LOCALE"); locale != "" {
+		settings.Locale = Locale(locale)
+	}
+	// Log output format (optional)
+	if format := os.Getenv("LOG_FORMAT"); format != "" {
+		if format != "console" && format != "json" {
+			return nil, fmt.Errorf("invalid LOG_FORMAT %q (supported: console, json)", format)
+		}
+		settings.LogFormat = format
+	}
+	// Stale account threshold (optional)
+	if staleAccountDays := os.Getenv("STALE_ACCOUNT_DAYS"); staleAccountDays != "" {
+		days, err := strconv.Atoi(staleAccountDays)
+		if err != nil {
+			return nil, fmt.Errorf("invalid STALE_ACCOUNT_DAYS %q: %w", staleAccountDays, err)
+		}
+		settings.StaleAccountDays = days
+	}
+	// Months of earlier expenses checked for subscription changes (optional)
+	if months := os.Getenv("SUBSCRIPTION_HISTORY_MONTHS"); months != "" {
+		value, err := strconv.Atoi(months)
+		if err != nil || value < 0 {
+			return nil, fmt.Errorf("invalid SUBSCRIPTION_HISTORY_MONTHS %q (expected a number of months, 0 or more)", months)
+		}
+		settings.SubscriptionMonths = value
+	}
+	// Interest earned year to date (optional)
+	if yearToDate := os.Getenv("INTEREST_YEAR_TO_DATE"); yearToDate != "" {
+		enabled, err := strconv.ParseBool(yearToDate)
+		if err != nil {
+			return nil, fmt.Errorf("invalid INTEREST_YEAR_TO_DATE %q: %w", yearToDate, err)
+		}
+		settings.InterestYearToDate = enabled
+	}
+	// Third-party favicon service for bank logos (optional); it learns which banks are linked
+	settings.BankLogoService = os.Getenv("BANK_LOGO_SERVICE")
+	// Credit utilization warning threshold (optional)
+	if threshold := os.Getenv("CREDIT_UTILIZATION_THRESHOLD"); threshold != "" {
+		percent, err := strconv.ParseFloat(threshold, 64)
+		if err != nil {
+			return nil, fmt.Errorf("invalid CREDIT_UTILIZATION_THRESHOLD %q: %w", threshold, err)
+		}
+		settings.UtilizationThreshold = percent
+	}
+	// SimpleFin request window (optional)
+	if maxDays := os.Getenv("SIMPLEFIN_MAX_DAYS"); maxDays != "" {
+		days, err := strconv.Atoi(maxDays)
+		if err != nil || days < 1 {
+			return nil, fmt.Errorf("invalid SIMPLEFIN_MAX_DAYS %q: must be a positive number of days", maxDays)
+		}
+		settings.SimplefinMaxDays = days
+	}
+	// Account number redaction (optional, enabled by default)
+	if redact := os.Getenv("REDACT_ACCOUNT_NUMBERS"); redact != "" {
+		enabled, err := strconv.ParseBool(redact)
+		if err != nil {
+			return nil, fmt.Errorf("invalid REDACT_ACCOUNT_NUMBERS %q: %w", redact, err)
+		}
+		settings.RedactAccountNumbers = enabled
+	}
+	// Summary cache backend (optional)
+	if cacheBackend := os.Getenv("CACHE_BACKEND"); cacheBackend != "" {
+		switch cacheBackend {
+		case CacheBackendJSON, CacheBackendNone:
+			settings.CacheBackend = cacheBackend
+		default:
+			return nil, fmt.Errorf("invalid CACHE_BACKEND %q (supported: %s, %s)", cacheBackend, CacheBackendJSON, CacheBackendNone)
+		}
+	}
+	// Summary cache directory (defaults to the user cache directory)
+	if cacheDir := os.Getenv("CACHE_DIR"); cacheDir != "" {
+		settings.CacheDir = cacheDir
+	} else if userCacheDir, err := os.UserCacheDir(); err == nil {
+		settings.CacheDir = filepath.Join(userCacheDir, "finance_tracker")
+	}
+	// Transaction attachments directory (defaults to a subdirectory of the cache directory)
+	if attachmentsDir := os.Getenv("ATTACHMENTS_DIR"); attachmentsDir != "" {
+		settings.AttachmentsDir = attachmentsDir
+	} else if settings.CacheDir != "" {
+		settings.AttachmentsDir = filepath.Join(settings.CacheDir, "attachments")
+	}
+	// Report archive destination (optional)
+	settings.ReportArchive = SecretURL(os.Getenv("REPORT_ARCHIVE"))
+	// Custom notification templates (optional)
+	settings.TemplatesDir = os.Getenv("TEMPLATES_DIR")
+	// YNAB integration (optional)
+	settings.YNABToken = Secret(os.Getenv("YNAB_TOKEN"))
+	settings.YNABBudgetID = os.Getenv("YNAB_BUDGET_ID")
+	// Firefly III integration (optional)
+	settings.FireflyURL = os.Getenv("FIREFLY_URL")
+	settings.FireflyToken = Secret(os.Getenv("FIREFLY_TOKEN"))
+	// Outgoing webhooks (optional)
+	for _, webhookURL := range strings.Split(os.Getenv("WEBHOOK_URLS"), ",") {
+		if webhookURL = strings.TrimSpace(webhookURL); webhookURL != "" {
+			settings.WebhookURLs = append(settings.WebhookURLs, SecretURL(webhookURL))
+		}
+	}
+	for _, event := range strings.Split(os.Getenv("WEBHOOK_EVENTS"), ",") {
+		switch event = strings.TrimSpace(event); event {
+		case "":
+		case WebhookEventTransactionCreated, WebhookEventAccountBalanceChanged, WebhookEventSummaryGenerated:
+			settings.WebhookEvents = append(settings.WebhookEvents, event)
+		default:
+			return nil, fmt.Errorf("invalid WEBHOOK_EVENTS entry %q (supported: %s, %s, %s)", event,
+				WebhookEventTransactionCreated, WebhookEventAccountBalanceChanged, WebhookEventSummaryGenerated)
+		}
+	}
+	settings.WebhookSecret = Secret(os.Getenv("WEBHOOK_SECRET"))
+	// Event bus (optional)
+	settings.EventBusURL = SecretURL(os.Getenv("EVENT_BUS_URL"))
 	// Optional filter config path
 	if filterConfigPath := os.Getenv("FILTER_CONFIG_PATH"); filterConfigPath != "" {
 		settings.FilterConfigPath = &filterConfigPath
@@ -78,10 +422,87 @@ func LoadFilterConfig(configPath string) (*FilterConfig, error) {
 		return nil, err
 	}
 
+	if config.CategoryPreset != "" {
+		if _, ok := categoryPresets[strings.ToLower(config.CategoryPreset)]; !ok {
+			return nil, fmt.Errorf("unknown category_preset %q (supported: plaid)", config.CategoryPreset)
+		}
+	}
+
 	log.Debug().
 		Int("rule_count", len(config.ExcludedTransactions)).
+		Int("category_mapping_count", len(config.CategoryMappings)).
 		Str("config_path", configPath).
 		Msg("Loaded filter configuration")
 
 	return &config, nil
 }
+
+// lookupAccountSettings returns the per-account settings for an account, matched by account ID
+// or name (case-insensitive). Accounts without settings get the zero value.
+func lookupAccountSettings(account Account, filterConfig *FilterConfig) AccountSettings {
+	if filterConfig == nil {
+		return AccountSettings{}
+	}
+
+	if accountSettings, ok := filterConfig.Accounts[account.ID]; ok {
+		return accountSettings
+	}
+	for key, accountSettings := range filterConfig.Accounts {
+		if strings.EqualFold(key, account.Name) {
+			return accountSettings
+		}
+	}
+	return AccountSettings{}
+}
+
+// applyAccountDisplaySettings sets configured nicknames and sorts accounts by their display order.
+// Accounts without a display order keep their relative order after the ordered ones.
+func applyAccountDisplaySettings(accounts []Account, filterConfig *FilterConfig) {
+	if filterConfig == nil || len(filterConfig.Accounts) == 0 {
+		return
+	}
+
+	orders := make(map[string]int, len(accounts))
+	for i := range accounts {
+		accountSettings := lookupAccountSettings(accounts[i], filterConfig)
+		accounts[i].Nickname = accountSettings.Nickname
+		orders[accounts[i].ID] = accountSettings.DisplayOrder
+	}
+
+	sort.SliceStable(accounts, func(i, j int) bool {
+		a, b := orders[accounts[i].ID], orders[accounts[j].ID]
+		if a == 0 || b == 0 {
+			return a != 0 && b == 0
+		}
+		return a < b
+	})
+}
+
+// accountDisplayName returns the account's nickname if configured, else its bank name
+func accountDisplayName(account Account) string {
+	if account.Nickname != "" {
+		return account.Nickname
+	}
+	return account.Name
+}
+
+// excludeAccounts removes accounts that are excluded from the given scope
+func excludeAccounts(accounts []Account, filterConfig *FilterConfig, scope AccountScope) []Account {
+	if filterConfig == nil || len(filterConfig.Accounts) == 0 {
+		return accounts
+	}
+
+	var included []Account
+	for _, account := range accounts {
+		if lookupAccountSettings(account, filterConfig).IsExcludedFrom(scope) {
+			log.Debug().
+				Str("account_id", account.ID).
+				Str("account_name", account.Name).
+				Str("scope", string(scope)).
+				Msg("Excluded account by configuration")
+			continue
+		}
+		included = append(included, account)
+	}
+	return included
+}
@@ -1,12 +1,16 @@
 package main
 
 import (
+	"crypto/sha256"
+	"encoding/hex"
 	"encoding/json"
 	"fmt"
 	"io"
 	"net/http"
+	"net/url"
 	"time"
 
+	"github.com/rs/zerolog"
 	"github.com/rs/zerolog/log"
 )
 
@@ -21,54 +25,217 @@ func getTransactionStatus(tx Transaction) string {
 // getTransactionTime returns the formatted time of a transaction
 func getTransactionTime(tx Transaction) string {
 	if tx.TransactedAt != nil {
-		return time.Unix(*tx.TransactedAt, 0).Format("2006-01-02 15:04:05")
+		return time.Unix(*tx.TransactedAt, 0).In(periodLocation).Format("2006-01-02 15:04:05")
 	}
 	return "not available"
 }
 
-// getTransactionsForPeriod fetches transactions from the SimpleFin bridge for the specified date range
+// transactionTime returns when a transaction occurred, preferring TransactedAt over Posted, in
+// the configured period time zone
+func transactionTime(tx Transaction) time.Time {
+	if tx.TransactedAt != nil {
+		return time.Unix(*tx.TransactedAt, 0).In(periodLocation)
+	}
+	return time.Unix(tx.Posted, 0).In(periodLocation)
+}
+
+// organizationLogoURL returns the logo URL for the organization's domain (or the host of its
+// website URL): the favicon on the bank's own site, or the domain appended to logoService when
+// BANK_LOGO_SERVICE opts into a third-party favicon service. Returns "" if neither is known.
+func organizationLogoURL(org Organization, logoService string) string {
+	domain := getStringValue(org.Domain)
+	if domain == "" && org.URL != nil {
+		if parsed, err := url.Parse(*org.URL); err == nil {
+			domain = parsed.Hostname()
+		}
+	}
+	if domain == "" {
+		return ""
+	}
+	if logoService != "" {
+		return logoService + url.QueryEscape(domain)
+	}
+	return (&url.URL{Scheme: "https", Host: domain, Path: "/favicon.ico"}).String()
+}
+
+// stableTransactionID derives a short (35 character) ID from the SimpleFin account and
+// transaction IDs, for external systems that deduplicate imports by ID
+func stableTransactionID(accountID string, transactionID string) string {
+	sum := sha256.Sum256([]byte(accountID + ":" + transactionID))
+	return "FT:" + hex.EncodeToString(sum[:])[:32]
+}
+
+// getTransactionsForPeriod fetches transactions from the SimpleFin bridge for the specified date
+// range. Ranges longer than SIMPLEFIN_MAX_DAYS are fetched in consecutive chunks, oldest first,
+// and merged: each account keeps the balance from the latest chunk and the transactions of all
+// chunks, without duplicates.
 func getTransactionsForPeriod(settings *Settings, startDate, endDate time.Time) ([]Account, []string, error) {
-	startTS := startDate.Unix()
-	endTS := endDate.Unix()
+	maxWindow := time.Duration(settings.SimplefinMaxDays) * 24 * time.Hour
+	if maxWindow <= 0 || endDate.Sub(startDate) <= maxWindow {
+		return getTransactionsForWindow(settings, startDate, endDate)
+	}
 
-	url := fmt.Sprintf("%s/accounts?start-date=%d&end-date=%d", settings.SimplefinBridgeURL, startTS, endTS)
-	log.Debug().Str("url", url).Msg("Fetching transactions from SimpleFin bridge")
+	merged := newAccountMerger()
+	for chunkStart := startDate; chunkStart.Before(endDate); chunkStart = chunkStart.Add(maxWindow) {
+		chunkEnd := chunkStart.Add(maxWindow)
+		if chunkEnd.After(endDate) {
+			chunkEnd = endDate
+		}
 
-	client := &http.Client{
-		Timeout: 120 * time.Second,
+		chunkAccounts, chunkErrors, err := getTransactionsForWindow(settings, chunkStart, chunkEnd)
+		if err != nil {
+			return nil, nil, fmt.Errorf("error fetching %s to %s: %w", chunkStart.Format("2006-01-02"), chunkEnd.Format("2006-01-02"), err)
+		}
+
+		merged.Add(chunkAccounts, chunkErrors)
 	}
 
+	log.Debug().
+		Int("account_count", len(merged.Accounts)).
+		Int("chunk_days", settings.SimplefinMaxDays).
+		Msg("Merged SimpleFin chunks")
+	return merged.Accounts, merged.Errors, nil
+}
+
+// accountMerger combines accounts fetched for several date ranges
+type accountMerger struct {
+	Accounts     []Account
+	Errors       []string
+	index        map[string]int
+	seenErrors   map[string]bool
+	transactions map[string]bool
+}
+
+// newAccountMerger returns an empty accountMerger
+func newAccountMerger() *accountMerger {
+	return &accountMerger{
+		index:        make(map[string]int),
+		seenErrors:   make(map[string]bool),
+		transactions: make(map[string]bool),
+	}
+}
+
+// Add merges the accounts and errors of one fetch. Each account keeps the details (balance,
+// name...) of its most recent fetch and the transactions of all fetches, without duplicates.
+func (m *accountMerger) Add(accounts []Account, apiErrors []string) {
+	for _, apiErr := range apiErrors {
+		if !m.seenErrors[apiErr] {
+			m.seenErrors[apiErr] = true
+			m.Errors = append(m.Errors, apiErr)
+		}
+	}
+
+	for _, account := range accounts {
+		var transactions []Transaction
+		for _, tx := range account.Transactions {
+			if key := account.ID + "/" + tx.ID; !m.transactions[key] {
+				m.transactions[key] = true
+				transactions = append(transactions, tx)
+			}
+		}
+
+		i, ok := m.index[account.ID]
+		if !ok {
+			account.Transactions = transactions
+			m.index[account.ID] = len(m.Accounts)
+			m.Accounts = append(m.Accounts, account)
+			continue
+		}
+		existing := m.Accounts[i]
+		if account.BalanceDate >= existing.BalanceDate {
+			account.Transactions = append(existing.Transactions, transactions...)
+			m.Accounts[i] = account
+		} else {
+			m.Accounts[i].Transactions = append(existing.Transactions, transactions...)
+		}
+	}
+}
+
+// requestAccounts requests the SimpleFin bridge's accounts endpoint with the given query, masking
+// card and account numbers when REDACT_ACCOUNT_NUMBERS is set
+func requestAccounts(settings *Settings, logger zerolog.Logger, query string) (*AccountsResponse, error) {
+	// The access URL embeds credentials, so it is never logged
+	url := settings.SimplefinBridgeURL.Reveal() + "/accounts?" + query
+
+	client := newHTTPClient(120 * time.Second)
+
 	req, err := http.NewRequest(http.MethodGet, url, nil)
 	if err != nil {
-		return nil, nil, fmt.Errorf("error creating request: %w", err)
+		return nil, fmt.Errorf("error creating request: %w", err)
 	}
 
 	resp, err := client.Do(req)
 	if err != nil {
-		return nil, nil, fmt.Errorf("error making request: %w", err)
+		return nil, fmt.Errorf("error making request: %w", err)
 	}
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
 		body, _ := io.ReadAll(resp.Body)
-		log.Debug().
+		if settings.RedactAccountNumbers {
+			body = []byte(redactAccountNumbers(string(body)))
+		}
+		logger.Debug().
 			Int("status_code", resp.StatusCode).
 			Str("body", string(body)).
 			Msg("API request failed")
-		return nil, nil, fmt.Errorf("API request failed with status %d: %s", resp.StatusCode, string(body))
+		return nil, fmt.Errorf("API request failed with status %d: %s", resp.StatusCode, string(body))
 	}
 
 	var accountsResponse AccountsResponse
 	if err := json.NewDecoder(resp.Body).Decode(&accountsResponse); err != nil {
-		return nil, nil, fmt.Errorf("error decoding response: %w", err)
+		return nil, fmt.Errorf("error decoding response: %w", err)
+	}
+	logger.Debug().Int("account_count", len(accountsResponse.Accounts)).Msg("Successfully decoded response")
+
+	// Mask card and account numbers before anything is logged or rendered
+	if settings.RedactAccountNumbers {
+		redactAccounts(accountsResponse.Accounts)
+		for i, errMsg := range accountsResponse.Errors {
+			accountsResponse.Errors[i] = redactAccountNumbers(errMsg)
+		}
+	}
+
+	return &accountsResponse, nil
+}
+
+// getAccountList fetches every account from the SimpleFin bridge without transactions, including
+// the zero-balance accounts getTransactionsForWindow leaves out
+func getAccountList(settings *Settings) (accounts []Account, err error) {
+	logger, finish := startSpan("simplefin_accounts", map[string]interface{}{"balances_only": true})
+	defer func() { finish(err) }()
+
+	accountsResponse, err := requestAccounts(settings, logger, "balances-only=1")
+	if err != nil {
+		return nil, err
+	}
+	return accountsResponse.Accounts, nil
+}
+
+// getTransactionsForWindow fetches transactions from the SimpleFin bridge for a date range in a
+// single request
+func getTransactionsForWindow(settings *Settings, startDate, endDate time.Time) (accounts []Account, apiErrors []string, err error) {
+	logger, finish := startSpan("simplefin_fetch", map[string]interface{}{
+		"start_date": startDate.Format("2006-01-02"),
+		"end_date":   endDate.Format("2006-01-02"),
+	})
+	defer func() { finish(err) }()
+
+	logger.Debug().Msg("Fetching transactions from SimpleFin bridge")
+	accountsResponse, err := requestAccounts(settings, logger, fmt.Sprintf("start-date=%d&end-date=%d", startDate.Unix(), endDate.Unix()))
+	if err != nil {
+		return nil, nil, err
+	}
+
+	// Add bank logos for display
+	for i := range accountsResponse.Accounts {
+		accountsResponse.Accounts[i].Org.LogoURL = organizationLogoURL(accountsResponse.Accounts[i].Org, settings.BankLogoService)
 	}
-	log.Debug().Int("account_count", len(accountsResponse.Accounts)).Msg("Successfully decoded response")
 
 	// Log account details for debugging
 	for _, account := range accountsResponse.Accounts {
-		event := log.Debug().
-			Str("id", account.ID).
-			Str("name", account.Name).
+		accountLog := logger.With().Str("account_id", account.ID).Str("account_name", account.Name).Logger()
+		event := accountLog.Debug().
 			Str("balance", account.Balance.String()).
 			Str("balance_date", time.Unix(account.BalanceDate, 0).Format("2006-01-02 15:04:05"))
 
@@ -78,23 +245,23 @@ func getTransactionsForPeriod(settings *Settings, startDate, endDate time.Time)
 		event.Msg("Account details")
 
 		if account.AvailableBalance != nil {
-			log.Debug().Str("available_balance", account.AvailableBalance.String()).Msg("Available Balance")
+			accountLog.Debug().Str("available_balance", account.AvailableBalance.String()).Msg("Available Balance")
 		}
 
 		// Log transaction details
-		log.Debug().Int("transaction_count", len(account.Transactions)).Msg("Transactions")
+		accountLog.Debug().Int("transaction_count", len(account.Transactions)).Msg("Transactions")
 	}
 
 	// Log any errors or messages from the API
 	if len(accountsResponse.Errors) > 0 {
 		for _, errMsg := range accountsResponse.Errors {
-			log.Warn().Str("error", errMsg).Msg("API Error")
+			logger.Warn().Str("error", errMsg).Msg("API Error")
 		}
 	}
 
 	if len(accountsResponse.XAPIMessage) > 0 {
 		for _, msg := range accountsResponse.XAPIMessage {
-			log.Debug().Str("message", msg).Msg("API Message")
+			logger.Debug().Str("message", msg).Msg("API Message")
 		}
 	}
 
@@ -102,16 +269,16 @@ func getTransactionsForPeriod(settings *Settings, startDate, endDate time.Time)
 	var filteredAccounts []Account
 	for _, account := range accountsResponse.Accounts {
 		if float64(account.Balance) != 0 {
-			log.Debug().
+			logger.Debug().
 				Str("account_id", account.ID).
 				Float64("balance", float64(account.Balance)).
 				Msg("Included account with non-zero balance")
 			filteredAccounts = append(filteredAccounts, account)
 		} else {
-			log.Debug().Str("account_id", account.ID).Msg("Filtered out account with zero balance")
+			logger.Debug().Str("account_id", account.ID).Msg("Filtered out account with zero balance")
 		}
 	}
-	log.Debug().Int("filtered_account_count", len(filteredAccounts)).Msg("Filtered accounts with non-zero balance")
+	logger.Debug().Int("filtered_account_count", len(filteredAccounts)).Msg("Filtered accounts with non-zero balance")
 
 	return filteredAccounts, accountsResponse.Errors, nil
 }
@@ -0,0 +1,184 @@
+package main
+
+import (
+	"crypto/tls"
+	"crypto/x509"
+	"fmt"
+	"net"
+	"net/smtp"
+	"net/url"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// SMTPTLSMode selects how the connection to the SMTP server is encrypted
+type SMTPTLSMode string
+
+// Supported SMTP TLS modes
+const (
+	SMTPTLSModeAuto     SMTPTLSMode = "auto"     // Implicit TLS on port 465, otherwise STARTTLS when the server offers it
+	SMTPTLSModeStartTLS SMTPTLSMode = "starttls" // Plain connection upgraded with STARTTLS; fails if the server doesn't offer it
+	SMTPTLSModeImplicit SMTPTLSMode = "tls"      // TLS from the first byte (SMTPS)
+	SMTPTLSModeNone     SMTPTLSMode = "none"     // No encryption
+)
+
+// parseSMTPTLSMode validates a MAILER_TLS_MODE value
+func parseSMTPTLSMode(s string) (SMTPTLSMode, error) {
+	switch mode := SMTPTLSMode(strings.ToLower(s)); mode {
+	case SMTPTLSModeAuto, SMTPTLSModeStartTLS, SMTPTLSModeImplicit, SMTPTLSModeNone:
+		return mode, nil
+	default:
+		return "", fmt.Errorf("unknown mode %q (supported: auto, starttls, tls, none)", s)
+	}
+}
+
+// parseAddressList splits a comma-separated list of email addresses, dropping empty entries
+func parseAddressList(s string) []string {
+	var addresses []string
+	for _, address := range strings.Split(s, ",") {
+		if address = strings.TrimSpace(address); address != "" {
+			addresses = append(addresses, address)
+		}
+	}
+	return addresses
+}
+
+// emailRecipients returns the To, Cc and Bcc addresses of summary emails
+func emailRecipients(settings *Settings) (to, cc, bcc []string) {
+	if settings.MailerTo != nil {
+		to = parseAddressList(*settings.MailerTo)
+	}
+	return to, settings.MailerCC, settings.MailerBCC
+}
+
+// smtpTLSConfig returns the TLS configuration for host, trusting MAILER_TLS_CA_FILE in
+// addition to the system roots
+func smtpTLSConfig(settings *Settings, host string) (*tls.Config, error) {
+	config := &tls.Config{
+		ServerName:         host,
+		InsecureSkipVerify: settings.MailerSkipVerify,
+	}
+	if settings.MailerCAFile == "" {
+		return config, nil
+	}
+
+	pem, err := os.ReadFile(settings.MailerCAFile)
+	if err != nil {
+		return nil, fmt.Errorf("error reading MAILER_TLS_CA_FILE: %w", err)
+	}
+	pool, err := x509.SystemCertPool()
+	if err != nil {
+		pool = x509.NewCertPool()
+	}
+	if !pool.AppendCertsFromPEM(pem) {
+		return nil, fmt.Errorf("no certificates found in %s", settings.MailerCAFile)
+	}
+	config.RootCAs = pool
+	return config, nil
+}
+
+// dialSMTP connects to the SMTP server of MAILER_URL and sets up encryption according to
+// MAILER_TLS_MODE. The returned address is host:port, for messages.
+func dialSMTP(settings *Settings, mailURL *url.URL) (*smtp.Client, string, error) {
+	host := mailURL.Hostname()
+	port := mailURL.Port()
+	if port == "" {
+		port = "587" // Default to the submission port
+	}
+	addr := net.JoinHostPort(host, port)
+
+	tlsConfig, err := smtpTLSConfig(settings, host)
+	if err != nil {
+		return nil, addr, err
+	}
+
+	mode := settings.MailerTLSMode
+	if mode == SMTPTLSModeAuto && port == "465" {
+		mode = SMTPTLSModeImplicit
+	}
+
+	dialer := &net.Dialer{Timeout: settings.MailerTimeout}
+	var conn net.Conn
+	if mode == SMTPTLSModeImplicit {
+		conn, err = tls.DialWithDialer(dialer, "tcp", addr, tlsConfig)
+	} else {
+		conn, err = dialer.Dial("tcp", addr)
+	}
+	if err != nil {
+		return nil, addr, fmt.Errorf("error connecting to %s: %w", addr, err)
+	}
+	if settings.MailerTimeout > 0 {
+		// Bounds the whole session, so a stalled server can't hang the run
+		conn.SetDeadline(time.Now().Add(settings.MailerTimeout))
+	}
+
+	client, err := smtp.NewClient(conn, host)
+	if err != nil {
+		conn.Close()
+		return nil, addr, fmt.Errorf("error starting SMTP session: %w", err)
+	}
+
+	if mode == SMTPTLSModeAuto || mode == SMTPTLSModeStartTLS {
+		ok, _ := client.Extension("STARTTLS")
+		switch {
+		case ok:
+			if err := client.StartTLS(tlsConfig); err != nil {
+				client.Close()
+				return nil, addr, fmt.Errorf("error starting TLS: %w", err)
+			}
+		case mode == SMTPTLSModeStartTLS:
+			client.Close()
+			return nil, addr, fmt.Errorf("%s doesn't offer STARTTLS", addr)
+		}
+	}
+
+	log.Debug().Str("addr", addr).Str("tls_mode", string(mode)).Msg("Connected to SMTP server")
+	return client, addr, nil
+}
+
+// sendSMTPMessage delivers message from MAILER_FROM to every recipient over a single SMTP session
+func sendSMTPMessage(settings *Settings, recipients []string, message []byte) error {
+	mailURL, err := settings.MailerURL.Parse()
+	if err != nil {
+		return fmt.Errorf("error parsing SMTP URL: %w", err)
+	}
+
+	client, _, err := dialSMTP(settings, mailURL)
+	if err != nil {
+		return err
+	}
+	defer client.Close()
+
+	if mailURL.User != nil {
+		password, _ := mailURL.User.Password()
+		if err := client.Auth(smtp.PlainAuth("", mailURL.User.Username(), password, mailURL.Hostname())); err != nil {
+			return fmt.Errorf("authentication failed: %w", err)
+		}
+	}
+
+	if err := client.Mail(*settings.MailerFrom); err != nil {
+		return fmt.Errorf("error setting sender: %w", err)
+	}
+	for _, recipient := range recipients {
+		if err := client.Rcpt(recipient); err != nil {
+			return fmt.Errorf("error adding recipient %s: %w", recipient, err)
+		}
+	}
+
+	w, err := client.Data()
+	if err != nil {
+		return fmt.Errorf("error starting message: %w", err)
+	}
+	if _, err := w.Write(message); err != nil {
+		w.Close()
+		return fmt.Errorf("error writing message: %w", err)
+	}
+	if err := w.Close(); err != nil {
+		return fmt.Errorf("error finishing message: %w", err)
+	}
+
+	return client.Quit()
+}
@@ -0,0 +1,105 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// statusCacheFile is the name of the JSON file holding the last status line inside the cache directory
+const statusCacheFile = "status.json"
+
+// cachedStatus is a rendered status line along with the account selection it was built for
+type cachedStatus struct {
+	Selection   string    `json:"selection"`
+	Line        string    `json:"line"`
+	GeneratedAt time.Time `json:"generated_at"`
+}
+
+// selectStatusAccounts returns the accounts matching the selection (IDs or case-insensitive names),
+// in selection order. An empty selection returns every account.
+func selectStatusAccounts(accounts []Account, selection []string) []Account {
+	if len(selection) == 0 {
+		return accounts
+	}
+
+	var selected []Account
+	for _, want := range selection {
+		for _, account := range accounts {
+			if account.ID == want || strings.EqualFold(account.Name, want) || (account.Nickname != "" && strings.EqualFold(account.Nickname, want)) {
+				selected = append(selected, account)
+				break
+			}
+		}
+	}
+	return selected
+}
+
+// formatStatusLine renders balances and the period's spending as a single line for status bars,
+// e.g. "Chequing 1234.00 | Visa -567.00 | Spent 2345.00"
+func formatStatusLine(accounts []Account) string {
+	parts := make([]string, 0, len(accounts)+1)
+	var spent Money
+	for _, account := range accounts {
+		parts = append(parts, fmt.Sprintf("%s %s", accountDisplayName(account), account.Balance))
+		for _, tx := range account.Transactions {
+			if tx.Amount >= 0 || tx.IsTransfer {
+				continue
+			}
+			if net, ok := netExpense(tx); ok {
+				spent -= toMoney(net.Amount)
+			}
+		}
+	}
+	parts = append(parts, fmt.Sprintf("Spent %s", spent))
+	return strings.Join(parts, " | ")
+}
+
+// loadCachedStatus returns the cached status line if it was built for the same selection within maxAge
+func loadCachedStatus(cacheDir string, selection string, maxAge time.Duration) (string, bool) {
+	if cacheDir == "" || maxAge <= 0 {
+		return "", false
+	}
+
+	data, err := os.ReadFile(filepath.Join(cacheDir, statusCacheFile))
+	if err != nil {
+		return "", false
+	}
+
+	var cached cachedStatus
+	if err := json.Unmarshal(data, &cached); err != nil {
+		return "", false
+	}
+	if cached.Selection != selection || time.Since(cached.GeneratedAt) > maxAge {
+		return "", false
+	}
+	return cached.Line, true
+}
+
+// storeCachedStatus saves the status line so frequent status bar refreshes don't hit SimpleFin
+func storeCachedStatus(cacheDir string, selection string, line string) error {
+	if cacheDir == "" {
+		return nil
+	}
+
+	data, err := json.MarshalIndent(cachedStatus{
+		Selection:   selection,
+		Line:        line,
+		GeneratedAt: time.Now().UTC(),
+	}, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling status: %w", err)
+	}
+
+	if err := os.MkdirAll(cacheDir, 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(filepath.Join(cacheDir, statusCacheFile), data); err != nil {
+		return fmt.Errorf("error writing status cache: %w", err)
+	}
+	return nil
+}
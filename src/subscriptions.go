@@ -0,0 +1,102 @@
+package main
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+)
+
+// SubscriptionChange is a recurring charge whose price went up, or that started during the history
+type SubscriptionChange struct {
+	Merchant      string
+	Amount        Money     // Latest charge (positive)
+	PreviousPrice *Money    // Charge before the increase (positive); nil for a new subscription
+	Interval      int       // Median days between charges
+	Date          time.Time // Date of the latest charge
+}
+
+// detectSubscriptionChanges finds recurring charges whose latest charge, on or after since, costs
+// more than the one before it, and recurring charges that started within the history: their first
+// charge is more than one interval after historyStart, so an earlier one would have been seen.
+func detectSubscriptionChanges(transactions []Transaction, historyStart, since time.Time) []SubscriptionChange {
+	groups := make(map[string][]Transaction)
+	for _, tx := range transactions {
+		if tx.Amount >= 0 || tx.IsTransfer {
+			continue
+		}
+		key := strings.ToLower(merchantName(tx))
+		groups[key] = append(groups[key], tx)
+	}
+
+	var changes []SubscriptionChange
+	for _, txs := range groups {
+		if len(txs) < 2 {
+			continue
+		}
+		sort.Slice(txs, func(i, j int) bool {
+			return transactionTime(txs[i]).Before(transactionTime(txs[j]))
+		})
+		latest := txs[len(txs)-1]
+		latestTime := transactionTime(latest)
+		if latestTime.Before(since) {
+			continue
+		}
+
+		// Price increase: the earlier charges form a regular series, and the latest charge arrives
+		// on schedule but costs more than the previous one
+		if earlier := detectRecurring(txs[:len(txs)-1]); len(earlier) > 0 {
+			series := earlier[0]
+			previous := -toMoney(txs[len(txs)-2].Amount)
+			gap := latestTime.Sub(series.Last).Hours() / 24
+			if current := -toMoney(latest.Amount); current > previous && gap <= float64(series.Interval)*1.5 {
+				changes = append(changes, SubscriptionChange{
+					Merchant:      merchantName(latest),
+					Amount:        current,
+					PreviousPrice: &previous,
+					Interval:      series.Interval,
+					Date:          latestTime,
+				})
+				continue
+			}
+		}
+
+		// New subscription: the whole group is a regular series that began well after the history start
+		if series := detectRecurring(txs); len(series) > 0 {
+			first := transactionTime(txs[0])
+			if first.Sub(historyStart) > time.Duration(series[0].Interval+3)*24*time.Hour {
+				changes = append(changes, SubscriptionChange{
+					Merchant: merchantName(latest),
+					Amount:   -toMoney(latest.Amount),
+					Interval: series[0].Interval,
+					Date:     latestTime,
+				})
+			}
+		}
+	}
+
+	sort.Slice(changes, func(i, j int) bool {
+		return changes[i].Merchant < changes[j].Merchant
+	})
+	return changes
+}
+
+// formatSubscriptionChanges renders subscription changes as a markdown section for notifications
+func formatSubscriptionChanges(changes []SubscriptionChange) string {
+	if len(changes) == 0 {
+		return ""
+	}
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "## %s\n\n", currentLocale.SubscriptionsHeading)
+	for _, c := range changes {
+		if c.PreviousPrice != nil {
+			fmt.Fprintf(&b, "- 📈 "+currentLocale.SubscriptionIncrease+"\n", c.Merchant,
+				currentLocale.formatAmount(c.Amount), currentLocale.formatAmount(*c.PreviousPrice), currentLocale.formatDate(c.Date))
+		} else {
+			fmt.Fprintf(&b, "- 🆕 "+currentLocale.SubscriptionNew+"\n", c.Merchant,
+				currentLocale.formatAmount(c.Amount), c.Interval, currentLocale.formatDate(c.Date))
+		}
+	}
+	return b.String()
+}
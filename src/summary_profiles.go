@@ -0,0 +1,80 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/rs/zerolog/log"
+)
+
+// SummaryProfile is a named summary configuration from the filter config's summaries section.
+// Each profile is run on its own schedule with `--profile <name>`.
+type SummaryProfile struct {
+	Name          string   `yaml:"name"`
+	Accounts      []string `yaml:"accounts"`      // Account names, nicknames or IDs in the summary (empty: credit cards, or all with all_accounts)
+	AllAccounts   bool     `yaml:"all_accounts"`  // Include all account types when no accounts are listed
+	Cadence       string   `yaml:"cadence"`       // daily, weekly or monthly (empty: SUMMARY_CADENCE)
+	Notifications []string `yaml:"notifications"` // Notification channels (empty: the --notifications flag)
+	EmailTo       []string `yaml:"email_to"`      // Recipients replacing MAILER_TO, MAILER_CC and MAILER_BCC
+}
+
+// findSummaryProfile returns the profile with the given name (case-insensitive)
+func findSummaryProfile(filterConfig *FilterConfig, name string) (SummaryProfile, error) {
+	if filterConfig != nil {
+		for _, profile := range filterConfig.Summaries {
+			if strings.EqualFold(profile.Name, name) {
+				return profile, nil
+			}
+		}
+	}
+	return SummaryProfile{}, fmt.Errorf("no summary profile named %q in FILTER_CONFIG_PATH", name)
+}
+
+// applySummaryProfile applies the profile's cadence, channels and recipients to the run. An
+// explicit --cadence or --date-range still takes precedence over the profile's cadence.
+func applySummaryProfile(profile SummaryProfile, settings *Settings, config *RunConfig) {
+	if profile.Cadence != "" && config.Cadence == "" && config.DateRange == "" {
+		config.Cadence = profile.Cadence
+	}
+	if len(profile.Notifications) > 0 {
+		config.Notifications = profile.Notifications
+	}
+	if profile.AllAccounts {
+		config.AllAccounts = true
+	}
+	if len(profile.EmailTo) > 0 {
+		mailerTo := strings.Join(profile.EmailTo, ",")
+		settings.MailerTo = &mailerTo
+		settings.MailerCC = nil
+		settings.MailerBCC = nil
+	}
+
+	log.Info().
+		Str("profile", profile.Name).
+		Str("cadence", config.Cadence).
+		Strs("notifications", config.Notifications).
+		Msg("📋 Using summary profile")
+}
+
+// selectProfileAccounts keeps the accounts listed in the profile, matched by ID, name or nickname
+func selectProfileAccounts(accounts []Account, profile SummaryProfile) ([]Account, error) {
+	var selected []Account
+	for _, account := range accounts {
+		for _, key := range profile.Accounts {
+			if key == account.ID || strings.EqualFold(key, account.Name) || (account.Nickname != "" && strings.EqualFold(key, account.Nickname)) {
+				selected = append(selected, account)
+				break
+			}
+		}
+	}
+
+	if len(selected) == 0 {
+		return nil, fmt.Errorf("none of the accounts of summary profile %q were found", profile.Name)
+	}
+	log.Info().
+		Int("profile_accounts", len(selected)).
+		Int("total_accounts", len(accounts)).
+		Str("profile", profile.Name).
+		Msg("💳 Filtering to the summary profile's accounts")
+	return selected, nil
+}
@@ -0,0 +1,120 @@
+package main
+
+import (
+	"encoding/csv"
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+)
+
+// TaxRule maps transactions matching a filter rule to a tax line (e.g. "Charitable donations")
+type TaxRule struct {
+	Line       string `yaml:"line"`
+	FilterRule `yaml:",inline"`
+}
+
+// TaxItem is a transaction assigned to a tax line
+type TaxItem struct {
+	Line        string
+	Account     string
+	Transaction Transaction
+}
+
+// TaxLineTotal is the total of a tax line (as a positive amount for expenses)
+type TaxLineTotal struct {
+	Line  string
+	Total Money
+	Count int
+}
+
+// assignTaxLines assigns each non-transfer transaction to the tax line of the first rule it matches.
+// Linked refunds are netted from their purchases, and fully refunded purchases are left out.
+func assignTaxLines(accounts []Account, rules []TaxRule) []TaxItem {
+	var items []TaxItem
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if tx.IsTransfer || tx.RefundOf != "" {
+				continue
+			}
+			net, ok := netExpense(tx)
+			if !ok {
+				continue
+			}
+			for _, rule := range rules {
+				value := ruleFieldValue(tx, rule.FilterRule)
+				if value != "" && matchesRule(value, rule.FilterRule) {
+					items = append(items, TaxItem{Line: rule.Line, Account: accountDisplayName(account), Transaction: net})
+					break
+				}
+			}
+		}
+	}
+
+	sort.SliceStable(items, func(i, j int) bool {
+		if items[i].Line != items[j].Line {
+			return items[i].Line < items[j].Line
+		}
+		return transactionTime(items[i].Transaction).Before(transactionTime(items[j].Transaction))
+	})
+	return items
+}
+
+// totalTaxLines totals the items per tax line, in line order
+func totalTaxLines(items []TaxItem) []TaxLineTotal {
+	var totals []TaxLineTotal
+	for _, item := range items {
+		if len(totals) == 0 || totals[len(totals)-1].Line != item.Line {
+			totals = append(totals, TaxLineTotal{Line: item.Line})
+		}
+		total := &totals[len(totals)-1]
+		total.Total -= toMoney(item.Transaction.Amount)
+		total.Count++
+	}
+	return totals
+}
+
+// writeTaxReportCSV writes the items grouped by tax line, followed by a total row per line.
+// Expenses are positive amounts; credits such as reimbursements are negative.
+func writeTaxReportCSV(w io.Writer, items []TaxItem) error {
+	writer := csv.NewWriter(w)
+	if err := writer.Write([]string{"Tax Line", "Date", "Account", "Description", "Merchant", "Category", "Amount", "Note", "ID"}); err != nil {
+		return fmt.Errorf("error writing CSV header: %w", err)
+	}
+
+	for _, item := range items {
+		record := []string{
+			item.Line,
+			transactionTime(item.Transaction).Format("2006-01-02"),
+			item.Account,
+			item.Transaction.Description,
+			merchantName(item.Transaction),
+			item.Transaction.Category,
+			(-toMoney(item.Transaction.Amount)).String(),
+			item.Transaction.Note,
+			item.Transaction.ID,
+		}
+		if err := writer.Write(record); err != nil {
+			return fmt.Errorf("error writing CSV record: %w", err)
+		}
+	}
+
+	for _, total := range totalTaxLines(items) {
+		record := []string{total.Line, "", "", fmt.Sprintf("TOTAL (%d transactions)", total.Count), "", "", total.Total.String(), "", ""}
+		if err := writer.Write(record); err != nil {
+			return fmt.Errorf("error writing CSV record: %w", err)
+		}
+	}
+
+	writer.Flush()
+	return writer.Error()
+}
+
+// formatTaxLineTotals formats the per-line totals for the terminal
+func formatTaxLineTotals(totals []TaxLineTotal) string {
+	var b strings.Builder
+	for _, total := range totals {
+		fmt.Fprintf(&b, "%s: %s (%d transactions)\n", total.Line, total.Total.Format(), total.Count)
+	}
+	return b.String()
+}
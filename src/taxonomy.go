@@ -0,0 +1,141 @@
+package main
+
+import "strings"
+
+// categorySeparator joins the levels of a hierarchical category, e.g. "Dining > Coffee"
+const categorySeparator = " > "
+
+// maxCategoryDepth bounds how many category_parents are followed, so a cycle can't loop forever
+const maxCategoryDepth = 5
+
+// plaidCategoryPreset maps Plaid's personal finance categories (primary and detailed codes) and
+// legacy category hierarchies to a two-level local taxonomy
+var plaidCategoryPreset = [][2]string{
+	{"FOOD_AND_DRINK", "Dining"},
+	{"FOOD_AND_DRINK_COFFEE", "Dining > Coffee"},
+	{"FOOD_AND_DRINK_FAST_FOOD", "Dining > Fast Food"},
+	{"FOOD_AND_DRINK_RESTAURANT", "Dining > Restaurants"},
+	{"FOOD_AND_DRINK_BEER_WINE_AND_LIQUOR", "Dining > Bars"},
+	{"FOOD_AND_DRINK_GROCERIES", "Groceries"},
+	{"GENERAL_MERCHANDISE", "Shopping"},
+	{"GENERAL_MERCHANDISE_CLOTHING_AND_ACCESSORIES", "Shopping > Clothing"},
+	{"GENERAL_MERCHANDISE_ELECTRONICS", "Shopping > Electronics"},
+	{"GENERAL_MERCHANDISE_ONLINE_MARKETPLACES", "Shopping > Online"},
+	{"GENERAL_MERCHANDISE_SUPERSTORES", "Shopping > Superstores"},
+	{"HOME_IMPROVEMENT", "Housing > Home Improvement"},
+	{"RENT_AND_UTILITIES", "Utilities"},
+	{"RENT_AND_UTILITIES_RENT", "Housing > Rent"},
+	{"RENT_AND_UTILITIES_GAS_AND_ELECTRICITY", "Utilities > Gas and Electricity"},
+	{"RENT_AND_UTILITIES_INTERNET_AND_CABLE", "Utilities > Internet and Cable"},
+	{"RENT_AND_UTILITIES_TELEPHONE", "Utilities > Phone"},
+	{"RENT_AND_UTILITIES_WATER", "Utilities > Water"},
+	{"TRANSPORTATION", "Transportation"},
+	{"TRANSPORTATION_GAS", "Transportation > Gas"},
+	{"TRANSPORTATION_PARKING", "Transportation > Parking"},
+	{"TRANSPORTATION_PUBLIC_TRANSIT", "Transportation > Public Transit"},
+	{"TRANSPORTATION_TAXIS_AND_RIDE_SHARES", "Transportation > Rideshare"},
+	{"TRAVEL", "Travel"},
+	{"TRAVEL_FLIGHTS", "Travel > Flights"},
+	{"TRAVEL_LODGING", "Travel > Lodging"},
+	{"TRAVEL_RENTAL_CARS", "Travel > Rental Cars"},
+	{"ENTERTAINMENT", "Entertainment"},
+	{"ENTERTAINMENT_MUSIC_AND_AUDIO", "Entertainment > Music"},
+	{"ENTERTAINMENT_TV_AND_MOVIES", "Entertainment > Streaming"},
+	{"ENTERTAINMENT_VIDEO_GAMES", "Entertainment > Games"},
+	{"MEDICAL", "Healthcare"},
+	{"MEDICAL_PHARMACIES_AND_SUPPLEMENTS", "Healthcare > Pharmacy"},
+	{"PERSONAL_CARE", "Personal Care"},
+	{"PERSONAL_CARE_GYMS_AND_FITNESS_CENTERS", "Personal Care > Fitness"},
+	{"GENERAL_SERVICES", "Services"},
+	{"GENERAL_SERVICES_INSURANCE", "Services > Insurance"},
+	{"BANK_FEES", "Fees"},
+	{"LOAN_PAYMENTS", "Loans"},
+
+	{"Food and Drink", "Dining"},
+	{"Food and Drink > Restaurants", "Dining > Restaurants"},
+	{"Food and Drink > Restaurants > Coffee Shop", "Dining > Coffee"},
+	{"Food and Drink > Restaurants > Fast Food", "Dining > Fast Food"},
+	{"Shops", "Shopping"},
+	{"Shops > Supermarkets and Groceries", "Groceries"},
+	{"Travel", "Travel"},
+	{"Travel > Airlines and Aviation Services", "Travel > Flights"},
+	{"Travel > Lodging", "Travel > Lodging"},
+	{"Travel > Taxi", "Transportation > Rideshare"},
+	{"Travel > Gas Stations", "Transportation > Gas"},
+	{"Recreation > Gyms and Fitness Centers", "Personal Care > Fitness"},
+	{"Service > Subscription", "Services > Subscriptions"},
+	{"Service > Utilities", "Utilities"},
+	{"Healthcare", "Healthcare"},
+	{"Bank Fees", "Fees"},
+	{"Payment > Rent", "Housing > Rent"},
+	{"Service > Cable", "Utilities > Internet and Cable"},
+	{"Service > Telecommunication Services", "Utilities > Phone"},
+	{"Shops > Computers and Electronics", "Shopping > Electronics"},
+	{"Shops > Clothing and Accessories", "Shopping > Clothing"},
+	{"Shops > Digital Purchase", "Shopping > Online"},
+	{"Recreation > Arts and Entertainment", "Entertainment"},
+	{"Food and Drink > Bar", "Dining > Bars"},
+	{"Service > Financial > Loans and Mortgages", "Loans"},
+	{"Shops > Pharmacies", "Healthcare > Pharmacy"},
+	{"Service > Insurance", "Services > Insurance"},
+	{"Travel > Car Service > Ride Share", "Transportation > Rideshare"},
+	{"Travel > Public Transportation Services", "Transportation > Public Transit"},
+	{"Travel > Parking", "Transportation > Parking"},
+	{"Service > Home Improvement", "Housing > Home Improvement"},
+}
+
+// categoryPresets are the built-in provider category mappings selectable with category_preset
+var categoryPresets = map[string][][2]string{
+	"plaid": plaidCategoryPreset,
+}
+
+// providerCategoryMappings returns the provider category mappings keyed by lowercase provider
+// category: the category_preset's, overridden by category_mappings
+func providerCategoryMappings(filterConfig *FilterConfig) map[string]string {
+	mappings := make(map[string]string)
+	for _, mapping := range categoryPresets[strings.ToLower(filterConfig.CategoryPreset)] {
+		mappings[strings.ToLower(mapping[0])] = mapping[1]
+	}
+	for provider, local := range filterConfig.CategoryMappings {
+		mappings[strings.ToLower(strings.TrimSpace(provider))] = local
+	}
+	return mappings
+}
+
+// splitCategory splits a hierarchical category into its top-level category and the rest
+// ("Dining > Coffee" -> "Dining", "Coffee"); flat categories have no subcategory
+func splitCategory(category string) (string, string) {
+	parent, child, _ := strings.Cut(category, categorySeparator)
+	return strings.TrimSpace(parent), strings.TrimSpace(child)
+}
+
+// categoryWithin reports whether the category is the given one or one of its subcategories
+func categoryWithin(category, ancestor string) bool {
+	return strings.EqualFold(category, ancestor) ||
+		strings.HasPrefix(strings.ToLower(category), strings.ToLower(ancestor)+categorySeparator)
+}
+
+// categoryParents returns the configured category_parents keyed by lowercase category
+func categoryParents(filterConfig *FilterConfig) map[string]string {
+	parents := make(map[string]string, len(filterConfig.CategoryParents))
+	for category, parent := range filterConfig.CategoryParents {
+		parents[strings.ToLower(strings.TrimSpace(category))] = strings.TrimSpace(parent)
+	}
+	return parents
+}
+
+// categoryPath prefixes the category with its configured parents, so "Coffee" with the parent
+// "Dining" becomes "Dining > Coffee"
+func categoryPath(category string, parents map[string]string) string {
+	path := category
+	top, _ := splitCategory(category)
+	for depth := 0; depth < maxCategoryDepth; depth++ {
+		parent, ok := parents[strings.ToLower(top)]
+		if !ok || parent == "" || strings.EqualFold(parent, top) {
+			break
+		}
+		path = parent + categorySeparator + path
+		top, _ = splitCategory(parent)
+	}
+	return path
+}
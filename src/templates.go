@@ -0,0 +1,168 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"text/template"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// Template file names looked up in TEMPLATES_DIR, one per notification channel
+const (
+	emailTemplateFile = "email.html" // html/template, see emailData
+	ntfyTemplateFile  = "ntfy.txt"   // text/template, see ntfyTemplateData
+)
+
+// ntfyTemplateData is the data available to a custom ntfy template
+type ntfyTemplateData struct {
+	Message  string // Summary with markdown stripped
+	Markdown string // Summary as markdown
+	Topic    string // Resolved ntfy topic
+	Warning  bool   // True for warning notifications
+}
+
+// defaultNotificationTitle is the ntfy and Gotify title when NOTIFICATION_TITLE isn't set
+const defaultNotificationTitle = "💰 Finance Tracker"
+
+// SummaryPeriod is the billing period and computed spending of the summary being sent, so its
+// title matches the body instead of being derived from the transactions attached to it
+type SummaryPeriod struct {
+	Start time.Time
+	End   time.Time
+	Spend Money // Analytics total: expenses net of linked refunds, without transfers
+}
+
+// titleTemplateData is the data available to the EMAIL_SUBJECT and NOTIFICATION_TITLE templates
+type titleTemplateData struct {
+	Household  string // HOUSEHOLD_NAME
+	Period     string // Billing period of a summary, otherwise the dates of the first and last transaction
+	TotalSpend string // Spending reported by a summary, otherwise of the transactions, formatted for the locale
+	Count      int    // Number of transactions
+	Warning    bool   // True for warning notifications
+}
+
+// newTitleTemplateData computes the title variables of a notification. Summaries use the period
+// and total they are sent with; other notifications (period is nil) fall back to the transactions
+// sent with them.
+func newTitleTemplateData(settings *Settings, transactions []Transaction, period *SummaryPeriod, notificationTopic string) titleTemplateData {
+	data := titleTemplateData{
+		Household: settings.HouseholdName,
+		Count:     len(transactions),
+		Warning:   notificationTopic == "warning",
+	}
+
+	if period != nil && !data.Warning {
+		data.Period = currentLocale.formatDate(period.Start) + " - " + currentLocale.formatDate(period.End)
+		data.TotalSpend = currentLocale.formatAmount(period.Spend)
+		return data
+	}
+
+	var spend Money
+	var first, last time.Time
+	for _, tx := range transactions {
+		if tx.Amount < 0 && !tx.IsTransfer {
+			if net, ok := netExpense(tx); ok {
+				spend -= toMoney(net.Amount)
+			}
+		}
+		t := transactionTime(tx)
+		if first.IsZero() || t.Before(first) {
+			first = t
+		}
+		if t.After(last) {
+			last = t
+		}
+	}
+	data.TotalSpend = currentLocale.formatAmount(spend)
+	if !first.IsZero() {
+		data.Period = currentLocale.formatDate(first) + " - " + currentLocale.formatDate(last)
+	}
+	return data
+}
+
+// parseTitleTemplate parses an EMAIL_SUBJECT or NOTIFICATION_TITLE template
+func parseTitleTemplate(name string, text string) (*template.Template, error) {
+	return template.New(name).Option("missingkey=error").Parse(text)
+}
+
+// renderTitle renders a subject or title template, returning fallback when none is configured.
+// Line breaks are folded into spaces since the result ends up in a header.
+func renderTitle(name string, text string, fallback string, data titleTemplateData) (string, error) {
+	if text == "" {
+		return fallback, nil
+	}
+
+	tmpl, err := parseTitleTemplate(name, text)
+	if err != nil {
+		return "", fmt.Errorf("error parsing %s template: %w", name, err)
+	}
+
+	var buf bytes.Buffer
+	if err := tmpl.Execute(&buf, data); err != nil {
+		return "", fmt.Errorf("error executing %s template: %w", name, err)
+	}
+	return strings.Join(strings.Fields(buf.String()), " "), nil
+}
+
+// notificationTitle returns the ntfy and Gotify title for the notification
+func notificationTitle(settings *Settings, transactions []Transaction, period *SummaryPeriod, notificationTopic string) (string, error) {
+	return renderTitle("NOTIFICATION_TITLE", settings.NotificationTitle, defaultNotificationTitle, newTitleTemplateData(settings, transactions, period, notificationTopic))
+}
+
+// emailSubject returns the email subject for the notification
+func emailSubject(settings *Settings, transactions []Transaction, period *SummaryPeriod, notificationTopic string) (string, error) {
+	return renderTitle("EMAIL_SUBJECT", settings.EmailSubject, currentLocale.EmailSubject, newTitleTemplateData(settings, transactions, period, notificationTopic))
+}
+
+// readTemplateOverride reads a user-supplied template from the templates directory. It returns
+// false when no directory is configured or the channel has no template, so the built-in is used.
+func readTemplateOverride(templatesDir string, name string) (string, bool, error) {
+	if templatesDir == "" {
+		return "", false, nil
+	}
+
+	path := filepath.Join(templatesDir, name)
+	data, err := os.ReadFile(path)
+	if errors.Is(err, os.ErrNotExist) {
+		return "", false, nil
+	}
+	if err != nil {
+		return "", false, fmt.Errorf("error reading template %s: %w", path, err)
+	}
+
+	log.Debug().Str("template", path).Msg("Using custom notification template")
+	return string(data), true, nil
+}
+
+// renderNtfyMessage renders the ntfy message body, using the custom template if one is configured
+// and the markdown-stripped summary otherwise
+func renderNtfyMessage(templatesDir string, message string, topic string, notificationTopic string) (string, error) {
+	plainMessage := stripMarkdown(message)
+
+	text, ok, err := readTemplateOverride(templatesDir, ntfyTemplateFile)
+	if err != nil || !ok {
+		return plainMessage, err
+	}
+
+	tmpl, err := template.New("ntfy").Parse(text)
+	if err != nil {
+		return "", fmt.Errorf("error parsing ntfy template: %w", err)
+	}
+
+	var buf bytes.Buffer
+	if err := tmpl.Execute(&buf, ntfyTemplateData{
+		Message:  plainMessage,
+		Markdown: message,
+		Topic:    topic,
+		Warning:  notificationTopic == "warning",
+	}); err != nil {
+		return "", fmt.Errorf("error executing ntfy template: %w", err)
+	}
+	return buf.String(), nil
+}
@@ -0,0 +1,138 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+)
+
+// TimelineEntry is a single transaction in the activity timeline
+type TimelineEntry struct {
+	Account     string    `json:"account"`
+	Description string    `json:"description"`
+	Amount      Money     `json:"amount"`
+	Time        time.Time `json:"time"`
+	Status      string    `json:"status"`
+}
+
+// TimelineDay groups the transactions of a single day with their subtotal and the
+// end-of-day running balance of each account
+type TimelineDay struct {
+	Date         string           `json:"date"`
+	Subtotal     Money            `json:"subtotal"`
+	Transactions []TimelineEntry  `json:"transactions"`
+	Balances     map[string]Money `json:"balances"`
+}
+
+// buildTimeline groups transactions by day (newest first). Running balances are derived by
+// walking backwards from each account's current balance, after first unwinding the transactions
+// posted since the period (later, fetched from the period end until now), so the balances of a
+// past period are the ones the account actually had.
+func buildTimeline(accounts, later []Account, expensesOnly bool) []TimelineDay {
+	days := make(map[string]*TimelineDay)
+
+	laterTransactions := make(map[string][]Transaction, len(later))
+	for _, account := range later {
+		laterTransactions[account.ID] = account.Transactions
+	}
+
+	for _, account := range accounts {
+		// Walk transactions from newest to oldest
+		transactions := make([]Transaction, len(account.Transactions))
+		copy(transactions, account.Transactions)
+		sort.SliceStable(transactions, func(i, j int) bool {
+			return transactionTime(transactions[i]).After(transactionTime(transactions[j]))
+		})
+
+		// The later fetch overlaps the period's last day, so skip what the period already has
+		inPeriod := make(map[string]bool, len(transactions))
+		for _, tx := range transactions {
+			inPeriod[tx.ID] = true
+		}
+		balance := toMoney(account.Balance)
+		for _, tx := range laterTransactions[account.ID] {
+			if !inPeriod[tx.ID] {
+				balance -= toMoney(tx.Amount)
+			}
+		}
+
+		for _, tx := range transactions {
+			date := transactionTime(tx).Format("2006-01-02")
+			day, ok := days[date]
+			if !ok {
+				day = &TimelineDay{
+					Date:     date,
+					Balances: make(map[string]Money),
+				}
+				days[date] = day
+			}
+
+			// The first (latest) transaction seen for a day carries the end-of-day balance
+			if _, ok := day.Balances[accountDisplayName(account)]; !ok {
+				day.Balances[accountDisplayName(account)] = balance
+			}
+			balance -= toMoney(tx.Amount)
+
+			if expensesOnly && (tx.Amount >= 0 || tx.IsTransfer) {
+				continue
+			}
+
+			day.Transactions = append(day.Transactions, TimelineEntry{
+				Account:     accountDisplayName(account),
+				Description: merchantName(tx),
+				Amount:      toMoney(tx.Amount),
+				Time:        transactionTime(tx),
+				Status:      getTransactionStatus(tx),
+			})
+			day.Subtotal += toMoney(tx.Amount)
+		}
+	}
+
+	timeline := make([]TimelineDay, 0, len(days))
+	for _, day := range days {
+		sort.SliceStable(day.Transactions, func(i, j int) bool {
+			return day.Transactions[i].Time.After(day.Transactions[j].Time)
+		})
+		timeline = append(timeline, *day)
+	}
+	sort.Slice(timeline, func(i, j int) bool {
+		return timeline[i].Date > timeline[j].Date
+	})
+
+	return timeline
+}
+
+// writeTimelineJSON writes the timeline as indented JSON
+func writeTimelineJSON(w io.Writer, timeline []TimelineDay) error {
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(timeline)
+}
+
+// writeTimelineText writes the timeline as a human-readable activity feed
+func writeTimelineText(w io.Writer, timeline []TimelineDay) error {
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	for _, day := range timeline {
+		fmt.Fprintf(tw, "%s\t\t\t%s\n", day.Date, day.Subtotal)
+		for _, entry := range day.Transactions {
+			fmt.Fprintf(tw, "  %s\t%s\t%s\t%s\n", entry.Time.Format("15:04"), entry.Account, entry.Description, entry.Amount)
+		}
+
+		accountNames := make([]string, 0, len(day.Balances))
+		for name := range day.Balances {
+			accountNames = append(accountNames, name)
+		}
+		sort.Strings(accountNames)
+
+		balances := make([]string, 0, len(accountNames))
+		for _, name := range accountNames {
+			balances = append(balances, fmt.Sprintf("%s %s", name, day.Balances[name]))
+		}
+		fmt.Fprintf(tw, "  balance\t%s\t\t\n\t\t\t\n", strings.Join(balances, " | "))
+	}
+	return tw.Flush()
+}
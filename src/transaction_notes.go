@@ -0,0 +1,186 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// transactionNotesFile is the name of the JSON file holding transaction annotations inside the cache directory
+const transactionNotesFile = "transaction_notes.json"
+
+// TransactionNote is a user annotation on a transaction. The original bank description is kept;
+// the edited description is preferred in reports.
+type TransactionNote struct {
+	Note        string    `json:"note,omitempty"`        // Free-form note (e.g. "split with roommate")
+	Description string    `json:"description,omitempty"` // User-edited description
+	Category    string    `json:"category,omitempty"`    // User-assigned category
+	UpdatedAt   time.Time `json:"updated_at"`
+}
+
+// LearnedCategory is a merchant -> category rule derived from the categories set by hand
+type LearnedCategory struct {
+	Category     string    `json:"category"`
+	Transactions []string  `json:"transactions"` // IDs of the categorized transactions supporting the rule
+	UpdatedAt    time.Time `json:"updated_at"`
+}
+
+// TransactionNotes holds annotations keyed by SimpleFin transaction ID
+type TransactionNotes struct {
+	path               string
+	Notes              map[string]TransactionNote `json:"notes"`
+	Refunds            map[string]RefundLink      `json:"refunds,omitempty"`             // Refund links keyed by the refund's transaction ID
+	MerchantCategories map[string]LearnedCategory `json:"merchant_categories,omitempty"` // Learned rules keyed by lowercase merchant name
+}
+
+// loadTransactionNotes loads the transaction annotations from the cache directory, returning nil
+// if no cache directory is configured or the file can't be read
+func loadTransactionNotes(cacheDir string) *TransactionNotes {
+	if cacheDir == "" {
+		return nil
+	}
+
+	notes := &TransactionNotes{
+		path:               filepath.Join(cacheDir, transactionNotesFile),
+		Notes:              make(map[string]TransactionNote),
+		Refunds:            make(map[string]RefundLink),
+		MerchantCategories: make(map[string]LearnedCategory),
+	}
+
+	data, err := os.ReadFile(notes.path)
+	if errors.Is(err, os.ErrNotExist) {
+		return notes
+	}
+	if err != nil {
+		log.Warn().Err(err).Str("notes_path", notes.path).Msg("Failed to read transaction notes, continuing without them")
+		return nil
+	}
+
+	if err := json.Unmarshal(data, notes); err != nil {
+		log.Warn().Err(err).Str("notes_path", notes.path).Msg("Failed to parse transaction notes, continuing without them")
+		return nil
+	}
+	if notes.Notes == nil {
+		notes.Notes = make(map[string]TransactionNote)
+	}
+	if notes.Refunds == nil {
+		notes.Refunds = make(map[string]RefundLink)
+	}
+	if notes.MerchantCategories == nil {
+		notes.MerchantCategories = make(map[string]LearnedCategory)
+	}
+
+	return notes
+}
+
+// Save writes the transaction annotations to disk
+func (n *TransactionNotes) Save() error {
+	if n == nil {
+		return fmt.Errorf("no cache directory configured")
+	}
+
+	data, err := json.MarshalIndent(n, "", "  ")
+	if err != nil {
+		return fmt.Errorf("error marshaling transaction notes: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(n.path), 0o755); err != nil {
+		return fmt.Errorf("error creating cache directory: %w", err)
+	}
+
+	if err := writeFileAtomic(n.path, data); err != nil {
+		return fmt.Errorf("error writing transaction notes: %w", err)
+	}
+
+	return nil
+}
+
+// learnCategory derives or strengthens the merchant's category rule from a transaction the user
+// categorized. A newer correction to another category replaces the rule. It reports whether the
+// rule changed.
+func (n *TransactionNotes) learnCategory(merchant, transactionID string, note TransactionNote) bool {
+	key := strings.ToLower(strings.TrimSpace(merchant))
+	if key == "" || note.Category == "" {
+		return false
+	}
+
+	learned, ok := n.MerchantCategories[key]
+	if ok && !strings.EqualFold(learned.Category, note.Category) {
+		if !note.UpdatedAt.After(learned.UpdatedAt) {
+			return false // Superseded by a newer correction
+		}
+		ok = false
+	}
+	if !ok {
+		learned = LearnedCategory{Category: note.Category}
+	}
+	for _, id := range learned.Transactions {
+		if id == transactionID {
+			return false
+		}
+	}
+
+	learned.Transactions = append(learned.Transactions, transactionID)
+	if note.UpdatedAt.After(learned.UpdatedAt) {
+		learned.UpdatedAt = note.UpdatedAt
+	}
+	n.MerchantCategories[key] = learned
+	return true
+}
+
+// forgetCategory removes a transaction from the learned rules, dropping rules it alone supported
+func (n *TransactionNotes) forgetCategory(transactionID string) {
+	for key, learned := range n.MerchantCategories {
+		for i, id := range learned.Transactions {
+			if id == transactionID {
+				learned.Transactions = append(learned.Transactions[:i], learned.Transactions[i+1:]...)
+				break
+			}
+		}
+		if len(learned.Transactions) == 0 {
+			delete(n.MerchantCategories, key)
+		} else {
+			n.MerchantCategories[key] = learned
+		}
+	}
+}
+
+// applyTransactionNotes copies annotations onto the matching transactions. Categories set by hand
+// teach the transaction's merchant its category, which is then applied to the merchant's other
+// transactions. It returns the number of learned rules that changed, to be saved by the caller.
+func applyTransactionNotes(accounts []Account, notes *TransactionNotes) int {
+	if notes == nil || (len(notes.Notes) == 0 && len(notes.MerchantCategories) == 0) {
+		return 0
+	}
+
+	learned := 0
+	for i := range accounts {
+		for j := range accounts[i].Transactions {
+			tx := &accounts[i].Transactions[j]
+			if note, ok := notes.Notes[tx.ID]; ok {
+				tx.Note = note.Note
+				tx.UserDescription = note.Description
+				tx.UserCategory = note.Category
+				if notes.learnCategory(tx.Merchant, tx.ID, note) {
+					learned++
+				}
+			}
+		}
+	}
+
+	for i := range accounts {
+		for j := range accounts[i].Transactions {
+			tx := &accounts[i].Transactions[j]
+			if rule, ok := notes.MerchantCategories[strings.ToLower(strings.TrimSpace(tx.Merchant))]; ok {
+				tx.LearnedCategory = rule.Category
+			}
+		}
+	}
+	return learned
+}
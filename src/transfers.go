@@ -0,0 +1,78 @@
+package main
+
+import (
+	"math"
+	"sort"
+
+	"github.com/rs/zerolog/log"
+)
+
+// transferMatchWindowDays is how far apart the two sides of a transfer may be dated
+const transferMatchWindowDays = 3
+
+// transferSide identifies a transaction by its position in the accounts slice
+type transferSide struct {
+	account int
+	tx      int
+}
+
+// markTransfers detects transfers between the given accounts, such as a credit card payment from
+// checking: an outflow and an inflow of the same amount on two different accounts within
+// transferMatchWindowDays. Both sides are marked so they can be left out of spending. Each
+// transaction is matched at most once, closest dates first.
+func markTransfers(accounts []Account) int {
+	var outflows, inflows []transferSide
+	for i := range accounts {
+		for j, tx := range accounts[i].Transactions {
+			if tx.Amount < 0 {
+				outflows = append(outflows, transferSide{i, j})
+			} else if tx.Amount > 0 {
+				inflows = append(inflows, transferSide{i, j})
+			}
+		}
+	}
+
+	type candidate struct {
+		out, in transferSide
+		gap     float64
+	}
+	var candidates []candidate
+	for _, out := range outflows {
+		outTx := accounts[out.account].Transactions[out.tx]
+		for _, in := range inflows {
+			if in.account == out.account {
+				continue
+			}
+			inTx := accounts[in.account].Transactions[in.tx]
+			if toMoney(inTx.Amount) != -toMoney(outTx.Amount) {
+				continue
+			}
+			gap := math.Abs(transactionTime(inTx).Sub(transactionTime(outTx)).Hours() / 24)
+			if gap <= transferMatchWindowDays {
+				candidates = append(candidates, candidate{out, in, gap})
+			}
+		}
+	}
+	sort.SliceStable(candidates, func(i, j int) bool {
+		return candidates[i].gap < candidates[j].gap
+	})
+
+	matched := 0
+	for _, c := range candidates {
+		outTx := &accounts[c.out.account].Transactions[c.out.tx]
+		inTx := &accounts[c.in.account].Transactions[c.in.tx]
+		if outTx.IsTransfer || inTx.IsTransfer {
+			continue
+		}
+		outTx.IsTransfer = true
+		inTx.IsTransfer = true
+		matched++
+
+		log.Debug().
+			Str("from_account", accounts[c.out.account].Name).
+			Str("to_account", accounts[c.in.account].Name).
+			Str("amount", (-toMoney(outTx.Amount)).String()).
+			Msg("Detected internal transfer")
+	}
+	return matched
+}
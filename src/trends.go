@@ -0,0 +1,89 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"text/tabwriter"
+	"time"
+)
+
+// TrendPoint is one month of spending in a trend series
+type TrendPoint struct {
+	Month         string `json:"month"` // YYYY-MM
+	Total         Money  `json:"total"`
+	Count         int    `json:"count"`
+	MovingAverage Money  `json:"moving_average"` // Average of this month and up to window-1 months before it
+}
+
+// buildTrend sums each calendar month's spending from start, optionally limited to a category
+// (case-insensitive), and computes a trailing moving average over window months. Transfers are
+// ignored and linked refunds are netted.
+func buildTrend(accounts []Account, category string, start time.Time, months, window int) []TrendPoint {
+	points := make([]TrendPoint, months)
+	index := make(map[string]int, months)
+	for i := range points {
+		month := time.Date(start.Year(), start.Month()+time.Month(i), 1, 0, 0, 0, 0, start.Location())
+		points[i].Month = month.Format("2006-01")
+		index[points[i].Month] = i
+	}
+
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if tx.Amount >= 0 || tx.IsTransfer {
+				continue
+			}
+			if category != "" && !categoryWithin(tx.Category, category) {
+				continue
+			}
+			net, ok := netExpense(tx)
+			if !ok {
+				continue
+			}
+			i, ok := index[transactionTime(tx).Format("2006-01")]
+			if !ok {
+				continue
+			}
+			points[i].Total -= toMoney(net.Amount)
+			points[i].Count++
+		}
+	}
+
+	if window < 1 {
+		window = 1
+	}
+	for i := range points {
+		first := i - window + 1
+		if first < 0 {
+			first = 0
+		}
+		var sum Money
+		for _, p := range points[first : i+1] {
+			sum += p.Total
+		}
+		points[i].MovingAverage = sum / Money(i+1-first)
+	}
+	return points
+}
+
+// writeTrendJSON writes the trend series as indented JSON
+func writeTrendJSON(w io.Writer, points []TrendPoint) error {
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(points)
+}
+
+// writeTrendText writes the trend series as a table
+func writeTrendText(w io.Writer, points []TrendPoint, category string, window int) error {
+	if category == "" {
+		category = "all categories"
+	}
+	fmt.Fprintf(w, "Monthly spending (%s)\n\n", category)
+
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintf(tw, "MONTH\tTRANSACTIONS\tTOTAL\t%d-MONTH AVERAGE\n", window)
+	for _, p := range points {
+		fmt.Fprintf(tw, "%s\t%d\t%s\t%s\n", p.Month, p.Count, p.Total, p.MovingAverage)
+	}
+	return tw.Flush()
+}
@@ -0,0 +1,140 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// creditLimitExtraKeys are the keys of the SimpleFin extra data that may hold an account's credit limit
+var creditLimitExtraKeys = []string{"credit_limit", "credit-limit", "creditLimit", "limit"}
+
+// providerCreditLimit returns the credit limit from the account's extra provider data, if present
+func providerCreditLimit(account Account) (Balance, bool) {
+	if account.Extra == nil {
+		return 0, false
+	}
+	for _, key := range creditLimitExtraKeys {
+		switch value := (*account.Extra)[key].(type) {
+		case float64:
+			return Balance(value), true
+		case string:
+			if parsed, err := strconv.ParseFloat(strings.TrimSpace(value), 64); err == nil {
+				return Balance(parsed), true
+			}
+		}
+	}
+	return 0, false
+}
+
+// applyCreditLimits sets each account's credit limit from its credit_limit setting, falling back
+// to the provider data
+func applyCreditLimits(accounts []Account, filterConfig *FilterConfig) {
+	for i := range accounts {
+		if limit := lookupAccountSettings(accounts[i], filterConfig).CreditLimit; limit > 0 {
+			accounts[i].CreditLimit = toMoney(Balance(limit))
+		} else if limit, ok := providerCreditLimit(accounts[i]); ok && limit > 0 {
+			accounts[i].CreditLimit = toMoney(limit)
+		}
+	}
+}
+
+// creditUtilization returns the percentage of the account's credit limit in use, and false if
+// the account has no known credit limit. Only an amount owed (negative balance) counts as used.
+func creditUtilization(account Account) (float64, bool) {
+	if account.CreditLimit <= 0 {
+		return 0, false
+	}
+	owed := -toMoney(account.Balance)
+	if owed < 0 {
+		owed = 0
+	}
+	return float64(owed) / float64(account.CreditLimit) * 100, true
+}
+
+// formatUtilization formats an account's credit utilization for listings, or "" without a limit
+func formatUtilization(account Account) string {
+	utilization, ok := creditUtilization(account)
+	if !ok {
+		return ""
+	}
+	return fmt.Sprintf("%.0f%% of %s", utilization, account.CreditLimit)
+}
+
+// checkCreditUtilization warns through the warning channel when an account's credit utilization
+// crosses the threshold. Each account is reported once until its utilization drops back below it.
+func checkCreditUtilization(settings *Settings, config RunConfig, accounts []Account, filterConfig *FilterConfig) error {
+	if settings.UtilizationThreshold <= 0 {
+		return nil
+	}
+	state := loadAccountState(settings.CacheDir)
+
+	var lines []string
+	var alerted []Account
+	var below []Account
+	for _, account := range accounts {
+		utilization, ok := creditUtilization(account)
+		if !ok {
+			continue
+		}
+		if utilization < settings.UtilizationThreshold {
+			below = append(below, account)
+			continue
+		}
+
+		log.Warn().
+			Str("account_name", account.Name).
+			Float64("utilization_percent", utilization).
+			Float64("threshold_percent", settings.UtilizationThreshold).
+			Msg("💳 Credit utilization is above the threshold")
+
+		if lookupAccountSettings(account, filterConfig).IsExcludedFrom(AccountScopeNotifications) {
+			continue
+		}
+		if state.AlreadyAlertedUtilization(account) {
+			log.Debug().Str("account_name", account.Name).Msg("High utilization already reported, skipping alert")
+			continue
+		}
+
+		lines = append(lines, fmt.Sprintf("- %s: %s of %s (%.0f%%)",
+			accountDisplayName(account), (-toMoney(account.Balance)).Format(), account.CreditLimit.Format(), utilization))
+		alerted = append(alerted, account)
+	}
+
+	if len(lines) > 0 && !config.DisableNotifications {
+		message := fmt.Sprintf("💳 **High credit utilization**\n\nThe following accounts are using more than %.0f%% of their credit limit:\n%s", settings.UtilizationThreshold, strings.Join(lines, "\n"))
+		if config.DryRun {
+			return renderNotification(settings, message, nil, nil, "warning", config.Notifications)
+		}
+		_, err := sendNotification(settings, message, nil, nil, "warning", config.Notifications)
+		if errors.Is(err, errNotificationDeferred) {
+			// Not marked as reported, so it is sent again by a later run
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("error sending credit utilization warning: %w", err)
+		}
+		now := time.Now().UTC()
+		for _, account := range alerted {
+			state.MarkUtilizationAlerted(account, now)
+		}
+	}
+
+	if config.DryRun || state == nil {
+		return nil
+	}
+
+	// Forget alerts for accounts back under the threshold so crossing it again is reported
+	for _, account := range below {
+		delete(state.UtilizationAlerts, account.ID)
+	}
+	if err := state.Save(); err != nil {
+		log.Warn().Err(err).Msg("Failed to save credit utilization alert state")
+	}
+
+	return nil
+}
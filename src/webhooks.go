@@ -0,0 +1,201 @@
+package main
+
+import (
+	"bytes"
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// Event types sent to webhooks and the event bus
+const (
+	WebhookEventTransactionCreated    = "transaction.created"
+	WebhookEventAccountBalanceChanged = "account.balance_changed"
+	WebhookEventSummaryGenerated      = "summary.generated"
+)
+
+// webhookAttempts is how many times a webhook delivery is tried before it is given up
+const webhookAttempts = 3
+
+// WebhookEvent is the JSON body POSTed to each webhook and published on the event bus
+type WebhookEvent struct {
+	Event     string      `json:"event"`
+	CreatedAt time.Time   `json:"created_at"`
+	Data      interface{} `json:"data"`
+}
+
+// webhookTransaction is the data of a transaction.created event
+type webhookTransaction struct {
+	AccountID   string      `json:"account_id"`
+	Account     string      `json:"account"`
+	Transaction Transaction `json:"transaction"`
+}
+
+// webhookBalanceChange is the data of an account.balance_changed event
+type webhookBalanceChange struct {
+	AccountID       string `json:"account_id"`
+	Account         string `json:"account"`
+	PreviousBalance string `json:"previous_balance"`
+	Balance         string `json:"balance"`
+	BalanceDate     int64  `json:"balance_date"`
+}
+
+// webhookSummary is the data of a summary.generated event
+type webhookSummary struct {
+	Start   string `json:"start"`
+	End     string `json:"end"`
+	Profile string `json:"profile,omitempty"`
+	Summary string `json:"summary"`
+}
+
+// webhookEnabled reports whether any webhook is configured for the event
+func webhookEnabled(settings *Settings, event string) bool {
+	if len(settings.WebhookURLs) == 0 {
+		return false
+	}
+	if len(settings.WebhookEvents) == 0 {
+		return true
+	}
+	for _, enabled := range settings.WebhookEvents {
+		if enabled == event {
+			return true
+		}
+	}
+	return false
+}
+
+// eventEnabled reports whether the event is sent anywhere: to a webhook or the event bus
+func eventEnabled(settings *Settings, event string) bool {
+	return webhookEnabled(settings, event) || settings.EventBusURL != ""
+}
+
+// signWebhookPayload returns the hex HMAC-SHA256 of the body with the webhook secret
+func signWebhookPayload(secret Secret, body []byte) string {
+	mac := hmac.New(sha256.New, []byte(secret.Reveal()))
+	mac.Write(body)
+	return hex.EncodeToString(mac.Sum(nil))
+}
+
+// emitEvent publishes the event on the event bus and sends it to every configured webhook. Each
+// webhook delivery is retried with exponential backoff on network errors, 429 and 5xx responses;
+// failures are logged and don't affect the run.
+func emitEvent(settings *Settings, event string, data interface{}) {
+	if !eventEnabled(settings, event) {
+		return
+	}
+
+	body, err := json.Marshal(WebhookEvent{Event: event, CreatedAt: time.Now().UTC(), Data: data})
+	if err != nil {
+		log.Error().Err(err).Str("event", event).Msg("Failed to encode event")
+		return
+	}
+
+	if settings.EventBusURL != "" {
+		if err := publishEvent(settings, event, body); err != nil {
+			log.Error().Err(err).Stringer("url", settings.EventBusURL).Str("event", event).Msg("❌ Event bus publish failed")
+		} else {
+			log.Debug().Str("event", event).Msg("Event published")
+		}
+	}
+
+	if !webhookEnabled(settings, event) {
+		return
+	}
+	for _, target := range settings.WebhookURLs {
+		if err := deliverWebhook(settings, target, event, body); err != nil {
+			log.Error().Err(err).Stringer("url", target).Str("event", event).Msg("❌ Webhook delivery failed")
+			continue
+		}
+		log.Debug().Stringer("url", target).Str("event", event).Msg("Webhook delivered")
+	}
+}
+
+// deliverWebhook POSTs the event body to a webhook, retrying transient failures
+func deliverWebhook(settings *Settings, target SecretURL, event string, body []byte) error {
+	client := newHTTPClient(10 * time.Second)
+	delay := time.Second
+
+	var lastErr error
+	for attempt := 1; attempt <= webhookAttempts; attempt++ {
+		if attempt > 1 {
+			time.Sleep(delay)
+			delay *= 2
+		}
+
+		req, err := http.NewRequest(http.MethodPost, target.Reveal(), bytes.NewReader(body))
+		if err != nil {
+			return fmt.Errorf("error creating request: %w", err)
+		}
+		req.Header.Set("Content-Type", "application/json")
+		req.Header.Set("X-Finance-Tracker-Event", event)
+		if settings.WebhookSecret != "" {
+			req.Header.Set("X-Finance-Tracker-Signature", "sha256="+signWebhookPayload(settings.WebhookSecret, body))
+		}
+
+		resp, err := client.Do(req)
+		if err != nil {
+			lastErr = fmt.Errorf("error sending webhook: %w", err)
+			continue
+		}
+		respBody, _ := io.ReadAll(resp.Body)
+		resp.Body.Close()
+
+		if resp.StatusCode >= 200 && resp.StatusCode < 300 {
+			return nil
+		}
+		lastErr = fmt.Errorf("webhook failed with status %d: %s", resp.StatusCode, strings.TrimSpace(string(respBody)))
+		if resp.StatusCode != http.StatusTooManyRequests && resp.StatusCode < 500 {
+			return lastErr
+		}
+	}
+	return lastErr
+}
+
+// emitAccountEvents sends transaction.created for the transactions not seen by an earlier run
+// and account.balance_changed for accounts whose balance differs from the previous run's
+func emitAccountEvents(settings *Settings, accounts []Account, unseen map[string]bool) {
+	for _, account := range accounts {
+		for _, tx := range account.Transactions {
+			if unseen[tx.ID] {
+				emitEvent(settings, WebhookEventTransactionCreated, webhookTransaction{
+					AccountID:   account.ID,
+					Account:     accountDisplayName(account),
+					Transaction: tx,
+				})
+			}
+		}
+	}
+
+	if !eventEnabled(settings, WebhookEventAccountBalanceChanged) {
+		return
+	}
+	state := loadAccountState(settings.CacheDir)
+	if state == nil {
+		return
+	}
+	for _, account := range accounts {
+		previous, known := state.WebhookBalances[account.ID]
+		state.WebhookBalances[account.ID] = account.Balance
+		if !known || toMoney(previous) == toMoney(account.Balance) {
+			continue
+		}
+		emitEvent(settings, WebhookEventAccountBalanceChanged, webhookBalanceChange{
+			AccountID:       account.ID,
+			Account:         accountDisplayName(account),
+			PreviousBalance: toMoney(previous).String(),
+			Balance:         toMoney(account.Balance).String(),
+			BalanceDate:     account.BalanceDate,
+		})
+	}
+	if err := state.Save(); err != nil {
+		log.Warn().Err(err).Msg("Failed to save webhook balances")
+	}
+}
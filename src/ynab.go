@@ -0,0 +1,162 @@
+package main
+
+import (
+	"bytes"
+	"encoding/csv"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math"
+	"net/http"
+	"sort"
+	"time"
+
+	"github.com/rs/zerolog/log"
+)
+
+// ynabAPIURL is the base URL of the YNAB API
+const ynabAPIURL = "https://api.ynab.com/v1"
+
+// YNABTransaction is a transaction in the format accepted by the YNAB API
+type YNABTransaction struct {
+	AccountID string `json:"account_id"`
+	Date      string `json:"date"`
+	Amount    int64  `json:"amount"` // Milliunits (1000 = 1.00)
+	PayeeName string `json:"payee_name,omitempty"`
+	Cleared   string `json:"cleared"`
+	ImportID  string `json:"import_id"`
+}
+
+// ynabMilliunits converts an amount to YNAB milliunits after rounding it to the report currency
+func ynabMilliunits(amount Balance) int64 {
+	scale := int64(math.Pow10(3 - minorUnits(moneyConfig.Currency)))
+	return int64(toMoney(amount)) * scale
+}
+
+// truncate shortens s to at most n runes
+func truncate(s string, n int) string {
+	runes := []rune(s)
+	if len(runes) <= n {
+		return s
+	}
+	return string(runes[:n])
+}
+
+// writeYNABCSV writes transactions in YNAB's CSV import format (Date, Payee, Memo, Outflow, Inflow)
+func writeYNABCSV(w io.Writer, accounts []Account) error {
+	writer := csv.NewWriter(w)
+	if err := writer.Write([]string{"Date", "Payee", "Memo", "Outflow", "Inflow"}); err != nil {
+		return fmt.Errorf("error writing CSV header: %w", err)
+	}
+
+	for _, account := range accounts {
+		transactions := make([]Transaction, len(account.Transactions))
+		copy(transactions, account.Transactions)
+		sort.SliceStable(transactions, func(i, j int) bool {
+			return transactionTime(transactions[i]).Before(transactionTime(transactions[j]))
+		})
+
+		for _, tx := range transactions {
+			amount := toMoney(tx.Amount)
+			outflow, inflow := "", ""
+			if amount < 0 {
+				outflow = (-amount).String()
+			} else {
+				inflow = amount.String()
+			}
+
+			record := []string{
+				transactionTime(tx).Format("01/02/2006"),
+				tx.Description,
+				account.Name,
+				outflow,
+				inflow,
+			}
+			if err := writer.Write(record); err != nil {
+				return fmt.Errorf("error writing CSV record: %w", err)
+			}
+		}
+	}
+
+	writer.Flush()
+	return writer.Error()
+}
+
+// buildYNABTransactions converts transactions for accounts mapped to a YNAB account via
+// ynab_account_id. Unmapped accounts are skipped.
+func buildYNABTransactions(accounts []Account, filterConfig *FilterConfig) []YNABTransaction {
+	var transactions []YNABTransaction
+	for _, account := range accounts {
+		ynabAccountID := lookupAccountSettings(account, filterConfig).YNABAccountID
+		if ynabAccountID == "" {
+			log.Warn().Str("account_name", account.Name).Msg("No ynab_account_id configured, skipping account")
+			continue
+		}
+
+		for _, tx := range account.Transactions {
+			cleared := "cleared"
+			if getTransactionStatus(tx) == "pending" {
+				cleared = "uncleared"
+			}
+
+			transactions = append(transactions, YNABTransaction{
+				AccountID: ynabAccountID,
+				Date:      transactionTime(tx).Format("2006-01-02"),
+				Amount:    ynabMilliunits(tx.Amount),
+				PayeeName: truncate(tx.Description, 200),
+				Cleared:   cleared,
+				ImportID:  stableTransactionID(account.ID, tx.ID), // YNAB limits import_id to 36 characters
+			})
+		}
+	}
+	return transactions
+}
+
+// pushYNABTransactions creates the transactions in the configured YNAB budget. YNAB ignores
+// transactions whose import_id already exists, so repeated pushes are safe. Returns the number
+// of created and duplicate transactions.
+func pushYNABTransactions(settings *Settings, transactions []YNABTransaction) (int, int, error) {
+	if settings.YNABToken == "" || settings.YNABBudgetID == "" {
+		return 0, 0, fmt.Errorf("YNAB_TOKEN and YNAB_BUDGET_ID must be set")
+	}
+	if len(transactions) == 0 {
+		return 0, 0, nil
+	}
+
+	body, err := json.Marshal(map[string]interface{}{"transactions": transactions})
+	if err != nil {
+		return 0, 0, fmt.Errorf("error marshaling YNAB transactions: %w", err)
+	}
+
+	url := fmt.Sprintf("%s/budgets/%s/transactions", ynabAPIURL, settings.YNABBudgetID)
+	req, err := http.NewRequest(http.MethodPost, url, bytes.NewBuffer(body))
+	if err != nil {
+		return 0, 0, fmt.Errorf("error creating request: %w", err)
+	}
+	req.Header.Set("Authorization", fmt.Sprintf("Bearer %s", settings.YNABToken.Reveal()))
+	req.Header.Set("Content-Type", "application/json")
+
+	client := newHTTPClient(60 * time.Second)
+	resp, err := client.Do(req)
+	if err != nil {
+		return 0, 0, fmt.Errorf("error sending request to YNAB: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusCreated && resp.StatusCode != http.StatusOK {
+		respBody, _ := io.ReadAll(resp.Body)
+		return 0, 0, fmt.Errorf("YNAB API returned status %d: %s", resp.StatusCode, string(respBody))
+	}
+
+	var result struct {
+		Data struct {
+			TransactionIDs     []string `json:"transaction_ids"`
+			DuplicateImportIDs []string `json:"duplicate_import_ids"`
+		} `json:"data"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&result); err != nil {
+		return 0, 0, fmt.Errorf("error decoding YNAB response: %w", err)
+	}
+
+	return len(result.Data.TransactionIDs), len(result.Data.DuplicateImportIDs), nil
+}